@@ -0,0 +1,76 @@
+//! Instructions per second of the emulator on a whole program, rather than
+//! one `Word` op at a time like `ternary`'s benches.
+//!
+//! `cargo bench -p JX_01`
+
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use JX_01::{
+    cpu::{self, profile::{Profiler, SymbolMap}},
+    isa::{
+        code::DecEncExt, registers::*, Instr, ADD_T, ALU_CTRL_R_RI, ALU_CTRL_R_RR, BNE_T,
+        CALL_CTRL_R, CMP_T, MUL_T, STRE_T, SUB_T,
+    },
+};
+use ternary::word::Word;
+
+/// Loop iterations, each a call and 10 instructions
+const N: isize = 2000;
+/// Where the squares are stored, past the program
+const DATA: isize = 300;
+
+/// Stores the squares of `N` down to 1 from `DATA` up, calling a function
+/// for each one, so the loop runs ALU ops, a branch, a call and a store
+fn squares() -> Vec<Instr> {
+    use Instr::*;
+
+    let (rr, ri) = (ALU_CTRL_R_RR, ALU_CTRL_R_RI);
+    let program = vec![
+        /* 00 */ OPRI(ri, ADD_T, N1, N.into()),
+        /* 03 */ OPRI(ri, ADD_T, N4, DATA.into()),
+        // loop:
+        /* 06 */ CALL(N0, CALL_CTRL_R, 27.into()),
+        /* 09 */ OPRR(rr, STRE_T, N4, N3, Word::ZERO),
+        /* 12 */ OPRI(ri, ADD_T, N4, 3.into()),
+        /* 15 */ OPRI(ri, SUB_T, N1, 1.into()),
+        /* 18 */ OPRI(ri, CMP_T, N1, Word::ZERO),
+        /* 21 */ OPRI(ri, BNE_T, N0, 6.into()),
+        /* 24 */ HALT,
+        // square:
+        /* 27 */ OPRI(ri, MUL_T, N3, Word::ZERO),
+        /* 30 */ OPRR(rr, ADD_T, N3, N1, Word::ZERO),
+        /* 33 */ OPRR(rr, MUL_T, N3, N1, Word::ZERO),
+        /* 36 */ RET,
+    ];
+    program.check();
+    program
+}
+
+fn ips(c: &mut Criterion) {
+    let program = squares();
+
+    let mut cpu = cpu::JX_01::new();
+    cpu.import_instrs(&program);
+    cpu.enable_profiling(Profiler::new(SymbolMap::new()));
+    cpu.run_program();
+    let instrs = cpu.take_profile().unwrap().total();
+    assert_eq!(instrs, 2 + 10 * N as u64 + 1);
+
+    let mut group = c.benchmark_group("ips");
+    group.throughput(Throughput::Elements(instrs));
+    group.sample_size(10);
+    group.bench_function("squares", |b| {
+        b.iter_batched(
+            || {
+                let mut cpu = cpu::JX_01::new();
+                cpu.import_instrs(&program);
+                cpu
+            },
+            |mut cpu| cpu.run_program(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, ips);
+criterion_main!(benches);
@@ -0,0 +1,684 @@
+//! Data driven conformance suite for the JX-01.
+//!
+//! Every case is a small program along with the registers and memory it starts with, and the
+//! registers, memory and flags it must end with. `HALT` is appended to every program, and
+//! nothing here touches the GPU, so the whole suite runs headlessly.
+//!
+//! Data lives at `DATA` and up, well past the end of any program in here.
+//!
+//! Not covered: `DTI`, `STI`, `WFI`, `INTM`, `INTE` and `INTS` are still `todo!()`, and `EGPU`,
+//! `LVB` and `EGEL` need a window.
+
+use ternary::{floating::Floating, trits::Trit, word::Word};
+
+use crate::{
+    cpu::{CSR, JX_01},
+    isa::{code::DecEncExt, registers::*, Control, Instr, Op, *},
+};
+
+const DATA: isize = 300;
+
+struct Case {
+    name: &'static str,
+    program: Vec<Instr>,
+    registers: Vec<(Register, Word)>,
+    memory: Vec<(isize, Word)>,
+    expect_registers: Vec<(Register, Word)>,
+    expect_memory: Vec<(isize, Word)>,
+    /// Carry, sign and parity
+    expect_flags: Option<[Trit; 3]>,
+}
+
+impl Case {
+    fn new(name: &'static str, program: Vec<Instr>) -> Case {
+        Case {
+            name,
+            program,
+            registers: Vec::new(),
+            memory: Vec::new(),
+            expect_registers: Vec::new(),
+            expect_memory: Vec::new(),
+            expect_flags: None,
+        }
+    }
+
+    fn reg(mut self, reg: Register, val: impl Into<Word>) -> Case {
+        self.registers.push((reg, val.into()));
+        self
+    }
+
+    fn mem(mut self, addr: isize, val: impl Into<Word>) -> Case {
+        self.memory.push((addr, val.into()));
+        self
+    }
+
+    fn expect_reg(mut self, reg: Register, val: impl Into<Word>) -> Case {
+        self.expect_registers.push((reg, val.into()));
+        self
+    }
+
+    fn expect_mem(mut self, addr: isize, val: impl Into<Word>) -> Case {
+        self.expect_memory.push((addr, val.into()));
+        self
+    }
+
+    fn expect_flags(mut self, carry: Trit, sign: Trit, parity: Trit) -> Case {
+        self.expect_flags = Some([carry, sign, parity]);
+        self
+    }
+
+    fn run(&self) {
+        let mut program = self.program.clone();
+        program.push(Instr::HALT);
+        program.check();
+
+        let mut cpu = JX_01::new();
+        cpu.import_instrs(&program);
+        for &(addr, val) in &self.memory {
+            *cpu.memory.get_physical_word_mut(addr.into()) = val;
+        }
+        for &(reg, val) in &self.registers {
+            cpu.registers.set_word(reg, val);
+        }
+
+        cpu.run_program();
+
+        for &(reg, val) in &self.expect_registers {
+            assert_eq!(cpu.registers.get_word(reg), val, "{}: register {:?}", self.name, reg);
+        }
+        for &(addr, val) in &self.expect_memory {
+            assert_eq!(*cpu.memory.get_physical_word(addr.into()), val, "{}: memory at {}", self.name, addr);
+        }
+        if let Some([carry, sign, parity]) = self.expect_flags {
+            let csr = cpu.status.csr;
+            assert_eq!(csr.get_carry(), carry, "{}: carry flag", self.name);
+            assert_eq!(csr.get_sign(), sign, "{}: sign flag", self.name);
+            assert_eq!(csr.get_parity(), parity, "{}: parity flag", self.name);
+        }
+    }
+}
+
+fn run_all(cases: impl IntoIterator<Item = Case>) {
+    for case in cases {
+        case.run();
+    }
+}
+
+/// `[R] op imm` and `[R] op ([R] + imm)` for one control tribble pair.
+/// Every value fits in a tryte so the same table holds for both register sizes.
+fn alu_cases(rr: Control, ri: Control) -> Vec<Case> {
+    use Instr::*;
+
+    vec![
+        Case::new("add ri", vec![OPRI(ri, ADD_T, N1, 5.into())])
+            .reg(N1, 7).expect_reg(N1, 12),
+        Case::new("add rr", vec![OPRR(rr, ADD_T, N1, N2, 2.into())])
+            .reg(N1, 7).reg(N2, -3).expect_reg(N1, 6).expect_reg(N2, -3),
+        Case::new("add to zero register", vec![OPRI(ri, ADD_T, N0, 5.into())])
+            .expect_reg(N0, 0),
+        Case::new("sub ri", vec![OPRI(ri, SUB_T, N1, 5.into())])
+            .reg(N1, 3).expect_reg(N1, -2),
+        Case::new("sub rr", vec![OPRR(rr, SUB_T, N1, N2, 1.into())])
+            .reg(N1, 3).reg(N2, 4).expect_reg(N1, -2),
+        Case::new("mul ri", vec![OPRI(ri, MUL_T, N1, (-4).into())])
+            .reg(N1, 6).expect_reg(N1, -24),
+        Case::new("mul rr", vec![OPRR(rr, MUL_T, N1, N2, 1.into())])
+            .reg(N1, -6).reg(N2, -5).expect_reg(N1, 24),
+        Case::new("qot ri", vec![OPRI(ri, QOT_T, N1, 2.into())])
+            .reg(N1, -7).expect_reg(N1, -4),
+        Case::new("qot rr", vec![OPRR(rr, QOT_T, N1, N2, Word::ZERO)])
+            .reg(N1, 7).reg(N2, -2).expect_reg(N1, -3),
+        Case::new("rem ri", vec![OPRI(ri, REM_T, N1, 2.into())])
+            .reg(N1, -7).expect_reg(N1, 1),
+        Case::new("rem rr", vec![OPRR(rr, REM_T, N1, N2, Word::ZERO)])
+            .reg(N1, 7).reg(N2, -2).expect_reg(N1, 1),
+        // 5 = 1TT, 3 = 010
+        Case::new("and ri", vec![OPRI(ri, AND_T, N1, 3.into())])
+            .reg(N1, 5).expect_reg(N1, -4),
+        Case::new("and rr", vec![OPRR(rr, AND_T, N1, N2, 1.into())])
+            .reg(N1, 5).reg(N2, 2).expect_reg(N1, -4),
+        Case::new("or ri", vec![OPRI(ri, OR_T, N1, 3.into())])
+            .reg(N1, 5).expect_reg(N1, 12),
+        Case::new("or rr", vec![OPRR(rr, OR_T, N1, N2, 1.into())])
+            .reg(N1, 5).reg(N2, 2).expect_reg(N1, 12),
+        Case::new("sft left", vec![OPRI(ri, SFT_T, N1, 2.into())])
+            .reg(N1, -5).expect_reg(N1, -45),
+        // 10 = 101
+        Case::new("sft right", vec![OPRR(rr, SFT_T, N1, N2, Word::NONE)])
+            .reg(N1, 10).expect_reg(N1, 3),
+        Case::new("sft zero", vec![OPRI(ri, SFT_T, N1, Word::ZERO)])
+            .reg(N1, -5).expect_reg(N1, -5),
+        Case::new("sft past the end", vec![OPRR(rr, SFT_T, N1, N2, Word::ZERO)])
+            .reg(N1, -5).reg(N2, 40).expect_reg(N1, 0),
+        Case::new("not ri", vec![OPRI(ri, NOT_T, N1, Word::ZERO)])
+            .reg(N1, 42).expect_reg(N1, -42),
+        Case::new("not rr", vec![OPRR(rr, NOT_T, N1, N2, Word::ZERO)])
+            .reg(N1, -42).reg(N2, 7).expect_reg(N1, 42),
+        Case::new("rot left", vec![OPRI(ri, ROT_T, N1, 1.into())])
+            .reg(N1, 1).expect_reg(N1, 3),
+        Case::new("rot right", vec![OPRR(rr, ROT_T, N1, N2, Word::ZERO)])
+            .reg(N1, 9).reg(N2, -2).expect_reg(N1, 1),
+        Case::new("rot zero", vec![OPRI(ri, ROT_T, N1, Word::ZERO)])
+            .reg(N1, 9).expect_reg(N1, 9),
+        // Past a full turn of either size
+        Case::new("rot 28", vec![OPRI(ri, ROT_T, N1, 28.into())])
+            .reg(N1, 1).expect_reg(N1, 3),
+        Case::new("rot -28", vec![OPRI(ri, ROT_T, N1, (-28).into())])
+            .reg(N1, 9).expect_reg(N1, 3),
+    ]
+}
+
+#[test]
+fn alu_word() {
+    run_all(alu_cases(ALU_CTRL_R_RR, ALU_CTRL_R_RI));
+}
+
+#[test]
+fn alu_tryte() {
+    run_all(alu_cases(ALU_CTRL_T_RR, ALU_CTRL_T_RI));
+}
+
+#[test]
+fn alu_tryte_truncates() {
+    use Instr::*;
+
+    // 3^9
+    const TRYTE_SPAN: isize = 19683;
+    const TRYTE_MAX: isize = 9841;
+
+    run_all([
+        Case::new("tryte add wraps", vec![OPRI(ALU_CTRL_T_RI, ADD_T, N1, 1.into())])
+            .reg(N1, TRYTE_MAX).expect_reg(N1, -TRYTE_MAX),
+        Case::new("word add does not wrap", vec![OPRI(ALU_CTRL_R_RI, ADD_T, N1, 1.into())])
+            .reg(N1, TRYTE_MAX).expect_reg(N1, TRYTE_MAX + 1),
+        Case::new("tryte reads low trits", vec![OPRR(ALU_CTRL_T_RR, ADD_T, N1, N2, Word::ZERO)])
+            .reg(N1, TRYTE_SPAN + 5).reg(N2, 2 * TRYTE_SPAN - 1).expect_reg(N1, 4),
+        Case::new("tryte mul wraps", vec![OPRI(ALU_CTRL_T_RI, MUL_T, N1, 81.into())])
+            .reg(N1, 243).expect_reg(N1, 0),
+        // TRYTE_MAX + 1 wraps to -TRYTE_MAX before dividing
+        Case::new("tryte operand wraps", vec![OPRR(ALU_CTRL_T_RR, QOT_T, N1, N2, 1.into())])
+            .reg(N1, -TRYTE_MAX).reg(N2, TRYTE_MAX).expect_reg(N1, 1),
+        // So does the immediate, 3^9 + 1 is 1 and 3^9 + 3 is 3
+        Case::new("tryte ri qot wraps", vec![OPRI(ALU_CTRL_T_RI, QOT_T, N1, (TRYTE_SPAN + 1).into())])
+            .reg(N1, 7).expect_reg(N1, 7),
+        Case::new("tryte ri rem wraps", vec![OPRI(ALU_CTRL_T_RI, REM_T, N1, (TRYTE_SPAN + 3).into())])
+            .reg(N1, 6).expect_reg(N1, 0),
+        Case::new("tryte sft drops trits", vec![OPRI(ALU_CTRL_T_RI, SFT_T, N1, 1.into())])
+            .reg(N1, 6562).expect_reg(N1, 3),
+        // 3^8 is the top trit of a tryte, which turns round to the bottom
+        Case::new("tryte rot wraps", vec![OPRI(ALU_CTRL_T_RI, ROT_T, N1, 1.into())])
+            .reg(N1, 6561).expect_reg(N1, 1),
+        Case::new("tryte rot wraps rr", vec![OPRR(ALU_CTRL_T_RR, ROT_T, N1, N2, Word::ZERO)])
+            .reg(N1, 1).reg(N2, -1).expect_reg(N1, 6561),
+        Case::new("word rot keeps trits", vec![OPRI(ALU_CTRL_R_RI, ROT_T, N1, 1.into())])
+            .reg(N1, 6561).expect_reg(N1, 19683),
+        Case::new("tryte load", vec![OPRI(ALU_CTRL_T_RI, LOAD_T, N1, DATA.into())])
+            .mem(DATA, TRYTE_SPAN - 1).expect_reg(N1, -1),
+        Case::new("tryte store", vec![OPRR(ALU_CTRL_T_RR, STRE_T, N1, N2, Word::ZERO)])
+            .reg(N1, DATA).reg(N2, TRYTE_SPAN + 1).expect_mem(DATA, 1),
+        Case::new("tryte pop", vec![
+            OPRI(ALU_CTRL_R_RI, PUSH_T, N1, Word::ZERO),
+            OPRI(ALU_CTRL_T_RI, POP_T, N2, Word::ZERO),
+        ])
+            .reg(N1, TRYTE_SPAN * 2 + 7).expect_reg(N2, 7),
+        Case::new("tryte in", vec![OUT(N1, OUT_CTRL_R, 2.into()), IN(N2, IN_CTRL_T, 2.into())])
+            .reg(N1, TRYTE_SPAN - 2).expect_reg(N2, -2),
+    ]);
+}
+
+#[test]
+fn compare_flags() {
+    use Instr::*;
+    use Trit::*;
+
+    let cmp = |name, a: isize, b: Word, carry, sign, parity| {
+        Case::new(name, vec![OPRR(ALU_CTRL_R_RR, CMP_T, N1, N2, Word::ZERO)])
+            .reg(N1, a)
+            .reg(N2, b)
+            // Compare never writes back
+            .expect_reg(N1, a)
+            .expect_flags(carry, sign, parity)
+    };
+
+    run_all([
+        cmp("cmp equal", 4, 4.into(), Zero, Zero, Zero),
+        // 2 = 1T
+        cmp("cmp greater", 5, 3.into(), Zero, POne, NOne),
+        // -2 = T1
+        cmp("cmp less", 3, 5.into(), Zero, NOne, POne),
+        // 3 = 10
+        cmp("cmp parity zero", 1, (-2).into(), Zero, POne, Zero),
+        cmp("cmp overflow", 1, Word::MIN, POne, NOne, NOne),
+        cmp("cmp underflow", -1, Word::MAX, NOne, POne, POne),
+        Case::new("cmp ri", vec![OPRI(ALU_CTRL_R_RI, CMP_T, N1, 9.into())])
+            .reg(N1, 5)
+            .expect_flags(Zero, NOne, NOne),
+        Case::new("cmp tryte", vec![OPRI(ALU_CTRL_T_RI, CMP_T, N1, (-9).into())])
+            .reg(N1, 5)
+            .expect_flags(Zero, POne, NOne),
+        // 9842 = 1TTTTTTTTT
+        Case::new("cmp tryte overflow", vec![OPRR(ALU_CTRL_T_RR, CMP_T, N1, N2, Word::ZERO)])
+            .reg(N1, 9841)
+            .reg(N2, -1)
+            .expect_flags(POne, NOne, NOne),
+        // -9843 = T111111110
+        Case::new("cmp tryte underflow", vec![OPRI(ALU_CTRL_T_RI, CMP_T, N1, 2.into())])
+            .reg(N1, -9841)
+            .expect_flags(NOne, POne, Zero),
+        Case::new("cmp tryte ignores high trits", vec![OPRR(ALU_CTRL_T_RR, CMP_T, N1, N2, Word::ZERO)])
+            .reg(N1, 19683 + 4)
+            .reg(N2, 4)
+            .expect_flags(Zero, Zero, Zero),
+        // The immediate too, so there's no carry out of 0 - 3^9
+        Case::new("cmp tryte ri ignores high trits", vec![OPRI(ALU_CTRL_T_RI, CMP_T, N1, 19683.into())])
+            .reg(N1, 0)
+            .expect_flags(Zero, Zero, Zero),
+    ]);
+}
+
+#[test]
+fn carry_flags() {
+    use Instr::*;
+    use Trit::*;
+
+    // 3^20
+    const BIG: isize = 3486784401;
+
+    run_all([
+        Case::new("add carries", vec![OPRI(ALU_CTRL_R_RI, ADD_T, N1, 1.into())])
+            .reg(N1, Word::MAX)
+            .expect_reg(N1, Word::MIN)
+            .expect_flags(POne, Zero, Zero),
+        Case::new("add rr carries", vec![OPRR(ALU_CTRL_R_RR, ADD_T, N1, N2, Word::ZERO)])
+            .reg(N1, Word::MIN)
+            .reg(N2, Word::MIN)
+            .expect_reg(N1, Word::MIN + Word::MIN)
+            .expect_flags(NOne, Zero, Zero),
+        Case::new("sub borrows", vec![OPRR(ALU_CTRL_R_RR, SUB_T, N1, N2, Word::ZERO)])
+            .reg(N1, Word::MIN)
+            .reg(N2, 1)
+            .expect_reg(N1, Word::MAX)
+            .expect_flags(NOne, Zero, Zero),
+        Case::new("carry clears", vec![
+            OPRI(ALU_CTRL_R_RI, ADD_T, N1, 1.into()),
+            OPRI(ALU_CTRL_R_RI, SUB_T, N2, 1.into()),
+        ])
+            .reg(N1, Word::MAX)
+            .expect_reg(N2, -1)
+            .expect_flags(Zero, Zero, Zero),
+        Case::new("mul carries", vec![OPRR(ALU_CTRL_R_RR, MUL_T, N1, N2, Word::ZERO)])
+            .reg(N1, BIG)
+            .reg(N2, -BIG)
+            .expect_reg(N1, 0)
+            .expect_flags(NOne, Zero, Zero),
+        Case::new("tryte add carries", vec![OPRI(ALU_CTRL_T_RI, ADD_T, N1, 2.into())])
+            .reg(N1, 9841)
+            .expect_reg(N1, -9840)
+            .expect_flags(POne, Zero, Zero),
+        Case::new("tryte mul carries", vec![OPRI(ALU_CTRL_T_RI, MUL_T, N1, 81.into())])
+            .reg(N1, -243)
+            .expect_reg(N1, 0)
+            .expect_flags(NOne, Zero, Zero),
+        Case::new("word add ignores tryte carry", vec![OPRI(ALU_CTRL_R_RI, ADD_T, N1, 2.into())])
+            .reg(N1, 9841)
+            .expect_reg(N1, 9843)
+            .expect_flags(Zero, Zero, Zero),
+        // Sign and parity only come from CMP
+        Case::new("add keeps sign", vec![
+            OPRI(ALU_CTRL_R_RI, CMP_T, N1, 1.into()),
+            OPRI(ALU_CTRL_R_RI, ADD_T, N1, 1.into()),
+        ])
+            .reg(N1, Word::MAX)
+            .expect_flags(POne, POne, Zero),
+    ]);
+}
+
+#[test]
+fn extended_alu() {
+    use Instr::*;
+    use Trit::*;
+
+    // 3^20 and 3^13
+    const BIG: isize = 3486784401;
+    const HIGH: isize = 1594323;
+
+    run_all([
+        // (N2:N1) += (N4:N3)
+        Case::new("adc chain", vec![
+            OPRR(ALU_CTRL_R_RR, ADD_T, N1, N3, Word::ZERO),
+            OPRR(EXT_CTRL_R_RR, ADC_T, N2, N4, Word::ZERO),
+        ])
+            .reg(N1, Word::MAX).reg(N2, 5)
+            .reg(N3, 1).reg(N4, 6)
+            .expect_reg(N1, Word::MIN)
+            .expect_reg(N2, 12)
+            .expect_flags(Zero, Zero, Zero),
+        // (N2:N1) -= (N4:N3)
+        Case::new("sbb chain", vec![
+            OPRR(ALU_CTRL_R_RR, SUB_T, N1, N3, Word::ZERO),
+            OPRR(EXT_CTRL_R_RR, SBB_T, N2, N4, Word::ZERO),
+        ])
+            .reg(N1, Word::MIN).reg(N2, 5)
+            .reg(N3, 1).reg(N4, 2)
+            .expect_reg(N1, Word::MAX)
+            .expect_reg(N2, 2),
+        Case::new("adc carries out", vec![OPRI(EXT_CTRL_R_RI, ADC_T, N1, 1.into())])
+            .reg(N1, Word::MAX)
+            .expect_reg(N1, Word::MIN)
+            .expect_flags(POne, Zero, Zero),
+        Case::new("sbb borrows out", vec![OPRR(EXT_CTRL_R_RR, SBB_T, N1, N2, 1.into())])
+            .reg(N1, Word::MIN)
+            .expect_reg(N1, Word::MAX)
+            .expect_flags(NOne, Zero, Zero),
+        Case::new("adc tryte chain", vec![
+            OPRI(ALU_CTRL_T_RI, ADD_T, N1, 1.into()),
+            OPRI(EXT_CTRL_T_RI, ADC_T, N2, Word::ZERO),
+        ])
+            .reg(N1, 9841)
+            .expect_reg(N1, -9841)
+            .expect_reg(N2, 1),
+        Case::new("mulh", vec![OPRR(EXT_CTRL_R_RR, MULH_T, N1, N2, Word::ZERO)])
+            .reg(N1, BIG).reg(N2, BIG)
+            .expect_reg(N1, HIGH),
+        Case::new("mulh negative", vec![OPRR(EXT_CTRL_R_RR, MULH_T, N1, N2, Word::ZERO)])
+            .reg(N1, -BIG).reg(N2, BIG)
+            .expect_reg(N1, -HIGH),
+        Case::new("mulh small", vec![OPRI(EXT_CTRL_R_RI, MULH_T, N1, 364.into())])
+            .reg(N1, 364)
+            .expect_reg(N1, 0),
+        Case::new("mulh tryte", vec![OPRI(EXT_CTRL_T_RI, MULH_T, N1, 81.into())])
+            .reg(N1, 243)
+            .expect_reg(N1, 1),
+    ]);
+}
+
+#[test]
+fn fpu() {
+    use Instr::*;
+    use Trit::*;
+
+    let float = |value: f64| Floating::from(value).to_word();
+    let nan = Floating::NAN.to_word();
+    let fcmp = |name, a: Word, b: Word, sign, parity| {
+        Case::new(name, vec![OPRR(FPU_CTRL_RR, FCMP_T, N1, N2, Word::ZERO)])
+            .reg(N1, a).reg(N2, b)
+            .expect_flags(Zero, sign, parity)
+    };
+
+    // Only fractions over powers of 3 are exact, so those are all the tests use
+    run_all([
+        Case::new("fadd", vec![OPRR(FPU_CTRL_RR, FADD_T, N1, N2, Word::ZERO)])
+            .reg(N1, float(1.0 / 3.0)).reg(N2, float(2.0))
+            .expect_reg(N1, float(7.0 / 3.0))
+            .expect_reg(N2, float(2.0)),
+        Case::new("fsub", vec![OPRR(FPU_CTRL_RR, FSUB_T, N1, N2, Word::ZERO)])
+            .reg(N1, float(1.0)).reg(N2, float(4.0))
+            .expect_reg(N1, float(-3.0)),
+        Case::new("fmul", vec![OPRR(FPU_CTRL_RR, FMUL_T, N1, N2, Word::ZERO)])
+            .reg(N1, float(-4.0 / 9.0)).reg(N2, float(9.0))
+            .expect_reg(N1, float(-4.0)),
+        Case::new("fdiv", vec![OPRR(FPU_CTRL_RR, FDIV_T, N1, N2, Word::ZERO)])
+            .reg(N1, float(1.0)).reg(N2, float(3.0))
+            .expect_reg(N1, float(1.0 / 3.0)),
+        Case::new("fdiv by zero", vec![OPRR(FPU_CTRL_RR, FDIV_T, N1, N2, Word::ZERO)])
+            .reg(N1, float(-2.0))
+            .expect_reg(N1, Floating::NEG_INFINITY.to_word()),
+        Case::new("fadd ri", vec![OPRI(FPU_CTRL_RI, FADD_T, N1, (-3).into())])
+            .reg(N1, float(1.0 / 3.0))
+            .expect_reg(N1, float(-8.0 / 3.0)),
+        Case::new("fmul ri", vec![OPRI(FPU_CTRL_RI, FMUL_T, N1, 10.into())])
+            .reg(N1, float(1.0 / 3.0))
+            .expect_reg(N1, float(10.0 / 3.0)),
+        Case::new("itof", vec![OPRR(FPU_CTRL_RR, ITOF_T, N1, N2, Word::ZERO)])
+            .reg(N2, -364)
+            .expect_reg(N1, float(-364.0)),
+        Case::new("itof ri", vec![OPRI(FPU_CTRL_RI, ITOF_T, N1, 17.into())])
+            .expect_reg(N1, float(17.0)),
+        Case::new("ftoi", vec![OPRR(FPU_CTRL_RR, FTOI_T, N1, N2, Word::ZERO)])
+            .reg(N2, float(-2.6))
+            .expect_reg(N1, -3)
+            .expect_flags(Zero, Zero, Zero),
+        Case::new("ftoi in place", vec![OPRR(FPU_CTRL_RR, FTOI_T, N1, N1, Word::ZERO)])
+            .reg(N1, float(1e6 + 0.4))
+            .expect_reg(N1, 1_000_000),
+        Case::new("ftoi saturates", vec![OPRR(FPU_CTRL_RR, FTOI_T, N1, N2, Word::ZERO)])
+            .reg(N2, float(-1e20))
+            .expect_reg(N1, Word::MIN)
+            .expect_flags(NOne, Zero, Zero),
+        Case::new("ftoi nan", vec![OPRR(FPU_CTRL_RR, FTOI_T, N1, N2, Word::ZERO)])
+            .reg(N1, 5).reg(N2, nan)
+            .expect_reg(N1, 0),
+        // Round trip through the FPU
+        Case::new("itof ftoi", vec![
+            OPRR(FPU_CTRL_RR, ITOF_T, N1, N1, Word::ZERO),
+            OPRI(FPU_CTRL_RI, FDIV_T, N1, 3.into()),
+            OPRR(FPU_CTRL_RR, FTOI_T, N1, N1, Word::ZERO),
+        ])
+            .reg(N1, 8)
+            .expect_reg(N1, 3),
+        fcmp("fcmp lt", float(-1.0), float(2.0), NOne, Zero),
+        fcmp("fcmp eq", float(2.0), float(2.0), Zero, Zero),
+        fcmp("fcmp gt", float(1e10), float(-1e10), POne, Zero),
+        fcmp("fcmp inf", Floating::INFINITY.to_word(), float(1e100), POne, Zero),
+        fcmp("fcmp nan", nan, float(2.0), Zero, POne),
+        fcmp("fcmp nan rhs", float(2.0), nan, Zero, POne),
+        Case::new("fcmp ri", vec![OPRI(FPU_CTRL_RI, FCMP_T, N1, 3.into())])
+            .reg(N1, float(2.9))
+            .expect_flags(Zero, NOne, Zero),
+    ]);
+}
+
+/// Compares `a` and `b`, then branches with `op`. `N4` ends up as 2 if the branch was taken, and
+/// 1 otherwise.
+fn branch_case(name: &'static str, op: Op, rr: bool, a: isize, b: isize) -> Case {
+    use Instr::*;
+
+    let branch = if rr {
+        // 9 + 0 + 3
+        OPRR(ALU_CTRL_R_RR, op, N3, N0, 3.into())
+    } else {
+        OPRI(ALU_CTRL_R_RI, op, N0, 12.into())
+    };
+
+    Case::new(name, vec![
+        /* 00 */ OPRR(ALU_CTRL_R_RR, CMP_T, N1, N2, Word::ZERO),
+        /* 03 */ branch,
+        /* 06 */ OPRI(ALU_CTRL_R_RI, ADD_T, N4, 1.into()),
+        /* 09 */ HALT,
+        /* 12 */ OPRI(ALU_CTRL_R_RI, ADD_T, N4, 2.into()),
+    ])
+    .reg(N1, a)
+    .reg(N2, b)
+    .reg(N3, 9)
+}
+
+#[test]
+fn branches() {
+    // Taken given the sign and parity of the difference
+    type Condition = fn(isize, isize) -> bool;
+
+    let branches: [(&'static str, Op, Condition); 9] = [
+        ("beq", BEQ_T, |sign, _| sign == 0),
+        ("bne", BNE_T, |sign, _| sign != 0),
+        ("bgt", BGT_T, |sign, _| sign > 0),
+        ("blt", BLT_T, |sign, _| sign < 0),
+        ("bgq", BGQ_T, |sign, _| sign >= 0),
+        ("blq", BLQ_T, |sign, _| sign <= 0),
+        ("bpz", BPZ_T, |_, parity| parity == 0),
+        ("bpp", BPP_T, |_, parity| parity == 1),
+        ("bpn", BPN_T, |_, parity| parity == -1),
+    ];
+    // Differences of 0, 2, -2, 3, -4 and 1 reach every sign and parity
+    let operands: [(isize, isize); 6] = [(4, 4), (5, 3), (3, 5), (1, -2), (-3, 1), (0, -1)];
+
+    for (name, op, taken) in branches {
+        for (a, b) in operands {
+            let diff = a - b;
+            // Least significant trit of the difference
+            let parity = match diff.rem_euclid(3) {
+                0 => 0,
+                1 => 1,
+                _ => -1,
+            };
+            let expected = if taken(diff.signum(), parity) { 2 } else { 1 };
+
+            for rr in [false, true] {
+                branch_case(name, op, rr, a, b)
+                    .expect_reg(N4, expected)
+                    .run();
+            }
+        }
+    }
+}
+
+#[test]
+fn load_store() {
+    use Instr::*;
+
+    run_all([
+        Case::new("load ri", vec![OPRI(ALU_CTRL_R_RI, LOAD_T, N1, DATA.into())])
+            .mem(DATA, 1234)
+            .expect_reg(N1, 1234),
+        Case::new("load rr", vec![OPRR(ALU_CTRL_R_RR, LOAD_T, N1, N2, 3.into())])
+            .mem(DATA + 3, -77)
+            .reg(N1, DATA)
+            .expect_reg(N2, -77)
+            .expect_reg(N1, DATA),
+        Case::new("store ri", vec![OPRI(ALU_CTRL_R_RI, STRE_T, N1, DATA.into())])
+            .reg(N1, 555)
+            .expect_mem(DATA, 555),
+        Case::new("store rr", vec![OPRR(ALU_CTRL_R_RR, STRE_T, N1, N2, 6.into())])
+            .reg(N1, DATA)
+            .reg(N2, Word::MIN)
+            .expect_mem(DATA + 6, Word::MIN),
+        Case::new("store then load", vec![
+            OPRI(ALU_CTRL_R_RI, STRE_T, N1, DATA.into()),
+            OPRI(ALU_CTRL_R_RI, LOAD_T, N2, DATA.into()),
+        ])
+            .reg(N1, Word::MAX)
+            .expect_reg(N2, Word::MAX),
+    ]);
+}
+
+#[test]
+fn stack() {
+    use Instr::*;
+
+    run_all([
+        Case::new("push pop ri", vec![
+            OPRI(ALU_CTRL_R_RI, PUSH_T, N1, 2.into()),
+            OPRI(ALU_CTRL_R_RI, PUSH_T, N1, Word::ZERO),
+            OPRI(ALU_CTRL_R_RI, POP_T, N2, Word::ZERO),
+            OPRI(ALU_CTRL_R_RI, POP_T, N3, Word::ZERO),
+        ])
+            .reg(N1, 40)
+            .expect_reg(N2, 40)
+            .expect_reg(N3, 42),
+        // [R] + [R] * imm
+        Case::new("push pop rr", vec![
+            OPRR(ALU_CTRL_R_RR, PUSH_T, N1, N2, 3.into()),
+            OPRR(ALU_CTRL_R_RR, POP_T, N3, N0, Word::ZERO),
+        ])
+            .reg(N1, 1)
+            .reg(N2, 2)
+            .expect_reg(N3, 7),
+    ]);
+}
+
+#[test]
+fn call_ret() {
+    use Instr::*;
+
+    let program = |target: Register, offset: isize| vec![
+        /* 00 */ CALL(target, CALL_CTRL_R, offset.into()),
+        /* 03 */ OPRI(ALU_CTRL_R_RI, ADD_T, N2, 1.into()),
+        /* 06 */ HALT,
+        /* 09 */ OPRI(ALU_CTRL_R_RI, ADD_T, N1, 5.into()),
+        // RET unwinds whatever is left on the stack
+        /* 12 */ OPRI(ALU_CTRL_R_RI, PUSH_T, N1, Word::ZERO),
+        /* 15 */ RET,
+    ];
+
+    run_all([
+        Case::new("call imm", program(N0, 9))
+            .expect_reg(N1, 5)
+            .expect_reg(N2, 1),
+        Case::new("call reg", program(N3, 3))
+            .reg(N3, 6)
+            .expect_reg(N1, 5)
+            .expect_reg(N2, 1),
+        Case::new("call tryte", vec![
+            CALL(N0, CALL_CTRL_T, 9.into()),
+            OPRI(ALU_CTRL_R_RI, ADD_T, N2, 1.into()),
+            HALT,
+            RET,
+        ])
+            .expect_reg(N2, 1),
+    ]);
+}
+
+#[test]
+fn interrupts() {
+    use Instr::*;
+
+    run_all([
+        Case::new("int", vec![
+            /* 00 */ OPRI(ALU_CTRL_R_RI, ADD_T, N1, DATA.into()),
+            /* 03 */ LIT(N1),
+            /* 06 */ INTERRUPT(1.into()),
+            /* 09 */ OPRI(ALU_CTRL_R_RI, ADD_T, N2, 1.into()),
+            /* 12 */ HALT,
+            /* 15 */ OPRI(ALU_CTRL_R_RI, ADD_T, N3, 7.into()),
+            /* 18 */ RTI,
+        ])
+            .mem(DATA, Word::ZERO)
+            .mem(DATA + 3, 15)
+            .expect_reg(N2, 1)
+            .expect_reg(N3, 7),
+    ]);
+}
+
+#[test]
+fn status_registers() {
+    use Instr::*;
+    use Trit::*;
+
+    let mut csr = CSR::default();
+    csr.set_sign(NOne);
+    csr.set_parity(NOne);
+
+    run_all([
+        Case::new("push pop csr", vec![
+            OPRR(ALU_CTRL_R_RR, CMP_T, N1, N2, Word::ZERO),
+            PCSR,
+            POCSR,
+        ])
+            .reg(N1, 1)
+            .reg(N2, 2)
+            .expect_reg(N13, csr.0)
+            .expect_flags(Zero, NOne, NOne),
+        Case::new("push pop psr", vec![PPSR, POPSR])
+            .reg(N12, 9)
+            .expect_reg(N12, 0),
+        Case::new("push pop ptr", vec![PPTR, POPTR])
+            .reg(N11, 9)
+            .expect_reg(N11, 0),
+        Case::new("lpt", vec![LPT(N1), OPRI(ALU_CTRL_R_RI, ADD_T, N2, 1.into())])
+            .reg(N1, DATA)
+            .expect_reg(N2, 1),
+    ]);
+}
+
+#[test]
+fn ports() {
+    use Instr::*;
+
+    run_all([
+        Case::new("out in word", vec![OUT(N1, OUT_CTRL_R, 2.into()), IN(N2, IN_CTRL_R, 2.into())])
+            .reg(N1, -31)
+            .expect_reg(N2, -31),
+        Case::new("out in tryte", vec![OUT(N1, OUT_CTRL_T, 3.into()), IN(N2, IN_CTRL_T, 3.into())])
+            .reg(N1, 31)
+            .expect_reg(N2, 31),
+    ]);
+}
@@ -1,10 +1,12 @@
 use std::{hint::unreachable_unchecked, sync::atomic::Ordering};
 
 use sdl3::{event::Event, keyboard::Keycode};
-use ternary::{prelude::Word, trits::Trit, tryte::Tryte};
+use ternary::{floating::Floating, prelude::Word, trits::Trit, tryte::Tryte};
+use tobj::{Section, TObj};
 
 use crate::{
     cpu::{CSR, JX_01, Status}, gpu::Gpu, isa::{
+        Size,
         self,
         registers::*,
         *,
@@ -13,6 +15,11 @@ use crate::{
 
 impl JX_01 {
     pub fn run_program(&mut self) {
+        self.run_from(Word::ZERO);
+    }
+
+    /// Runs the program in memory starting at `entry`, until it halts
+    pub fn run_from(&mut self, entry: Word) {
         // Initalize multi-threaded portions here
         // Ports
         self.ports = Some(
@@ -26,7 +33,7 @@ impl JX_01 {
             psr: Word::ZERO,
             sp: Word::MIN,
             bp: Word::MIN,
-            ip: Word::ZERO,
+            ip: entry,
         };
 
         // Option<Gpu>
@@ -60,6 +67,9 @@ impl JX_01 {
                     int_ret = self.status.ip;
                     let int_addr = idt + (<Tryte as Into<Word>>::into(int) << 1);
                     self.status.ip = *self.memory.get_physical_word(int_addr);
+                    if let Some(profiler) = self.profiler.as_mut() {
+                        profiler.call(self.status.ip);
+                    }
                 } else {
                     panic!("Must load an IDT before handling interrupts")
                 }
@@ -177,7 +187,11 @@ impl JX_01 {
             let instruction_ptr = self.status.ip;
 
             let instruction = *self.memory.get_physical_word(instruction_ptr);
-            match isa::decode(instruction) {
+            let instruction = isa::decode(instruction);
+            if let Some(profiler) = self.profiler.as_mut() {
+                profiler.sample(instruction_ptr, &instruction);
+            }
+            match instruction {
                 HALT => break,
                 DTI => {
                     todo!();
@@ -195,6 +209,9 @@ impl JX_01 {
                     self.status.ip = self.status.ip + (Word::PONE << 1);
                 },
                 RTI => {
+                    if let Some(profiler) = self.profiler.as_mut() {
+                        profiler.ret();
+                    }
                     self.status.ip = int_ret;
                     int_status.enabled = true;
                     self.interrupt.store(false, Ordering::Release);
@@ -261,31 +278,45 @@ impl JX_01 {
                 },
                 LPT(reg) => {
                     self.page_table = Some(self.registers.get_word(reg));
+                    self.status.ip = self.status.ip + (Word::PONE << 1);
                 },
                 INTM(_imm) => todo!("Interrupt masking not supported"),
                 INTE(_imm) => todo!("Interrupt masking not supported"),
                 INTS(_imm) => todo!("Interrupt masking not supported"),
-                IN(reg, _ctrl, imm) => {
+                IN(reg, ctrl, imm) => {
                     if let Some(ports) = self.ports.as_mut() {
                         let index: isize = imm.into();
                         assert!(index > 0, "Not using negative ports yet");
                         let port = &mut ports.ports[index as usize];
-                        self.registers.set_word(reg, unsafe {
+                        self.registers.set_sized(reg, Size::from_ctrl(ctrl), unsafe {
                             Word::from_u64(port.load(Ordering::Acquire))
                         });
                     }
+                    self.status.ip = self.status.ip + (Word::PONE << 1);
                 },
-                OUT(reg, _ctrl, imm) => {
+                OUT(reg, ctrl, imm) => {
                     if let Some(ports) = self.ports.as_mut() {
                         let index: isize = imm.into();
                         assert!(index > 0, "Not using negative ports yet");
                         let port = &mut ports.ports[index as usize];
-                        let val = self.registers.get_word(reg);
+                        let val = self.registers.get_sized(reg, Size::from_ctrl(ctrl));
                         port.store(val.num(), Ordering::Release);
                     }
+                    self.status.ip = self.status.ip + (Word::PONE << 1);
                 },
                 // Unused: Don't use OP CALL/RET
                 // For full compliance/optimization, make them identical with different meanings
+                OPRR(ctrl @ Tribble([_, _, Trit::NOne]), op, reg1, reg2, imm) => {
+                    let rhs = self.registers.get_sized(reg2, Size::from_ctrl(ctrl)) + imm;
+                    self.execute_ext_op(op, ctrl, reg1, rhs)
+                }
+                OPRI(ctrl @ Tribble([_, _, Trit::NOne]), op, reg, imm) => self.execute_ext_op(op, ctrl, reg, imm),
+                // IN and OUT took the other controls ending in 1
+                OPRR(Tribble([_, _, Trit::POne]), op, reg1, reg2, _imm) => {
+                    let rhs = self.registers.get_word(reg2);
+                    self.execute_fpu_op(op, reg1, rhs, false)
+                }
+                OPRI(Tribble([_, _, Trit::POne]), op, reg, imm) => self.execute_fpu_op(op, reg, imm, true),
                 OPRR(ctrl, op, reg1, reg2, imm) => self.execute_rr_op(op, ctrl, reg1, reg2, imm),
                 OPRI(ctrl, op, reg, imm) => self.execute_ri_op(op, ctrl, reg, imm),
                 // Call calls (jumps to addr in reg + imm), sets up stack frame
@@ -310,6 +341,10 @@ impl JX_01 {
 
                     // Jumps to loc at reg + imm
                     self.status.ip = self.registers.get_word(reg) + imm;
+
+                    if let Some(profiler) = self.profiler.as_mut() {
+                        profiler.call(self.status.ip);
+                    }
                 },
                 RET => {
                     // Moves SP to BP
@@ -325,6 +360,10 @@ impl JX_01 {
                     // Pop BP
                     self.status.sp = self.status.sp - (Word::PONE << 1);
                     self.status.bp = *self.memory.get_physical_word_mut(self.status.sp);
+
+                    if let Some(profiler) = self.profiler.as_mut() {
+                        profiler.ret();
+                    }
                 },
                 ENTER => panic!("Do not use: outdated"),
                 LEAVE => panic!("Do not use: outdated"),
@@ -343,10 +382,12 @@ impl JX_01 {
     ///  Stack Ops:      ALU Ops          *imm = [R]         [R] ~ imm
     ///  [R] + imm       [R] = [R] op imm *([R] + imm) = [R] [R] ~ [R] + imm
     ///  [R] + [R] * imm [R] = [R] op imm
-    fn execute_rr_op(&mut self, op: Op, _ctrl: Control, reg1: Register, reg2: Register, imm: Word) {
-        let (reg1_val, reg2_val) =  (self.registers.get_word(reg1), self.registers.get_word(reg2));
-
-        let op = op_to_opt(op);
+    ///
+    /// ADD, SUB and MUL leave the trit carried out of the result in the CSR.
+    /// Only CMP sets the sign and parity flags.
+    fn execute_rr_op(&mut self, op: Op, ctrl: Control, reg1: Register, reg2: Register, imm: Word) {
+        let size = Size::from_ctrl(ctrl);
+        let (reg1_val, reg2_val) = (self.registers.get_sized(reg1, size), self.registers.get_sized(reg2, size));
 
         // Values to override after execution
         let ip: Word;
@@ -355,79 +396,21 @@ impl JX_01 {
         let mut csr: CSR = self.status.csr;
 
         match op {
-            BPN => {
-                if csr.get_parity() == Trit::NOne {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BPP => {
-                if csr.get_parity() == Trit::POne {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BPZ => {
-                if csr.get_parity() == Trit::Zero {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BGQ => {
-                if csr.get_sign() == Trit::Zero || csr.get_sign() == Trit::POne {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BLQ => {
-                if csr.get_sign() == Trit::Zero || csr.get_sign() == Trit::NOne {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BLT => {
-                if csr.get_sign() == Trit::NOne {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BGT => {
-                if csr.get_sign() == Trit::POne {
+            op if is_branch(op) => {
+                if csr.takes_branch(op) {
                     ip = reg1_val + (reg2_val + imm);
                 } else {
                     ip = self.status.ip + (Word::PONE << 1);
                 }
             }
-            BNE => {
-                if csr.get_sign() == Trit::NOne || csr.get_sign() == Trit::POne {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BEQ => {
-                if csr.get_sign() == Trit::Zero {
-                    ip = reg1_val + (reg2_val + imm);
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            CMP => {
+            CMP_T => {
                 let res: Word = reg1_val - reg2_val;
                 // set sign, parity, and carry
-                csr.set_sign(res.get_sign());
-                csr.set_parity(res.get_parity());
-                csr.set_carry(res.get_carry());
+                csr.set_flags(size, res);
 
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            STRE => {
+            STRE_T => {
                 // *([R] + imm) = [R]
                 let addr = reg1_val + imm;
                 let mem_loc = self.memory.get_physical_word_mut(addr);
@@ -435,97 +418,97 @@ impl JX_01 {
                 *mem_loc = reg2_val;
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            LOAD => {
+            LOAD_T => {
                 // [R] = *([R] + imm)
                 let addr = reg1_val + imm;
                 let val = self.memory.get_physical_word(addr);
-                self.registers.set_word(reg2, *val);
+                self.registers.set_sized(reg2, size, *val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            ADD => {
+            ADD_T => {
                 //  ALU:-[C][I]-[R][imm @ 3-8]
                 //             \[R][R][imm @ 4-8]
                 //  ALU Ops         
                 //  [R] = [R] op ([R] + imm)
                 //  [R] = [R] op imm
-                let val = reg1_val + (reg2_val + imm);
-                self.registers.set_word(reg1, val);
+                let (val, carry) = size.overflowing_add(reg1_val, reg2_val + imm);
+                self.registers.set_sized(reg1, size, val);
+                csr.set_carry(carry);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            SUB => {
-                let val = reg1_val - (reg2_val + imm);
-                self.registers.set_word(reg1, val);
+            SUB_T => {
+                let (val, carry) = size.overflowing_add(reg1_val, -(reg2_val + imm));
+                self.registers.set_sized(reg1, size, val);
+                csr.set_carry(carry);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            MUL => {
-                let val = dbg!(reg1_val) * dbg!(reg2_val + imm);
-                dbg!(<Word as Into<isize>>::into(val));
-                self.registers.set_word(reg1, val);
+            MUL_T => {
+                let (val, carry) = size.overflowing_mul(reg1_val, reg2_val + imm);
+                self.registers.set_sized(reg1, size, val);
+                csr.set_carry(carry);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            QOT => {
+            QOT_T => {
                 // TODO: Handle Div by Zero Fault
-                let val = reg1_val / (reg2_val + imm);
-                self.registers.set_word(reg1, val.unwrap());
+                let val = reg1_val / size.truncate(reg2_val + imm);
+                self.registers.set_sized(reg1, size, val.unwrap());
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            REM => {
+            REM_T => {
                 // TODO: Handle Div by Zero Fault
-                let val = reg1_val % (reg2_val + imm);
-                self.registers.set_word(reg1, val.unwrap());
+                let val = reg1_val % size.truncate(reg2_val + imm);
+                self.registers.set_sized(reg1, size, val.unwrap());
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            AND => {
-                let val = reg1_val & (reg2_val + imm);
-                self.registers.set_word(reg1, val);
+            AND_T => {
+                let val = reg1_val & size.truncate(reg2_val + imm);
+                self.registers.set_sized(reg1, size, val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            OR => {
-                let val = reg1_val | (reg2_val + imm);
-                self.registers.set_word(reg1, val);
+            OR_T => {
+                let val = reg1_val | size.truncate(reg2_val + imm);
+                self.registers.set_sized(reg1, size, val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            SFT => {
+            SFT_T => {
                 let shift: isize = (reg2_val + imm).into();
                 let val = match shift.signum() {
-                    -1 => reg1_val >> (shift.abs() as usize),
-                    1 => reg1_val << (shift.abs() as usize),
-                    _ => unsafe {
-                        std::hint::unreachable_unchecked()
-                    }
+                    -1 => reg1_val >> shift.unsigned_abs(),
+                    1 => reg1_val << shift.unsigned_abs(),
+                    _ => reg1_val,
                 };
-                self.registers.set_word(reg1, val);
+                self.registers.set_sized(reg1, size, val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            NOT => {
+            NOT_T => {
                 //  ALU:-[C][I]-[R][imm @ 3-8]
                 //             \[R][R][imm @ 4-8]
                 //  ALU Ops
                 //  [R] = [R] op ([R] + imm)
                 //  [R] = [R] op imm
-                self.registers.set_word(reg1, -reg1_val);
+                self.registers.set_sized(reg1, size, -reg1_val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            ROT => {
-                self.registers.set_word(reg1, reg1_val.rot(reg2_val.into()));
+            ROT_T => {
+                self.registers.set_sized(reg1, size, size.rot(reg1_val, reg2_val.into()));
                 ip = self.status.ip + (Word::PONE << 1);
             }
             //  Stack Ops:
             //  [R] + imm
             //  [R] + ([R] * imm)
             // ONLY SUPPORTING WORD SIZE VALUES RIGHT NOW
-            PUSH => {
+            PUSH_T => {
                 *self.memory.get_physical_word_mut(sp) = reg1_val + (reg2_val * imm);
                 ip = self.status.ip + (Word::PONE << 1);
                 sp = self.status.sp + (Word::PONE << 1);
             }
-            POP => {
+            POP_T => {
                 ip = self.status.ip + (Word::PONE << 1);
                 sp = self.status.sp - (Word::PONE << 1);
-                self.registers.set_word(reg1, *self.memory.get_physical_word(sp));
+                self.registers.set_sized(reg1, size, *self.memory.get_physical_word(sp));
             }
-            CALL => panic!("invalid instr"),
-            RET => panic!("invalid instr"),
+            CALL_T => panic!("invalid instr"),
+            RET_T => panic!("invalid instr"),
             _ => unsafe { unreachable_unchecked() },
         }
         self.status.ip = ip;
@@ -533,10 +516,12 @@ impl JX_01 {
         self.status.csr = csr;
     }
 
-    fn execute_ri_op(&mut self, op: Op, _ctrl: Control, reg: Register, imm: Word) {
-        let reg_val = self.registers.get_word(reg);
-
-        let op = op_to_opt(op);
+    fn execute_ri_op(&mut self, op: Op, ctrl: Control, reg: Register, imm: Word) {
+        let size = Size::from_ctrl(ctrl);
+        let reg_val = self.registers.get_sized(reg, size);
+        // The immediate as an operand is read at the register's size, like
+        // the RR form's. Addresses and counts still use all of it.
+        let operand = size.truncate(imm);
 
         // Values to override after execution
         let ip: Word;
@@ -545,75 +530,17 @@ impl JX_01 {
         let mut csr: CSR = self.status.csr;
 
         match op {
-            BPN => {
-                if csr.get_parity() == Trit::NOne {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BPP => {
-                if csr.get_parity() == Trit::POne {
+            op if is_branch(op) => {
+                if csr.takes_branch(op) {
                     ip = reg_val + imm;
                 } else {
                     ip = self.status.ip + (Word::PONE << 1);
                 }
             }
-            BPZ => {
-                if csr.get_parity() == Trit::Zero {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BGQ => {
-                if csr.get_sign() == Trit::Zero || csr.get_sign() == Trit::POne {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BLQ => {
-                if csr.get_sign() == Trit::Zero || csr.get_sign() == Trit::NOne {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BLT => {
-                if csr.get_sign() == Trit::NOne {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BGT => {
-                if csr.get_sign() == Trit::POne {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BNE => {
-                if csr.get_sign() == Trit::NOne || csr.get_sign() == Trit::POne {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            BEQ => {
-                if csr.get_sign() == Trit::Zero {
-                    ip = reg_val + imm;
-                } else {
-                    ip = self.status.ip + (Word::PONE << 1);
-                }
-            }
-            CMP => {
-                let res = reg_val - imm;
+            CMP_T => {
+                let res = reg_val - operand;
                 // set sign, parity, and carry
-                csr.set_sign(res.get_sign());
-                csr.set_parity(res.get_parity());
-                csr.set_carry(res.get_carry());
+                csr.set_flags(size, res);
 
                 ip = self.status.ip + (Word::PONE << 1);
             }
@@ -626,109 +553,110 @@ impl JX_01 {
             //  Stack Ops:      ALU Ops          *imm = [R]         [R] ~ imm
             //  [R] + imm       [R] = [R] op imm *([R] + imm) = [R] [R] ~ [R] + imm
             //  [R] + [R] * imm [R] = [R] op imm
-            STRE => {
+            STRE_T => {
                 let addr = imm;
                 let mem_loc = self.memory.get_physical_word_mut(addr);
                 // I might have messed things up but it's okay :)
                 *mem_loc = reg_val;
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            LOAD => {
+            LOAD_T => {
                 let addr = imm;
                 let val = self.memory.get_physical_word(addr);
-                self.registers.set_word(reg, *val);
+                self.registers.set_sized(reg, size, *val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            ADD => {
+            ADD_T => {
                 //  ALU:-[C][I]-[R][imm @ 3-8]
                 //             \[R][R][imm @ 4-8]
                 //  ALU Ops         
                 //  [R] = [R] op ([R] + imm)
                 //  [R] = [R] op imm
-                let val = reg_val + imm;
-                self.registers.set_word(reg, val);
+                let (val, carry) = size.overflowing_add(reg_val, operand);
+                self.registers.set_sized(reg, size, val);
+                csr.set_carry(carry);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            SUB => {
-                let val = reg_val - imm;
-                self.registers.set_word(reg, val);
+            SUB_T => {
+                let (val, carry) = size.overflowing_add(reg_val, -operand);
+                self.registers.set_sized(reg, size, val);
+                csr.set_carry(carry);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            MUL => {
-                let val = reg_val * imm;
-                self.registers.set_word(reg, val);
+            MUL_T => {
+                let (val, carry) = size.overflowing_mul(reg_val, operand);
+                self.registers.set_sized(reg, size, val);
+                csr.set_carry(carry);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            QOT => {
+            QOT_T => {
                 // TODO: Handle Div by Zero Fault
-                let val = reg_val / imm;
-                self.registers.set_word(reg, val.unwrap());
+                let val = reg_val / operand;
+                self.registers.set_sized(reg, size, val.unwrap());
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            REM => {
+            REM_T => {
                 // TODO: Handle Div by Zero Fault
-                let val = reg_val % imm;
-                self.registers.set_word(reg, val.unwrap());
+                let val = reg_val % operand;
+                self.registers.set_sized(reg, size, val.unwrap());
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            AND => {
-                let val = reg_val & imm;
-                self.registers.set_word(reg, val);
+            AND_T => {
+                let val = reg_val & operand;
+                self.registers.set_sized(reg, size, val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            OR => {
-                let val = reg_val | imm;
-                self.registers.set_word(reg, val);
+            OR_T => {
+                let val = reg_val | operand;
+                self.registers.set_sized(reg, size, val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            SFT => {
+            SFT_T => {
                 let shift: isize = imm.into();
                 let val = match shift.signum() {
-                    -1 => reg_val >> (shift.abs() as usize),
-                    1 => reg_val << (shift.abs() as usize),
-                    _ => unsafe {
-                        std::hint::unreachable_unchecked()
-                    }
+                    -1 => reg_val >> shift.unsigned_abs(),
+                    1 => reg_val << shift.unsigned_abs(),
+                    _ => reg_val,
                 };
-                self.registers.set_word(reg, val);
+                self.registers.set_sized(reg, size, val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            NOT => {
+            NOT_T => {
                 //  ALU:-[C][I]-[R][imm @ 3-8]
                 //             \[R][R][imm @ 4-8]
                 //  ALU Ops
                 //  [R] = [R] op ([R] + imm)
                 //  [R] = [R] op imm
-                self.registers.set_word(reg, -reg_val);
+                self.registers.set_sized(reg, size, -reg_val);
                 ip = self.status.ip + (Word::PONE << 1);
             }
-            ROT => {
-                self.registers.set_word(reg, reg_val.rot(imm.into()));
+            ROT_T => {
+                self.registers.set_sized(reg, size, size.rot(reg_val, imm.into()));
                 ip = self.status.ip + (Word::PONE << 1);
             }
             //  Stack Ops:
             //  [R] + imm
             //  [R] + ([R] * imm)
             // ONLY SUPPORTING WORD SIZE VALUES RIGHT NOW
-            PUSH => {
+            PUSH_T => {
                 *self.memory.get_physical_word_mut(sp) = reg_val + imm;
                 ip = self.status.ip + (Word::PONE << 1);
                 sp = self.status.sp + (Word::PONE << 1);
             }
-            POP => {
+            POP_T => {
                 ip = self.status.ip + (Word::PONE << 1);
                 sp = self.status.sp - (Word::PONE << 1);
-                self.registers.set_word(reg, *self.memory.get_physical_word(sp));
+                self.registers.set_sized(reg, size, *self.memory.get_physical_word(sp));
             }
             // Setup the new stack frame
-            CALL => {
+            CALL_T => {
                 panic!("Unused Op");
             }
-            RET => {
+            RET_T => {
                 panic!("Unused Op");
             }
             val => unsafe {
-                println!("{val}");
+                println!("{val:?}");
                 unreachable_unchecked();
             },
         }
@@ -737,6 +665,89 @@ impl JX_01 {
         self.status.csr = csr;
     }
 
+    /// Extended ALU group, for chaining arithmetic across words
+    ///  [R] = [R] op ([R] + imm)
+    ///  [R] = [R] op imm
+    ///
+    /// ADC adds the CSR carry in, SBB subtracts and adds it in (a balanced borrow is just a
+    /// carry of T), and both leave their own carry in the CSR. MULH keeps the high half of the
+    /// double width product.
+    fn execute_ext_op(&mut self, op: Op, ctrl: Control, reg: Register, rhs: Word) {
+        let size = Size::from_ctrl(ctrl);
+        let lhs = self.registers.get_sized(reg, size);
+        let csr = &mut self.status.csr;
+
+        match op {
+            ADC_T => {
+                let (val, carry) = size.carrying_add(lhs, rhs, csr.get_carry());
+                self.registers.set_sized(reg, size, val);
+                csr.set_carry(carry);
+            }
+            SBB_T => {
+                let (val, carry) = size.carrying_add(lhs, -rhs, csr.get_carry());
+                self.registers.set_sized(reg, size, val);
+                csr.set_carry(carry);
+            }
+            MULH_T => {
+                let (_, high) = size.widening_mul(lhs, rhs);
+                self.registers.set_sized(reg, size, high);
+            }
+            _ => panic!("invalid instr"),
+        }
+        self.status.ip = self.status.ip + (Word::PONE << 1);
+    }
+
+    /// FPU group, on floats held in the word registers
+    ///  [R] = [R] op [R]
+    ///  [R] = [R] op imm
+    ///
+    /// The immediate is an integer, converted to a float first, and RR ignores it. ITOF reads its
+    /// operand as an integer and FTOI rounds its operand to the nearest integer. A float that is
+    /// NaN becomes 0 and one that doesn't fit saturates, with the carry set to its sign.
+    /// FCMP sets the sign to how [R] orders against the operand, and the parity to 1 if they
+    /// are unordered (either is NaN), in which case the sign is 0.
+    fn execute_fpu_op(&mut self, op: Op, reg: Register, rhs: Word, imm: bool) {
+        let lhs = Floating::from_word(self.registers.get_word(reg));
+        let float = if imm { Floating::from_integer(rhs) } else { Floating::from_word(rhs) };
+        let csr = &mut self.status.csr;
+
+        let res = match op {
+            FADD_T => Some(lhs + float),
+            FSUB_T => Some(lhs - float),
+            FMUL_T => Some(lhs * float),
+            FDIV_T => Some(lhs / float),
+            FCMP_T => {
+                let (sign, parity) = match lhs.partial_cmp(&float) {
+                    Some(std::cmp::Ordering::Less) => (Trit::NOne, Trit::Zero),
+                    Some(std::cmp::Ordering::Equal) => (Trit::Zero, Trit::Zero),
+                    Some(std::cmp::Ordering::Greater) => (Trit::POne, Trit::Zero),
+                    None => (Trit::Zero, Trit::POne),
+                };
+                csr.set_sign(sign);
+                csr.set_parity(parity);
+                csr.set_carry(Trit::Zero);
+                None
+            }
+            ITOF_T => Some(Floating::from_integer(rhs)),
+            FTOI_T => {
+                let (val, carry) = match float.to_integer() {
+                    Some(val) => (val, Trit::Zero),
+                    None if float.is_nan() => (Word::ZERO, Trit::Zero),
+                    None if float > Floating::ZERO => (Word::MAX, Trit::POne),
+                    None => (Word::MIN, Trit::NOne),
+                };
+                self.registers.set_word(reg, val);
+                csr.set_carry(carry);
+                None
+            }
+            _ => panic!("invalid instr"),
+        };
+        if let Some(res) = res {
+            self.registers.set_word(reg, res.to_word());
+        }
+        self.status.ip = self.status.ip + (Word::PONE << 1);
+    }
+
     pub fn import_memory(&mut self, memory: &[Word]) {
         let mut index = Word::ZERO;
         let add = Word::PONE << 1;
@@ -747,6 +758,15 @@ impl JX_01 {
         }
     }
 
+    /// Loads a tobj's text with its data right after from address 0, and
+    /// returns where it starts
+    pub fn import_tobj(&mut self, tobj: &TObj) -> Word {
+        let text = tobj.section(Section::Text);
+        let data = tobj.section(Section::Data);
+        self.import_memory(&[text, data].concat());
+        tobj.entry().into()
+    }
+
     pub fn import_instrs(&mut self, instrs: &[Instr]) {
         let mut index = Word::ZERO;
         let add = Word::PONE << 1;
@@ -1,14 +1,17 @@
 pub mod event_loop;
+pub mod profile;
+#[cfg(test)]
+mod conformance;
 
 use std::{mem::{self, MaybeUninit}, sync::{Arc, atomic::{AtomicBool, AtomicU32, Ordering}}};
 
 use crossbeam_utils::CachePadded;
 
-use septivigntimal::{to_num, ZERO};
+use septivigntimal::ZERO;
 
-use ternary::{TRYTE_BIT_MASK, TRYTE_LEN, WORD_LEN, trits::Trit, tryte::Tryte, word::Word};
+use ternary::{TRYTE_LEN, WORD_LEN, bitfield, trits::Trit, tryte::Tryte, word::Word};
 
-use crate::{isa::registers::Register, memory::Memory, ports::Ports};
+use crate::{cpu::profile::Profiler, isa::{self, Op, Size, registers::Register}, memory::Memory, ports::Ports};
 #[cfg(feature = "gpu")]
 use crate::gpu::Gpu;
 
@@ -23,6 +26,7 @@ pub struct JX_01 {
     // Change to allow zero register to be zero
     registers: Registers,
     status: Status,
+    profiler: Option<Profiler>,
     pub interrupt: Arc<CachePadded<AtomicBool>>,
     pub interrupt_num: Arc<CachePadded<AtomicU32>>,
 }
@@ -38,6 +42,16 @@ impl JX_01 {
         }
     }
 
+    /// Profile every instruction executed from now on
+    pub fn enable_profiling(&mut self, profiler: Profiler) {
+        self.profiler = Some(profiler);
+    }
+
+    /// Stops profiling, returning what was collected
+    pub fn take_profile(&mut self) -> Option<Profiler> {
+        self.profiler.take()
+    }
+
     pub fn new() -> JX_01 {
         let memory = Memory::default();
         let interrupt = Arc::new(CachePadded::new(AtomicBool::new(false)));
@@ -55,6 +69,7 @@ impl JX_01 {
             page_table: None,
             registers,
             status,
+            profiler: None,
             interrupt,
             interrupt_num,
         }
@@ -66,43 +81,49 @@ pub struct Registers([Word; 27]);
 
 impl Registers {
     fn get_word(&self, index: Register) -> Word {
-        let ind = to_num(index.0) + 13;
-
-        if ind == to_num(ZERO) + 13 {
+        if index.0 == ZERO {
             return Word::ZERO;
         }
 
-        self.0[ind as usize]
+        self.0[index.0.index()]
     }
 
     fn set_word(&mut self, index: Register, val: Word) {
-        let ind = to_num(index.0) + 13;
-
-        if ind == to_num(ZERO) + 13 {
+        if index.0 == ZERO {
             return;
         }
 
-        self.0[ind as usize] = val;
+        self.0[index.0.index()] = val;
     }
 
     fn get_tryte(&self, index: Register) -> Tryte {
-        let ind = to_num(index.0) + 13;
-
-        if ind == to_num(ZERO) + 13 {
+        if index.0 == ZERO {
             return Tryte::ZERO;
         }
 
-        self.0[ind as usize].into()
+        self.0[index.0.index()].into()
     }
 
     fn set_tryte(&mut self, index: Register, val: Tryte) {
-        let ind = to_num(index.0) + 13;
-
-        if ind == to_num(ZERO) + 13 {
+        if index.0 == ZERO {
             return;
         }
 
-        self.0[ind as usize] = val.into();
+        self.0[index.0.index()] = val.into();
+    }
+
+    fn get_sized(&self, index: Register, size: Size) -> Word {
+        match size {
+            Size::Tryte => self.get_tryte(index).into(),
+            Size::Word => self.get_word(index),
+        }
+    }
+
+    fn set_sized(&mut self, index: Register, size: Size, val: Word) {
+        match size {
+            Size::Tryte => self.set_tryte(index, val.into()),
+            Size::Word => self.set_word(index, val),
+        }
     }
 }
 pub struct Status {
@@ -130,132 +151,66 @@ impl Default for Status {
     }
 }
 
-// The CPU status register holds informations such as:
-// Status Word:
-/// [C, S, P, I, R, G, T, _, _,
-///  interrupt_vector,
-///  interrupt_number,
-/// ]
-/// C: Carry Flag
-/// S: Sign Flag
-/// P: Parity Flag
-/// I: Interrupt Enable
-/// R: Privledge Level
-/// G: GPU Enable
-/// T: Page Enable
-#[derive(Clone, Copy)]
-pub struct CSR(Word);
-
-impl Default for CSR {
-    fn default() -> Self {
-        CSR(Word::ZERO)
+bitfield! {
+    // The CPU status register holds informations such as:
+    // Status Word:
+    /// [C, S, P, I, R, G, T, _, _,
+    ///  interrupt_vector,
+    ///  interrupt_number,
+    /// ]
+    /// C: Carry Flag
+    /// S: Sign Flag
+    /// P: Parity Flag
+    /// I: Interrupt Enable
+    /// R: Privledge Level
+    /// G: GPU Enable
+    /// T: Page Enable
+    #[derive(Clone, Copy, Default)]
+    pub struct CSR(Word) {
+        pub get_carry, set_carry: Trit [WORD_LEN - 1];
+        pub get_sign, set_sign: Trit [WORD_LEN - 2];
+        pub get_parity, set_parity: Trit [WORD_LEN - 3];
+        pub get_interrupt, set_interrupt: Trit [WORD_LEN - 4];
+        pub get_privilege, set_privilege: Trit [WORD_LEN - 5];
+        pub get_gpu, set_gpu: Trit [WORD_LEN - 6];
+        pub get_paging, set_paging: Trit [WORD_LEN - 7];
+        pub get_interrupt_vector, set_interrupt_vector: Tryte [TRYTE_LEN..2 * TRYTE_LEN];
     }
 }
 
 impl CSR {
-    pub fn get_carry(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 1) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_sign(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 2) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_parity(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 3) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_interrupt(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 4) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_privilege(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 5) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_gpu(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 6) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_paging(&self) -> Trit {
-        unsafe { Trit::from_num(((self.0.num() >> ((WORD_LEN - 7) * 2)) & 0b11) as u8) }
-    }
-    pub fn get_interrupt_vector(&self) -> Tryte {
-        unsafe { Tryte::from_num((self.0.num() >> (TRYTE_LEN * 2)) as u32 & TRYTE_BIT_MASK) }
-    }
-
-    pub fn set_carry(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Trit::TRIT_BIT_MASK as u64) << ((WORD_LEN - 1) * 2))) &
-                self.0.num()) |
-                ((val as u64) << ((WORD_LEN - 1) * 2)))
-            }
-        );
-    }
-    pub fn set_sign(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                    (!(0b11 << ((WORD_LEN - 2) * 2)) & self.0.num())
-                    | ((val as u64) << ((WORD_LEN - 2) * 2))
-                )
-            }
-        );
-    }
-    pub fn set_parity(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Trit::TRIT_BIT_MASK as u64) << ((WORD_LEN - 3) * 2))) &
-                self.0.num()) |
-                ((val as u64) << ((WORD_LEN - 3) * 2)))
-            }
-        );
-    }
-    pub fn set_interrupt(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Trit::TRIT_BIT_MASK as u64) << ((WORD_LEN - 4) * 2))) &
-                self.0.num()) |
-                ((val as u64) << ((WORD_LEN - 4) * 2)))
-            }
-        );
-    }
-    pub fn set_privilege(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Trit::TRIT_BIT_MASK as u64) << ((WORD_LEN - 5) * 2))) &
-                self.0.num()) |
-                ((val as u64) << ((WORD_LEN - 5) * 2)))
-            }
-        );
-    }
-    pub fn set_gpu(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Trit::TRIT_BIT_MASK as u64) << ((WORD_LEN - 6) * 2))) &
-                self.0.num()) |
-                ((val as u64) << ((WORD_LEN - 6) * 2)))
+    /// Sets carry, sign and parity from the result of a `size` operation.
+    /// A tryte result keeps its carry in the trit just above the tryte.
+    pub fn set_flags(&mut self, size: Size, res: Word) {
+        match size {
+            Size::Tryte => {
+                let truncated = size.truncate(res);
+                self.set_sign(truncated.get_sign());
+                self.set_parity(truncated.get_parity());
+                self.set_carry(res.get(TRYTE_LEN).unwrap());
             }
-        );
-    }
-    pub fn set_paging(&mut self, val: Trit) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Trit::TRIT_BIT_MASK as u64) << ((WORD_LEN - 7) * 2))) &
-                self.0.num()) |
-                ((val as u64) << ((WORD_LEN - 7) * 2)))
+            Size::Word => {
+                self.set_sign(res.get_sign());
+                self.set_parity(res.get_parity());
+                self.set_carry(res.get_carry());
             }
-        );
+        }
     }
-    pub fn set_interrupt_vector(&mut self, val: Tryte) {
-        *self = CSR(
-            unsafe {
-                Word::from_u64(
-                ((Word::WORD_BIT_MASK ^ ((Tryte::TRYTE_BIT_MASK as u64) << (TRYTE_LEN * 2))) &
-                self.0.num()) |
-                ((val.num() as u64) << (TRYTE_LEN * 2)))
-            }
-        );
+
+    /// Whether the flags take the branch `op`, which is false for anything but a branch
+    pub fn takes_branch(&self, op: Op) -> bool {
+        let (sign, parity) = (self.get_sign(), self.get_parity());
+        match op {
+            isa::BPN_T => parity == Trit::NOne,
+            isa::BPP_T => parity == Trit::POne,
+            isa::BPZ_T => parity == Trit::Zero,
+            isa::BGQ_T => sign != Trit::NOne,
+            isa::BLQ_T => sign != Trit::POne,
+            isa::BLT_T => sign == Trit::NOne,
+            isa::BGT_T => sign == Trit::POne,
+            isa::BNE_T => sign != Trit::Zero,
+            isa::BEQ_T => sign == Trit::Zero,
+            _ => false,
+        }
     }
 }
@@ -0,0 +1,523 @@
+//! Instruction level profiler for guest programs.
+//!
+//! Every executed instruction is counted by its instruction pointer and by its
+//! opcode. A shadow call stack is kept from `CALL`/`RET` (and interrupt
+//! entry/`RTI`), so each sample is also attributed to the chain of functions
+//! that were active when it ran. Functions are named from a [`SymbolMap`] if
+//! one is given, and by their entry address otherwise. The map comes from a
+//! tobj's `sym` section, or an `nm` style listing.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    str::FromStr,
+};
+
+use septivigntimal::Tribble;
+use ternary::{trits::Trit, word::Word};
+use tobj::Symbol;
+
+use crate::isa::{self, Instr, Op};
+
+/// Maps guest addresses to symbol names
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SymbolMap(BTreeMap<isize, String>);
+
+impl SymbolMap {
+    pub fn new() -> SymbolMap {
+        SymbolMap(BTreeMap::new())
+    }
+
+    pub fn insert(&mut self, addr: isize, name: impl Into<String>) {
+        self.0.insert(addr, name.into());
+    }
+
+    /// Finds the symbol an address belongs to, which is the closest symbol at
+    /// or below it, along with the offset of the address into that symbol.
+    pub fn lookup(&self, addr: isize) -> Option<(&str, isize)> {
+        self.0
+            .range(..=addr)
+            .next_back()
+            .map(|(&start, name)| (name.as_str(), addr - start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(isize, String)> for SymbolMap {
+    fn from_iter<T: IntoIterator<Item = (isize, String)>>(iter: T) -> Self {
+        SymbolMap(iter.into_iter().collect())
+    }
+}
+
+/// A later symbol at the same address replaces the earlier one
+impl Extend<(isize, String)> for SymbolMap {
+    fn extend<T: IntoIterator<Item = (isize, String)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl IntoIterator for SymbolMap {
+    type Item = (isize, String);
+    type IntoIter = std::collections::btree_map::IntoIter<isize, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// From a tobj's `sym` section
+impl FromIterator<Symbol> for SymbolMap {
+    fn from_iter<T: IntoIterator<Item = Symbol>>(iter: T) -> Self {
+        iter.into_iter().map(|symbol| (symbol.addr, symbol.name)).collect()
+    }
+}
+
+impl Extend<Symbol> for SymbolMap {
+    fn extend<T: IntoIterator<Item = Symbol>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|symbol| (symbol.addr, symbol.name)));
+    }
+}
+
+/// Parses `nm` style symbol listings: one `<address> <name>` pair per line,
+/// with the address in decimal. Blank lines and lines starting with `;` are
+/// skipped. The error is the (1-based) number of the first bad line.
+impl FromStr for SymbolMap {
+    type Err = usize;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut map = SymbolMap::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (Some(addr), Some(name), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(i + 1);
+            };
+            let addr: isize = addr.parse().map_err(|_| i + 1)?;
+            map.insert(addr, name);
+        }
+        Ok(map)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Profiler {
+    symbols: SymbolMap,
+    total: u64,
+    ips: HashMap<Word, u64>,
+    /// Indexed by [`Tribble::index`]
+    ops: [u64; 27],
+    instrs: HashMap<&'static str, u64>,
+    /// Entry addresses of the functions currently on the guest stack
+    stack: Vec<Word>,
+    stacks: HashMap<Vec<Word>, u64>,
+    /// (caller, callee) entry addresses
+    edges: HashMap<(Word, Word), u64>,
+}
+
+impl Profiler {
+    pub fn new(symbols: SymbolMap) -> Profiler {
+        Profiler {
+            symbols,
+            ..Default::default()
+        }
+    }
+
+    /// Records one execution of `instr` at `ip`
+    pub(crate) fn sample(&mut self, ip: Word, instr: &Instr) {
+        // The first instruction executed is the program entry point
+        if self.stack.is_empty() {
+            self.stack.push(ip);
+        }
+
+        self.total += 1;
+        *self.ips.entry(ip).or_default() += 1;
+        match instr {
+            Instr::OPRR(Tribble([_, _, Trit::Zero]), op, ..)
+            | Instr::OPRI(Tribble([_, _, Trit::Zero]), op, ..) => self.ops[op.index()] += 1,
+            // The extended and FPU groups reuse opcodes, so they are counted by name
+            instr => *self.instrs.entry(instr_name(instr)).or_default() += 1,
+        }
+
+        match self.stacks.get_mut(&self.stack) {
+            Some(count) => *count += 1,
+            None => {
+                self.stacks.insert(self.stack.clone(), 1);
+            }
+        }
+    }
+
+    /// Control was transferred to a function (or interrupt handler) at `target`
+    pub(crate) fn call(&mut self, target: Word) {
+        if let Some(&caller) = self.stack.last() {
+            *self.edges.entry((caller, target)).or_default() += 1;
+        }
+        self.stack.push(target);
+    }
+
+    /// The current function returned to its caller
+    pub(crate) fn ret(&mut self) {
+        // Never pop the entry point, a stray `RET` shouldn't lose the root
+        if self.stack.len() > 1 {
+            self.stack.pop();
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    pub fn ip_count(&self, ip: Word) -> u64 {
+        self.ips.get(&ip).copied().unwrap_or_default()
+    }
+
+    /// Executions of an ALU/branch/stack opcode
+    pub fn op_count(&self, op: Op) -> u64 {
+        self.ops[op.index()]
+    }
+
+    /// Executions of anything outside the base ALU/branch/stack ops, by mnemonic
+    pub fn instr_count(&self, mnemonic: &str) -> u64 {
+        self.instrs.get(mnemonic).copied().unwrap_or_default()
+    }
+
+    /// Name of the function starting at `entry`
+    fn function_name(&self, entry: Word) -> String {
+        let addr: isize = entry.into();
+        match self.symbols.lookup(addr) {
+            Some((name, 0)) => name.to_owned(),
+            Some((name, off)) => format!("{name}+{off}"),
+            None => format!("sub_{addr}"),
+        }
+    }
+
+    fn location(&self, ip: Word) -> String {
+        let addr: isize = ip.into();
+        match self.symbols.lookup(addr) {
+            Some((name, off)) => format!("{name}+{off}"),
+            None => "-".to_owned(),
+        }
+    }
+
+    fn percent(&self, count: u64) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            100.0 * count as f64 / self.total as f64
+        }
+    }
+
+    /// (self, inclusive) sample counts per function entry
+    fn function_counts(&self) -> HashMap<Word, (u64, u64)> {
+        let mut counts: HashMap<Word, (u64, u64)> = HashMap::new();
+        for (stack, &count) in &self.stacks {
+            if let Some(&leaf) = stack.last() {
+                counts.entry(leaf).or_default().0 += count;
+            }
+            // Recursive functions are only counted once per stack
+            let mut seen: Vec<Word> = Vec::with_capacity(stack.len());
+            for &frame in stack {
+                if !seen.contains(&frame) {
+                    seen.push(frame);
+                    counts.entry(frame).or_default().1 += count;
+                }
+            }
+        }
+        counts
+    }
+
+    /// A gprof style flat profile: time per function, the hottest instruction
+    /// pointers and the opcode mix.
+    pub fn flat_profile(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Flat profile ({} instructions)", self.total).unwrap();
+        writeln!(out, "{:>10} {:>10} {:>7}  function", "self", "total", "%").unwrap();
+        let mut functions: Vec<_> = self.function_counts().into_iter().collect();
+        functions.sort_by_key(|&(entry, (own, _))| (std::cmp::Reverse(own), entry));
+        for (entry, (own, inclusive)) in functions {
+            writeln!(
+                out,
+                "{own:>10} {inclusive:>10} {:>7.2}  {}",
+                self.percent(own),
+                self.function_name(entry)
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "\nHot spots").unwrap();
+        writeln!(out, "{:>10} {:>7} {:>10}  location", "count", "%", "address").unwrap();
+        let mut ips: Vec<_> = self.ips.iter().map(|(&ip, &count)| (ip, count)).collect();
+        ips.sort_by_key(|&(ip, count)| (std::cmp::Reverse(count), ip));
+        for (ip, count) in ips {
+            let addr: isize = ip.into();
+            writeln!(
+                out,
+                "{count:>10} {:>7.2} {addr:>10}  {}",
+                self.percent(count),
+                self.location(ip)
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "\nOpcodes").unwrap();
+        writeln!(out, "{:>10} {:>7}  opcode", "count", "%").unwrap();
+        let mut ops: Vec<(&str, u64)> = Tribble::iter()
+            .zip(self.ops)
+            .filter(|&(_, count)| count > 0)
+            .map(|(op, count)| (op_name(op), count))
+            .chain(self.instrs.iter().map(|(&name, &count)| (name, count)))
+            .collect();
+        ops.sort_by_key(|&(name, count)| (std::cmp::Reverse(count), name));
+        for (name, count) in ops {
+            writeln!(out, "{count:>10} {:>7.2}  {name}", self.percent(count)).unwrap();
+        }
+        out
+    }
+
+    /// The call graph built from the `CALL`/`RET` edges seen while running
+    pub fn call_graph(&self) -> String {
+        let counts = self.function_counts();
+        let mut functions: Vec<_> = counts.iter().map(|(&entry, &count)| (entry, count)).collect();
+        functions.sort_by_key(|&(entry, (_, inclusive))| (std::cmp::Reverse(inclusive), entry));
+
+        let mut out = String::new();
+        writeln!(out, "Call graph ({} instructions)", self.total).unwrap();
+        for (entry, (own, inclusive)) in functions {
+            writeln!(
+                out,
+                "{} [self {own}, total {inclusive}]",
+                self.function_name(entry)
+            )
+            .unwrap();
+            let mut callees: Vec<_> = self
+                .edges
+                .iter()
+                .filter(|((caller, _), _)| *caller == entry)
+                .map(|(&(_, callee), &calls)| (callee, calls))
+                .collect();
+            callees.sort_by_key(|&(callee, calls)| (std::cmp::Reverse(calls), callee));
+            for (callee, calls) in callees {
+                writeln!(out, "    -> {} ({calls} calls)", self.function_name(callee)).unwrap();
+            }
+        }
+        out
+    }
+
+    /// Samples in the collapsed stack format (`main;foo;bar 12`) understood by
+    /// flamegraph tools.
+    pub fn collapsed_stacks(&self) -> String {
+        let mut lines: Vec<String> = self
+            .stacks
+            .iter()
+            .map(|(stack, count)| {
+                let frames: Vec<String> = stack.iter().map(|&f| self.function_name(f)).collect();
+                format!("{} {count}", frames.join(";"))
+            })
+            .collect();
+        lines.sort();
+        let mut out = lines.join("\n");
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Mnemonics of the ALU/branch/stack opcodes, from `Z` up to `M`
+const OP_NAMES: [&str; 27] = [
+    "bpn", "bpp", "bpz", "bgq", "blq", "blt", "bgt", "bne", "beq", "cmp", "stre", "load", "ret",
+    "???", "add", "sub", "mul", "qot", "rem", "and", "or", "sft", "not", "rot", "push", "pop",
+    "call",
+];
+
+/// Assembly mnemonic of an ALU/branch/stack opcode
+pub fn op_name(op: Op) -> &'static str {
+    OP_NAMES[op.index()]
+}
+
+/// Name of an opcode from the extended ALU group
+pub fn ext_op_name(op: Op) -> &'static str {
+    match op {
+        isa::ADC_T => "adc",
+        isa::SBB_T => "sbb",
+        isa::MULH_T => "mulh",
+        _ => "???",
+    }
+}
+
+/// Name of an opcode from the FPU group
+pub fn fpu_op_name(op: Op) -> &'static str {
+    match op {
+        isa::FADD_T => "fadd",
+        isa::FSUB_T => "fsub",
+        isa::FMUL_T => "fmul",
+        isa::FDIV_T => "fdiv",
+        isa::FCMP_T => "fcmp",
+        isa::ITOF_T => "itof",
+        isa::FTOI_T => "ftoi",
+        _ => "???",
+    }
+}
+
+/// Assembly mnemonic of an instruction
+pub fn instr_name(instr: &Instr) -> &'static str {
+    match instr {
+        Instr::HALT => "hlt",
+        Instr::DTI => "dti",
+        Instr::STI => "sti",
+        Instr::WFI => "wfi",
+        Instr::RTI => "rti",
+        Instr::LIT(_) => "lit",
+        Instr::INTERRUPT(_) => "int",
+        Instr::EGPU(_) => "egpu",
+        Instr::LVB(..) => "lvb",
+        Instr::EGEL(_) => "egel",
+        Instr::PCSR => "pcsr",
+        Instr::PPSR => "ppsr",
+        Instr::PPTR => "pptr",
+        Instr::POCSR => "pocsr",
+        Instr::POPSR => "popsr",
+        Instr::POPTR => "poptr",
+        Instr::LPT(_) => "lpt",
+        Instr::INTM(_) => "intm",
+        Instr::INTE(_) => "inte",
+        Instr::INTS(_) => "ints",
+        Instr::IN(..) => "in",
+        Instr::OUT(..) => "out",
+        Instr::OPRR(Tribble([_, _, Trit::NOne]), op, ..)
+        | Instr::OPRI(Tribble([_, _, Trit::NOne]), op, ..) => ext_op_name(*op),
+        Instr::OPRR(Tribble([_, _, Trit::POne]), op, ..)
+        | Instr::OPRI(Tribble([_, _, Trit::POne]), op, ..) => fpu_op_name(*op),
+        Instr::OPRR(_, op, ..) | Instr::OPRI(_, op, ..) => op_name(*op),
+        Instr::CALL(..) => "call",
+        Instr::RET => "ret",
+        Instr::ENTER => "enter",
+        Instr::LEAVE => "leave",
+        Instr::INVALID => "invalid",
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ternary::word::Word;
+    use tobj::{Symbol, TObj};
+
+    use crate::{
+        cpu::{JX_01, profile::{Profiler, SymbolMap}},
+        isa::{
+            code::DecEncExt, encode, registers::*, Instr, ADD_T, ALU_CTRL_R_RI, ALU_CTRL_R_RR, BEQ_T, BGT_T,
+            CALL_CTRL_R, CMP_T, MUL_T, POP_T, PUSH_T,
+        },
+    };
+
+    #[test]
+    fn symbol_map() {
+        let symbols: SymbolMap = "; comment\n0 main\n\n15 fact\n48 hlt\n".parse().unwrap();
+        assert_eq!(symbols.lookup(0), Some(("main", 0)));
+        assert_eq!(symbols.lookup(12), Some(("main", 12)));
+        assert_eq!(symbols.lookup(21), Some(("fact", 6)));
+        assert_eq!(symbols.lookup(-3), None);
+        assert_eq!("0 main\nfact\n".parse::<SymbolMap>(), Err(2));
+        assert_eq!("x main".parse::<SymbolMap>(), Err(1));
+    }
+
+    #[test]
+    fn profile_recursion() {
+        use Instr::*;
+
+        // Recursive factorial from `event_loop::tests::test_func`
+        let instrs = [
+            /* 00 */ OPRI(ALU_CTRL_R_RI, ADD_T, NN11, 2.into()),
+            /* 03 */ OPRI(ALU_CTRL_R_RI, ADD_T, NN13, 4.into()),
+            /* 06 */ CALL(N0, CALL_CTRL_R, 15.into()),
+            /* 09 */ OPRR(ALU_CTRL_R_RR, CMP_T, N0, N0, Word::ZERO),
+            /* 12 */ OPRI(ALU_CTRL_R_RI, BEQ_T, N0, 48.into()),
+            // fact:
+            /* 15 */ OPRR(ALU_CTRL_R_RR, CMP_T, NN13, NN11, Word::ZERO),
+            /* 18 */ OPRI(ALU_CTRL_R_RI, BGT_T, N0, 30.into()),
+            /* 21 */ OPRI(ALU_CTRL_R_RI, ADD_T, NN12, 2.into()),
+            /* 24 */ OPRR(ALU_CTRL_R_RR, CMP_T, N0, N0, Word::ZERO),
+            /* 27 */ OPRI(ALU_CTRL_R_RI, BEQ_T, N0, 45.into()),
+            // calcs:
+            /* 30 */ OPRI(ALU_CTRL_R_RI, PUSH_T, NN13, Word::ZERO),
+            /* 33 */ OPRI(ALU_CTRL_R_RI, ADD_T, NN13, Word::NONE),
+            /* 36 */ CALL(N0, CALL_CTRL_R, 15.into()),
+            /* 39 */ OPRI(ALU_CTRL_R_RI, POP_T, NN10, Word::ZERO),
+            /* 42 */ OPRR(ALU_CTRL_R_RR, MUL_T, NN12, NN10, Word::ZERO),
+            // return:
+            /* 45 */ RET,
+            /* 48 */ HALT,
+        ];
+        instrs.check();
+
+        let symbols: SymbolMap = "0 main\n15 fact\n48 done".parse().unwrap();
+
+        let mut cpu = JX_01::new();
+        cpu.import_instrs(&instrs);
+        cpu.enable_profiling(Profiler::new(symbols));
+        cpu.run_program();
+        let profile = cpu.take_profile().unwrap();
+
+        assert_eq!(cpu.registers.get_word(NN12), 24.into());
+
+        // main runs 6 instructions (including the halt), fact(4) and fact(3)
+        // recurse with 8 each and the base case fact(2) runs 6
+        assert_eq!(profile.total(), 6 + 8 + 8 + 6);
+        assert_eq!(profile.ip_count(15.into()), 3);
+        assert_eq!(profile.ip_count(48.into()), 1);
+        assert_eq!(profile.instr_count("call"), 3);
+        assert_eq!(profile.instr_count("ret"), 3);
+        assert_eq!(profile.op_count(CMP_T), 1 + 3 + 1);
+        assert_eq!(profile.op_count(MUL_T), 2);
+        assert_eq!(profile.op_count(ADD_T), 2 + 2 + 1);
+
+        assert_eq!(
+            profile.collapsed_stacks(),
+            "main 6\nmain;fact 8\nmain;fact;fact 8\nmain;fact;fact;fact 6\n"
+        );
+
+        let graph = profile.call_graph();
+        assert!(graph.contains("main [self 6, total 28]\n    -> fact (1 calls)"), "{graph}");
+        assert!(graph.contains("fact [self 22, total 22]\n    -> fact (2 calls)"), "{graph}");
+
+        let flat = profile.flat_profile();
+        assert!(flat.starts_with("Flat profile (28 instructions)"), "{flat}");
+        assert!(flat.contains("fact+0"), "{flat}");
+    }
+
+    #[test]
+    fn profile_tobj() {
+        use Instr::*;
+
+        let text = [
+            /* 00 */ RET,
+            /* 03 */ CALL(N0, CALL_CTRL_R, Word::ZERO),
+            /* 06 */ HALT,
+        ];
+        text.check();
+        let sym: Vec<Word> = [Symbol::new(0, "leaf"), Symbol::new(3, "start")]
+            .iter()
+            .flat_map(|symbol| symbol.to_words().unwrap())
+            .collect();
+        let mut image: Vec<Word> = [tobj::MAGIC as isize, 3, 0, 0, sym.len() as isize, 3, 0, 0]
+            .map(Word::from)
+            .to_vec();
+        image.extend(text.map(encode));
+        image.extend(sym);
+        let tobj = TObj::parse(&image).unwrap();
+
+        let mut cpu = JX_01::new();
+        let entry = cpu.import_tobj(&tobj);
+        cpu.enable_profiling(Profiler::new(tobj.symbols().unwrap().into_iter().collect()));
+        cpu.run_from(entry);
+        let profile = cpu.take_profile().unwrap();
+
+        assert_eq!(entry, 3.into());
+        assert_eq!(profile.collapsed_stacks(), "start 2\nstart;leaf 1\n");
+    }
+}
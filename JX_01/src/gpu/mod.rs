@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
 use sdl3::{Sdl, pixels::Color, render::Canvas, video::Window};
-use ternary::{trits::Trit, word::Word};
+use ternary::{ternary::Ternary, trits::Trit, tritvec::TritVec, word::Word};
 
 use crate::memory::{Address, Memory};
 
@@ -142,19 +142,14 @@ impl Gpu {
 
 /// All coordinates must be less than 364
 pub fn make_line(one: (isize, isize), two: (isize, isize), color: [Trit; 3]) -> Word {
-    let one: (Word, Word) = (one.0.into(), one.1.into());
-    let two: (Word, Word) = (two.0.into(), two.1.into());
-    let one: ([Trit; 27], [Trit; 27]) = (one.0.into(), one.1.into());
-    let two: ([Trit; 27], [Trit; 27]) = (two.0.into(), two.1.into());
-
-    let mut result = [Trit::Zero; 27];
-    result[0..6].copy_from_slice(&one.0[0..6]);
-    result[6..12].copy_from_slice(&one.1[0..6]);
-    result[12..18].copy_from_slice(&two.0[0..6]);
-    result[18..24].copy_from_slice(&two.1[0..6]);
-    result[24..27].copy_from_slice(&color);
-
-    result.into()
+    let mut result = TritVec::zeros(27);
+    result.set_field(0..6, Word::from(one.0));
+    result.set_field(6..12, Word::from(one.1));
+    result.set_field(12..18, Word::from(two.0));
+    result.set_field(18..24, Word::from(two.1));
+    result.set_field(24..27, Ternary::from(color));
+
+    Word::try_from(&result).unwrap()
 }
 
 #[cfg(test)]
@@ -12,12 +12,12 @@ pub fn encode(instr: Instr) -> Word {
         Instr::RTI => [V, R, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         Instr::LIT(register) => [V, A, register.0, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         Instr::INTERRUPT(tryte) => {
-            let [i, n, t]: [[Trit; 3]; 3] = tryte.into();
+            let [i, n, t]: [Tribble; 3] = tryte.into();
             [V, I, I, t, n, i, ZERO, ZERO, ZERO].into()
         }
         Instr::EGPU(register) => [V, G, register.0, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         Instr::LVB(register, word) => {
-            let [a, b, c, d, e, f, _, _, _]: [[Trit; 3]; 9] = word.into();
+            let [a, b, c, d, e, f, _, _, _]: [Tribble; 9] = word.into();
             [V, L, register.0, f, e, d, c, b, a].into()
         }
         Instr::EGEL(register) => [V, X, register.0, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
@@ -28,22 +28,22 @@ pub fn encode(instr: Instr) -> Word {
         Instr::POPSR => [V, D, A, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         Instr::POPTR => [V, E, A, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         Instr::INTM(tryte) => {
-            let [a, b, c]: [[Trit; 3]; 3] = tryte.into();
+            let [a, b, c]: [Tribble; 3] = tryte.into();
             [V, I, M, c, b, a, ZERO, ZERO, ZERO].into()
         }
         Instr::INTE(tryte) => {
-            let [a, b, c]: [[Trit; 3]; 3] = tryte.into();
+            let [a, b, c]: [Tribble; 3] = tryte.into();
             [V, I, E, c, b, a, ZERO, ZERO, ZERO].into()
         }
         Instr::INTS(tryte) => {
-            let [a, b, c]: [[Trit; 3]; 3] = tryte.into();
+            let [a, b, c]: [Tribble; 3] = tryte.into();
             [V, I, S, c, b, a, ZERO, ZERO, ZERO].into()
         }
         Instr::LPT(register) => [V, P, register.0, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         Instr::IN(register, control, tryte) => {
-            let [a, b, c]: [[Trit; 3]; 3] = tryte.into();
+            let [a, b, c]: [Tribble; 3] = tryte.into();
             [
-                [control[0], control[1], Trit::POne],
+                Tribble([control.0[0], control.0[1], Trit::POne]),
                 Q,
                 register.0,
                 c,
@@ -56,9 +56,9 @@ pub fn encode(instr: Instr) -> Word {
             .into()
         }
         Instr::OUT(register, control, tryte) => {
-            let [a, b, c]: [[Trit; 3]; 3] = tryte.into();
+            let [a, b, c]: [Tribble; 3] = tryte.into();
             [
-                [control[0], control[1], Trit::POne],
+                Tribble([control.0[0], control.0[1], Trit::POne]),
                 V,
                 register.0,
                 c,
@@ -81,7 +81,7 @@ pub fn encode(instr: Instr) -> Word {
         Instr::CALL(register, control, word) => {
             let [a, b, c, d, e, f, _, _, _] = word.into();
             [
-                [Trit::Zero, control[1], Trit::Zero],
+                Tribble([Trit::Zero, control.0[1], Trit::Zero]),
                 CALL,
                 register.0,
                 f,
@@ -101,7 +101,7 @@ pub fn encode(instr: Instr) -> Word {
 }
 
 pub fn decode(word: Word) -> Instr {
-    let word: [[Trit; 3]; 9] = word.into();
+    let word: [Tribble; 9] = word.into();
     match word {
         [V, I, I, i, n, t, ..] => {
             let tryte = [t, n, i].into();
@@ -131,14 +131,14 @@ pub fn decode(word: Word) -> Instr {
         [V, I, E, a, b, c, ..] => Instr::INTE([c, b, a].into()),
         [V, I, S, a, b, c, ..] => Instr::INTS([c, b, a].into()),
         // POne, control (reg size), RR/RI (doesn't matter)
-        [ctrl @ [_, _, Trit::POne], Q, r, a, b, c, ..] => {
+        [ctrl @ Tribble([_, _, Trit::POne]), Q, r, a, b, c, ..] => {
             Instr::IN(Register(r), ctrl, [c, b, a].into())
         }
-        [ctrl @ [_, _, Trit::POne], V, r, a, b, c, ..] => {
+        [ctrl @ Tribble([_, _, Trit::POne]), V, r, a, b, c, ..] => {
             Instr::OUT(Register(r), ctrl, [c, b, a].into())
         }
         // Call/Ret/Enter/Leave
-        [ctrl @ [_, _, Trit::Zero], CALL, r, a, b, c, d, e, f] => Instr::CALL(
+        [ctrl @ Tribble([_, _, Trit::Zero]), CALL, r, a, b, c, d, e, f] => Instr::CALL(
             Register(r),
             ctrl,
             [f, e, d, c, b, a, ZERO, ZERO, ZERO].into(),
@@ -150,14 +150,16 @@ pub fn decode(word: Word) -> Instr {
         // Instr::LEAVE => [I, N, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
         [I, RET, ..] => Instr::LEAVE,
         // TODO: Consolidate the Op RR/RI into one instruction?
-        [ctrl @ [Trit::NOne, _, Trit::Zero], op, r1, r2, a, b, c, d, e] => Instr::OPRR(
+        // The FPU controls end in 1 like IN/OUT's, so the arms above already took ops Q and V
+        [ctrl @ Tribble([Trit::NOne, _, Trit::Zero | Trit::NOne] | [Trit::NOne, Trit::POne, Trit::POne]), op, r1, r2, a, b, c, d, e] => Instr::OPRR(
             ctrl,
             op,
             Register(r1),
             Register(r2),
             [e, d, c, b, a, ZERO, ZERO, ZERO, ZERO].into(),
         ),
-        [ctrl @ [Trit::Zero, _, Trit::Zero], op, r, a, b, c, d, e, f] => Instr::OPRI(
+        // A size of 0 with a trailing T is V, which the CPU specific instructions already took
+        [ctrl @ Tribble([Trit::Zero, _, Trit::Zero] | [Trit::Zero, Trit::NOne | Trit::POne, Trit::NOne] | [Trit::Zero, Trit::POne, Trit::POne]), op, r, a, b, c, d, e, f] => Instr::OPRI(
             ctrl,
             op,
             Register(r),
@@ -197,6 +199,9 @@ pub mod tests {
         use crate::isa::registers::*;
         use crate::isa::{
             ALU_CTRL_R_RI, ALU_CTRL_R_RR, CALL_CTRL_R, CALL_CTRL_T, IN_CTRL_R, IN_CTRL_T,
+            EXT_CTRL_R_RI, EXT_CTRL_R_RR, EXT_CTRL_T_RI, EXT_CTRL_T_RR, ADC_T as ADC,
+            MULH_T as MULH, SBB_T as SBB, FPU_CTRL_RI, FPU_CTRL_RR, FADD_T as FADD,
+            FCMP_T as FCMP, FTOI_T as FTOI, ITOF_T as ITOF,
         };
         use ternary::word::Word;
 
@@ -227,6 +232,14 @@ pub mod tests {
             OUT(NN13, IN_CTRL_R, 12.into()),
             OPRR(ALU_CTRL_R_RR, ADD, N12, N13, Word::ZERO),
             OPRI(ALU_CTRL_R_RI, QOT, N11, Word::NONE),
+            OPRR(EXT_CTRL_T_RR, ADC, N1, N2, Word::PONE),
+            OPRR(EXT_CTRL_R_RR, SBB, N3, N4, Word::ZERO),
+            OPRI(EXT_CTRL_T_RI, MULH, N5, 364.into()),
+            OPRI(EXT_CTRL_R_RI, ADC, NN13, (-364).into()),
+            OPRR(FPU_CTRL_RR, FADD, N1, N2, Word::ZERO),
+            OPRR(FPU_CTRL_RR, FCMP, N3, N4, Word::ZERO),
+            OPRI(FPU_CTRL_RI, ITOF, N5, (-364).into()),
+            OPRI(FPU_CTRL_RI, FTOI, NN13, Word::ZERO),
             CALL(N4, CALL_CTRL_R, 6.into()),
             CALL(N4, CALL_CTRL_T, 6.into()),
             RET,
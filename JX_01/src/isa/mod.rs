@@ -1,5 +1,5 @@
 use septivigntimal::{Tribble, *};
-use ternary::{trits::Trit, tryte::Tryte, word::Word};
+use ternary::{TRYTE_LEN, trits::Trit, tryte::Tryte, word::Word};
 
 use crate::isa::registers::Register;
 
@@ -10,7 +10,6 @@ pub mod registers;
 
 pub type Control = Tribble;
 pub type Op = Tribble;
-pub type Opt = u32;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Instr {
@@ -76,54 +75,102 @@ pub enum Instr {
     INVALID,
 }
 
-pub const IN_CTRL_T: Control = [Trit::Zero, Trit::NOne, Trit::POne];
-pub const IN_CTRL_R: Control = [Trit::Zero, Trit::POne, Trit::POne];
-pub const OUT_CTRL_T: Control = [Trit::Zero, Trit::NOne, Trit::POne];
-pub const OUT_CTRL_R: Control = [Trit::Zero, Trit::POne, Trit::POne];
+pub const IN_CTRL_T: Control = Tribble([Trit::Zero, Trit::NOne, Trit::POne]);
+pub const IN_CTRL_R: Control = Tribble([Trit::Zero, Trit::POne, Trit::POne]);
+pub const OUT_CTRL_T: Control = Tribble([Trit::Zero, Trit::NOne, Trit::POne]);
+pub const OUT_CTRL_R: Control = Tribble([Trit::Zero, Trit::POne, Trit::POne]);
 
-pub const ALU_CTRL_T_RR: Control = [Trit::NOne, Trit::NOne, Trit::Zero];
-pub const ALU_CTRL_T_RI: Control = [Trit::Zero, Trit::NOne, Trit::Zero];
-pub const ALU_CTRL_R_RR: Control = [Trit::NOne, Trit::POne, Trit::Zero];
-pub const ALU_CTRL_R_RI: Control = [Trit::Zero, Trit::POne, Trit::Zero];
+pub const ALU_CTRL_T_RR: Control = Tribble([Trit::NOne, Trit::NOne, Trit::Zero]);
+pub const ALU_CTRL_T_RI: Control = Tribble([Trit::Zero, Trit::NOne, Trit::Zero]);
+pub const ALU_CTRL_R_RR: Control = Tribble([Trit::NOne, Trit::POne, Trit::Zero]);
+pub const ALU_CTRL_R_RI: Control = Tribble([Trit::Zero, Trit::POne, Trit::Zero]);
 
-pub const CALL_CTRL_R: Control = [Trit::Zero, Trit::POne, Trit::Zero];
-pub const CALL_CTRL_T: Control = [Trit::Zero, Trit::NOne, Trit::Zero];
+// The extended ALU group (ADC, SBB, MULH, ...) ends its control tribble with T rather than 0
+pub const EXT_CTRL_T_RR: Control = Tribble([Trit::NOne, Trit::NOne, Trit::NOne]);
+pub const EXT_CTRL_T_RI: Control = Tribble([Trit::Zero, Trit::NOne, Trit::NOne]);
+pub const EXT_CTRL_R_RR: Control = Tribble([Trit::NOne, Trit::POne, Trit::NOne]);
+pub const EXT_CTRL_R_RI: Control = Tribble([Trit::Zero, Trit::POne, Trit::NOne]);
 
-pub const fn op_to_opt(op: Op) -> Opt {
-    let mut opt: u32 = 0;
-    opt |= (op[0] as u8) as u32 ;
-    opt |= ((op[1] as u8) << 2) as u32 ;
-    opt |= ((op[2] as u8) << 4) as u32 ;
+// The FPU group (FADD, FCMP, ITOF, ...) ends its control tribble with 1. Floats are always a word,
+// and the ops steer clear of Q and V, which IN and OUT use with the same controls.
+pub const FPU_CTRL_RR: Control = Tribble([Trit::NOne, Trit::POne, Trit::POne]);
+pub const FPU_CTRL_RI: Control = Tribble([Trit::Zero, Trit::POne, Trit::POne]);
 
-    opt
+/// Operand size selected by the middle trit of a control tribble
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Size {
+    /// 9 trits, from a `T` control tribble
+    Tryte,
+    /// 27 trits, from an `R` control tribble
+    Word,
 }
 
-pub const BPN:  Opt = op_to_opt(BPN_T);
-pub const BPP:  Opt = op_to_opt(BPP_T);
-pub const BPZ:  Opt = op_to_opt(BPZ_T);
-pub const BGQ:  Opt = op_to_opt(BGQ_T);
-pub const BLQ:  Opt = op_to_opt(BLQ_T);
-pub const BLT:  Opt = op_to_opt(BLT_T);
-pub const BGT:  Opt = op_to_opt(BGT_T);
-pub const BNE:  Opt = op_to_opt(BNE_T);
-pub const BEQ:  Opt = op_to_opt(BEQ_T);
-pub const CMP:  Opt = op_to_opt(CMP_T);
-pub const STRE: Opt = op_to_opt(STRE_T);
-pub const LOAD: Opt = op_to_opt(LOAD_T);
-pub const ADD:  Opt = op_to_opt(ADD_T);
-pub const SUB:  Opt = op_to_opt(SUB_T);
-pub const MUL:  Opt = op_to_opt(MUL_T);
-pub const QOT:  Opt = op_to_opt(QOT_T);
-pub const REM:  Opt = op_to_opt(REM_T);
-pub const AND:  Opt = op_to_opt(AND_T);
-pub const OR:   Opt = op_to_opt(OR_T);
-pub const SFT:  Opt = op_to_opt(SFT_T);
-pub const NOT:  Opt = op_to_opt(NOT_T);
-pub const ROT:  Opt = op_to_opt(ROT_T);
-pub const PUSH: Opt = op_to_opt(PUSH_T);
-pub const POP:  Opt = op_to_opt(POP_T);
-pub const CALL: Opt = op_to_opt(CALL_T);
-pub const RET:  Opt = op_to_opt(RET_T);
+impl Size {
+    pub const fn from_ctrl(ctrl: Control) -> Size {
+        match ctrl.0[1] {
+            Trit::NOne => Size::Tryte,
+            _ => Size::Word,
+        }
+    }
+
+    /// Wraps `val` to the low trits of this size
+    pub fn truncate(self, val: Word) -> Word {
+        match self {
+            Size::Tryte => <Tryte as Into<Word>>::into(val.into()),
+            Size::Word => val,
+        }
+    }
+
+    /// Rotates within this size, so the trits carried out of the top of a
+    /// tryte come back in at its bottom
+    pub fn rot(self, val: Word, by: isize) -> Word {
+        match self {
+            Size::Tryte => Tryte::from(val).rot(by).into(),
+            Size::Word => val.rot(by),
+        }
+    }
+
+    /// Adds with an incoming carry at this size, returning the wrapped sum and the trit carried out
+    pub fn carrying_add(self, lhs: Word, rhs: Word, carry: Trit) -> (Word, Trit) {
+        match self {
+            Size::Tryte => {
+                let res = (self.truncate(lhs) + self.truncate(rhs)) + carry;
+                (self.truncate(res), (res >> TRYTE_LEN).get_sign())
+            }
+            Size::Word => lhs.carrying_add(rhs, carry),
+        }
+    }
+
+    /// Adds at this size, returning the wrapped sum and the trit carried out of it
+    pub fn overflowing_add(self, lhs: Word, rhs: Word) -> (Word, Trit) {
+        self.carrying_add(lhs, rhs, Trit::Zero)
+    }
+
+    /// Full product at this size, as `(low, high)`
+    pub fn widening_mul(self, lhs: Word, rhs: Word) -> (Word, Word) {
+        match self {
+            Size::Tryte => {
+                let res = self.truncate(lhs) * self.truncate(rhs);
+                (self.truncate(res), res >> TRYTE_LEN)
+            }
+            Size::Word => lhs.widening_mul(rhs),
+        }
+    }
+
+    /// Multiplies at this size, returning the wrapped product and the sign of what was cut off
+    pub fn overflowing_mul(self, lhs: Word, rhs: Word) -> (Word, Trit) {
+        let (low, high) = self.widening_mul(lhs, rhs);
+        (low, high.get_sign())
+    }
+}
+
+pub const CALL_CTRL_R: Control = Tribble([Trit::Zero, Trit::POne, Trit::Zero]);
+pub const CALL_CTRL_T: Control = Tribble([Trit::Zero, Trit::NOne, Trit::Zero]);
+
+/// The branches are the ops from BPN up to BEQ
+pub fn is_branch(op: Op) -> bool {
+    (BPN_T..=BEQ_T).contains(&op)
+}
 
 pub const BPN_T: Op = Z;
 pub const BPP_T: Op = Y;
@@ -144,10 +191,26 @@ pub const QOT_T: Op = D;
 pub const REM_T: Op = E;
 pub const AND_T: Op = F;
 pub const OR_T: Op = G;
+/// Shifts towards the high trits by a positive count, multiplying by 3 per
+/// trit, and towards the low trits by a negative one
 pub const SFT_T: Op = H;
 pub const NOT_T: Op = I;
+/// Turns the same way [`SFT_T`] shifts, with the trits that leave one end
+/// coming back in at the other
 pub const ROT_T: Op = J;
 pub const PUSH_T: Op = K;
 pub const POP_T: Op = L;
 pub const CALL_T: Op = M;
 pub const RET_T: Op = N;
+
+pub const ADC_T: Op = A;
+pub const SBB_T: Op = B;
+pub const MULH_T: Op = C;
+
+pub const FADD_T: Op = A;
+pub const FSUB_T: Op = B;
+pub const FMUL_T: Op = C;
+pub const FDIV_T: Op = D;
+pub const FCMP_T: Op = E;
+pub const ITOF_T: Op = F;
+pub const FTOI_T: Op = G;
@@ -1,9 +1,82 @@
-use std::array;
+use std::{array, fs, path::PathBuf, process::ExitCode};
 
-use JX_01::{gpu::make_line, isa::{ADD_T, ALU_CTRL_R_RI, ALU_CTRL_R_RR, BEQ_T, CALL_CTRL_R, CMP_T, Instr, code::DecEncExt, encode, registers::*}};
+use clap::Parser;
+use JX_01::{cpu::profile::{Profiler, SymbolMap}, gpu::make_line, isa::{ADD_T, ALU_CTRL_R_RI, ALU_CTRL_R_RR, BEQ_T, CALL_CTRL_R, CMP_T, Instr, code::DecEncExt, encode, registers::*}};
 use ternary::{trits::Trit, word::Word};
+use tobj::TObj;
 
-fn main() {
+/// The JX_01 emulator
+#[derive(Parser, Debug)]
+#[command(version, about, long_about)]
+struct Config {
+    /// tobj image to run, the hello world demo if missing
+    program: Option<PathBuf>,
+    /// Profile the program, writing the flat profile and call graph here
+    /// when it halts
+    #[arg(long)]
+    profile: Option<PathBuf>,
+    /// Profile the program, writing its collapsed stacks here when it halts,
+    /// for flamegraph tools
+    #[arg(long)]
+    stacks: Option<PathBuf>,
+    /// `<address> <name>` listing to name functions with, on top of the
+    /// program's symbols
+    #[arg(long)]
+    symbols: Option<PathBuf>,
+}
+
+fn run(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let mut cpu = JX_01::cpu::JX_01::new();
+    let mut symbols = SymbolMap::new();
+    let entry = match &config.program {
+        Some(path) => {
+            let image = jxdump::read_image(&fs::read(path)?)?;
+            let tobj = TObj::parse(&image)?;
+            symbols.extend(tobj.symbols()?);
+            cpu.import_tobj(&tobj)
+        }
+        None => {
+            cpu.import_memory(&demo());
+            Word::ZERO
+        }
+    };
+    if let Some(path) = &config.symbols {
+        let listing = fs::read_to_string(path)?;
+        let listing: SymbolMap = listing
+            .parse()
+            .map_err(|line| format!("{}:{line}: expected `<address> <name>`", path.display()))?;
+        symbols.extend(listing);
+    }
+
+    if config.profile.is_some() || config.stacks.is_some() {
+        cpu.enable_profiling(Profiler::new(symbols));
+    }
+    cpu.run_from(entry);
+
+    if let Some(profile) = cpu.take_profile() {
+        if let Some(path) = &config.profile {
+            fs::write(path, profile.flat_profile() + "\n" + &profile.call_graph())?;
+        }
+        if let Some(path) = &config.stacks {
+            fs::write(path, profile.collapsed_stacks())?;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let config = Config::parse();
+    match run(&config) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("JX_01: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Draws "Hello World" on the GPU, one letter an interrupt
+fn demo() -> Vec<Word> {
         use Instr::*;
 
         // main:
@@ -194,8 +267,5 @@ fn main() {
         data.extend_from_slice(&mut gpu);
         data.extend_from_slice(&mut idt);
         data.extend_from_slice(&mut vec_buf);
-
-        let mut cpu = JX_01::cpu::JX_01::new();
-        cpu.import_memory(&data);
-        cpu.run_program();
-} 
+        data
+}
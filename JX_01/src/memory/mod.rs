@@ -8,7 +8,10 @@
 //! n: n-level index
 //! offset: tryte offset into page
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    hash::{BuildHasherDefault, Hasher},
+};
 
 use ternary::word::Word;
 
@@ -24,7 +27,30 @@ pub type Address = Word;
 #[derive(Default)]
 pub struct Memory {
     // mmu: Box<MMU>,
-    memory: HashMap<u64, Page>,
+    memory: HashMap<u64, Page, BuildHasherDefault<PageHasher>>,
+}
+
+/// Every fetch looks up a page, so the keys get a multiply and a fold
+/// instead of SipHash. They're our own addresses, there's nothing to defend.
+#[derive(Default)]
+struct PageHasher(u64);
+
+impl Hasher for PageHasher {
+    fn finish(&self) -> u64 {
+        // The low trits of a key are always zero, so fold the high bits down
+        let hash = self.0.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        hash ^ (hash >> 32)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ byte as u64;
+        }
+    }
+
+    fn write_u64(&mut self, key: u64) {
+        self.0 ^= key;
+    }
 }
 
 pub type Page = [Word; PAGE_TABLE_SIZE];
@@ -45,23 +71,12 @@ pub const EMPTY_PAGE: Page = [Word::ZERO; PAGE_TABLE_SIZE];
 impl Memory {
     pub(crate) fn get_physical_word(&mut self, index: Address) -> &Word {
         // We want the first 7 trits to be zero, but the next 20 to be our type
-        let addr_page =
-            (
-                (index.num() & (0b1111111111111111111111111111111111111111 << 14))
-                | (Word::ZERO.num() & 0b11111111111111)
-            ) & Word::WORD_BIT_MASK;
-        let addr_elem = unsafe {
-            Word::from_u64(
-                ((index.num() & 0b11111111111111) | (Word::ZERO.num() << 14)) & Word::WORD_BIT_MASK,
-            )
-        };
-
-        let page = if self.memory.contains_key(&addr_page) {
-            self.memory.get(&addr_page).unwrap()
-        } else {
-            let _ = self.memory.insert(addr_page, EMPTY_PAGE);
-            self.memory.get(&addr_page).unwrap()
-        };
+        let mut page_base = index;
+        page_base.set_field(0..7, Word::ZERO);
+        let addr_page = page_base.num();
+        let addr_elem = index.get_field(0..7);
+
+        let page = self.memory.entry(addr_page).or_insert_with(|| EMPTY_PAGE);
         
         // let page = match self.memory.get(&addr_page) {
         //     Some(page) => page,
@@ -78,16 +93,10 @@ impl Memory {
 
     pub(crate) fn get_physical_word_mut(&mut self, index: Address) -> &mut Word {
         // We want the first 7 trits to be zero, but the next 20 to be our type
-        let addr_page =
-            (
-                (index.num() & (0b1111111111111111111111111111111111111111 << 14))
-                | (Word::ZERO.num() & 0b11111111111111)
-            ) & Word::WORD_BIT_MASK;
-        let addr_elem = unsafe {
-            Word::from_u64(
-                ((index.num() & 0b11111111111111) | (Word::ZERO.num() << 14)) & Word::WORD_BIT_MASK,
-            )
-        };
+        let mut page_base = index;
+        page_base.set_field(0..7, Word::ZERO);
+        let addr_page = page_base.num();
+        let addr_elem = index.get_field(0..7);
         // let addr_page = ((index.num() & (0b1111111111111111111111111111111111111111 << 14))
         //     | (Word::ZERO.num() & 0b11111111111111))
         //     & Word::WORD_BIT_MASK;
@@ -97,12 +106,7 @@ impl Memory {
         //     )
         // };
 
-        let page = if self.memory.contains_key(&addr_page) {
-            self.memory.get_mut(&addr_page).unwrap()
-        } else {
-            let _ = self.memory.insert(addr_page, EMPTY_PAGE);
-            self.memory.get_mut(&addr_page).unwrap()
-        };
+        let page = self.memory.entry(addr_page).or_insert_with(|| EMPTY_PAGE);
         
         // let page = match self.memory.get_mut(&addr_page) {
         //     Some(page) => page,
@@ -1,4 +1,4 @@
-use JX_01::isa::registers::Register;
+use JX_01::isa::{self, registers::Register};
 use ternary::trits::Trit;
 
 pub enum Item<'src> {
@@ -97,6 +97,7 @@ pub enum Instr<'src> {
     RET,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Op {
     BPN,
     BPP,
@@ -124,4 +125,159 @@ pub enum Op {
     PUSH,
     POP,
     MOV,
+    ADC,
+    SBB,
+    MULH,
+    FADD,
+    FSUB,
+    FMUL,
+    FDIV,
+    FCMP,
+    ITOF,
+    FTOI,
+}
+
+/// Which opcode table an op lives in, picked by the last trit of its control tribble
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Group {
+    /// ALU, branches, loads and stores and the stack
+    Base,
+    /// Extended ALU
+    Ext,
+    Fpu,
+}
+
+impl Op {
+    pub const MNEMONICS: [(&'static str, Op); 36] = [
+        ("bpn", Op::BPN),
+        ("bpp", Op::BPP),
+        ("bpz", Op::BPZ),
+        ("bgq", Op::BGQ),
+        ("blq", Op::BLQ),
+        ("blt", Op::BLT),
+        ("bgt", Op::BGT),
+        ("bne", Op::BNE),
+        ("beq", Op::BEQ),
+        ("jmp", Op::JMP),
+        ("cmp", Op::CMP),
+        ("stre", Op::STRE),
+        ("load", Op::LOAD),
+        ("add", Op::ADD),
+        ("sub", Op::SUB),
+        ("mul", Op::MUL),
+        ("qot", Op::QOT),
+        ("rem", Op::REM),
+        ("and", Op::AND),
+        ("or", Op::OR),
+        ("sft", Op::SFT),
+        ("not", Op::NOT),
+        ("rot", Op::ROT),
+        ("push", Op::PUSH),
+        ("pop", Op::POP),
+        ("mov", Op::MOV),
+        ("adc", Op::ADC),
+        ("sbb", Op::SBB),
+        ("mulh", Op::MULH),
+        ("fadd", Op::FADD),
+        ("fsub", Op::FSUB),
+        ("fmul", Op::FMUL),
+        ("fdiv", Op::FDIV),
+        ("fcmp", Op::FCMP),
+        ("itof", Op::ITOF),
+        ("ftoi", Op::FTOI),
+    ];
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Op> {
+        Op::MNEMONICS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(mnemonic))
+            .map(|&(_, op)| op)
+    }
+
+    /// Opcode, and the group it is in.
+    /// `jmp` and `mov` are pseudo instructions, so they have none.
+    pub fn opcode(self) -> Option<(isa::Op, Group)> {
+        let opcode = match self {
+            Op::BPN => (isa::BPN_T, Group::Base),
+            Op::BPP => (isa::BPP_T, Group::Base),
+            Op::BPZ => (isa::BPZ_T, Group::Base),
+            Op::BGQ => (isa::BGQ_T, Group::Base),
+            Op::BLQ => (isa::BLQ_T, Group::Base),
+            Op::BLT => (isa::BLT_T, Group::Base),
+            Op::BGT => (isa::BGT_T, Group::Base),
+            Op::BNE => (isa::BNE_T, Group::Base),
+            Op::BEQ => (isa::BEQ_T, Group::Base),
+            Op::CMP => (isa::CMP_T, Group::Base),
+            Op::STRE => (isa::STRE_T, Group::Base),
+            Op::LOAD => (isa::LOAD_T, Group::Base),
+            Op::ADD => (isa::ADD_T, Group::Base),
+            Op::SUB => (isa::SUB_T, Group::Base),
+            Op::MUL => (isa::MUL_T, Group::Base),
+            Op::QOT => (isa::QOT_T, Group::Base),
+            Op::REM => (isa::REM_T, Group::Base),
+            Op::AND => (isa::AND_T, Group::Base),
+            Op::OR => (isa::OR_T, Group::Base),
+            Op::SFT => (isa::SFT_T, Group::Base),
+            Op::NOT => (isa::NOT_T, Group::Base),
+            Op::ROT => (isa::ROT_T, Group::Base),
+            Op::PUSH => (isa::PUSH_T, Group::Base),
+            Op::POP => (isa::POP_T, Group::Base),
+            Op::ADC => (isa::ADC_T, Group::Ext),
+            Op::SBB => (isa::SBB_T, Group::Ext),
+            Op::MULH => (isa::MULH_T, Group::Ext),
+            Op::FADD => (isa::FADD_T, Group::Fpu),
+            Op::FSUB => (isa::FSUB_T, Group::Fpu),
+            Op::FMUL => (isa::FMUL_T, Group::Fpu),
+            Op::FDIV => (isa::FDIV_T, Group::Fpu),
+            Op::FCMP => (isa::FCMP_T, Group::Fpu),
+            Op::ITOF => (isa::ITOF_T, Group::Fpu),
+            Op::FTOI => (isa::FTOI_T, Group::Fpu),
+            Op::JMP | Op::MOV => return None,
+        };
+        Some(opcode)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use JX_01::{
+        cpu::profile::instr_name,
+        isa::{self, decode, encode, registers::*, Instr},
+    };
+    use ternary::{trits::Trit, word::Word};
+
+    use crate::ast::{Group, Op};
+
+    #[test]
+    fn mnemonics() {
+        for (name, op) in Op::MNEMONICS {
+            assert_eq!(Op::from_mnemonic(name), Some(op), "{name}");
+            assert_eq!(Op::from_mnemonic(&name.to_uppercase()), Some(op), "{name}");
+            let Some((opcode, group)) = op.opcode() else {
+                assert!(matches!(op, Op::JMP | Op::MOV), "{name} has no opcode");
+                continue;
+            };
+
+            // The decoder picks the group by the last trit of the control
+            let (last, rr, ri) = match group {
+                Group::Base => (Trit::Zero, isa::ALU_CTRL_R_RR, isa::ALU_CTRL_R_RI),
+                Group::Ext => (Trit::NOne, isa::EXT_CTRL_R_RR, isa::EXT_CTRL_R_RI),
+                Group::Fpu => (Trit::POne, isa::FPU_CTRL_RR, isa::FPU_CTRL_RI),
+            };
+            for instr in [
+                Instr::OPRR(rr, opcode, N1, N2, Word::ZERO),
+                Instr::OPRI(ri, opcode, N1, 4.into()),
+            ] {
+                let decoded = decode(encode(instr));
+                assert_eq!(decoded, instr, "{name}");
+                let (Instr::OPRR(ctrl, ..) | Instr::OPRI(ctrl, ..)) = decoded else {
+                    unreachable!()
+                };
+                assert_eq!(ctrl.0[2], last, "{name}");
+                // And the disassembler has the same name for it
+                assert_eq!(instr_name(&decoded), name);
+            }
+        }
+        assert_eq!(Op::from_mnemonic("fsqrt"), None);
+    }
 }
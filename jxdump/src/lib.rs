@@ -0,0 +1,282 @@
+//! An `xxd` for word images.
+//!
+//! Each row is the address of its first word, the words, then their trytes
+//! as TERSCII with `.` for anything that isn't a printable character:
+//!
+//! ```text
+//! 000000000: VM0 000 000  A00 000 000  ZXX ZXX ZXY  |......ccb|
+//! ```
+//!
+//! Memory is addressed by tryte, so the addresses go up by three a word.
+//! Addresses and words are both written the way `spec.txt` writes
+//! instructions, lowest tribble first, so the address 9 is `I00000000`. The
+//! words have a space between trytes.
+//!
+//! An image on disk is each word's packed trits as a little endian `u64`,
+//! what [`Word::num`] gives.
+
+use std::{fmt, io::{self, Write}};
+
+use septivigntimal::{from_spec_letters, to_spec_letters};
+use ternary::{tryte::Tryte, word::Word};
+use terscii::TERSCII;
+
+/// Trytes in a word, each shown as a group of letters and a side column character
+const TRYTES: usize = 3;
+/// Letters in a group
+const GROUP: usize = 3;
+
+/// The most words [`undump`] makes an image of, 2187 pages of 729 words.
+/// Images are flat, so a row is written at its address with zeros before
+/// it, and one bad address could otherwise ask for terabytes.
+pub const MAX_WORDS: usize = 3usize.pow(13);
+
+/// How [`dump`] lays out its rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Words per row
+    pub cols: usize,
+    /// Address of the first word
+    pub start: isize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { cols: 3, start: 0 }
+    }
+}
+
+/// The side column character for a tryte
+fn glyph(tryte: Tryte) -> char {
+    TERSCII::try_from(tryte).ok().and_then(TERSCII::glyph).unwrap_or('.')
+}
+
+/// A word as space separated groups, `VM0 000 000`
+fn groups(word: Word) -> String {
+    let letters = to_spec_letters(&word);
+    let groups: Vec<&str> = (0..TRYTES).map(|i| &letters[GROUP * i..GROUP * (i + 1)]).collect();
+    groups.join(" ")
+}
+
+/// Writes `words` as rows, see the [crate docs](crate)
+pub fn dump(words: &[Word], options: &Options, out: &mut impl Write) -> io::Result<()> {
+    let cols = options.cols.max(1);
+    // Every word takes its groups and the two spaces after them
+    let width = cols * (TRYTES * (GROUP + 1) + 1);
+    for (row, chunk) in words.chunks(cols).enumerate() {
+        let address = Word::from(options.start + (row * cols * TRYTES) as isize);
+        let hex: String = chunk.iter().map(|&word| groups(word) + "  ").collect();
+        let side: String = chunk
+            .iter()
+            .flat_map(|&word| <[Tryte; TRYTES]>::from(word))
+            .map(glyph)
+            .collect();
+        writeln!(out, "{}: {hex:width$}|{side}|", to_spec_letters(&address))?;
+    }
+    Ok(())
+}
+
+/// Why [`undump`] couldn't read a line, counting from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDumpError {
+    /// No `address:` to start the line, or one that isn't a word's
+    Address { line: usize },
+    /// Something other than three septivigntimal letters among the words
+    Group { line: usize },
+    /// Groups left over that don't make a whole word
+    PartialWord { line: usize },
+    /// A row that would end past [`MAX_WORDS`]
+    TooFar { line: usize },
+}
+
+impl fmt::Display for ParseDumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseDumpError::Address { line } => write!(f, "line {line}: bad address"),
+            ParseDumpError::Group { line } => {
+                write!(f, "line {line}: expected groups of three septivigntimal letters")
+            }
+            ParseDumpError::PartialWord { line } => {
+                write!(f, "line {line}: the groups don't make whole words")
+            }
+            ParseDumpError::TooFar { line } => {
+                write!(f, "line {line}: ends past the largest image, {MAX_WORDS} words")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseDumpError {}
+
+/// Reads rows that [`dump`] wrote back into words. Each row goes to its
+/// address, like `xxd -r`, so the image starts at address 0 and anything
+/// the rows skip over is zero. Blank lines are ignored, and no image is
+/// longer than [`MAX_WORDS`].
+pub fn undump(text: &str) -> Result<Vec<Word>, ParseDumpError> {
+    let mut words = Vec::new();
+    for (line, row) in text.lines().enumerate().map(|(i, row)| (i + 1, row)) {
+        if row.trim().is_empty() {
+            continue;
+        }
+        let (address, rest) = row.split_once(':').ok_or(ParseDumpError::Address { line })?;
+        let address = from_spec_letters::<Word>(address.trim())
+            .map(isize::from)
+            .map_err(|_| ParseDumpError::Address { line })?;
+        if address < 0 || address % TRYTES as isize != 0 {
+            return Err(ParseDumpError::Address { line });
+        }
+
+        // The side column can hold anything, but the words can't hold a `|`
+        let hex = rest.split_once('|').map_or(rest, |(hex, _)| hex);
+        let groups: Vec<&str> = hex.split_whitespace().collect();
+        if groups.iter().any(|group| group.chars().count() != GROUP) {
+            return Err(ParseDumpError::Group { line });
+        }
+        if !groups.len().is_multiple_of(TRYTES) {
+            return Err(ParseDumpError::PartialWord { line });
+        }
+
+        let index = address as usize / TRYTES;
+        if index + groups.len() / TRYTES > MAX_WORDS {
+            return Err(ParseDumpError::TooFar { line });
+        }
+        for (i, word) in groups.chunks(TRYTES).enumerate() {
+            let word = from_spec_letters::<Word>(&word.concat())
+                .map_err(|_| ParseDumpError::Group { line })?;
+            if words.len() <= index + i {
+                words.resize(index + i + 1, Word::ZERO);
+            }
+            words[index + i] = word;
+        }
+    }
+    Ok(words)
+}
+
+/// Why bytes aren't a word image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageError {
+    /// Not a whole number of words, with the byte count
+    Length(usize),
+    /// Bits that aren't packed trits, and which word they're in
+    InvalidWord(usize),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Length(len) => write!(f, "{len} bytes isn't a whole number of words"),
+            ImageError::InvalidWord(index) => write!(f, "word {index} isn't packed trits"),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+/// Reads an image, see the [crate docs](crate)
+pub fn read_image(bytes: &[u8]) -> Result<Vec<Word>, ImageError> {
+    if !bytes.len().is_multiple_of(8) {
+        return Err(ImageError::Length(bytes.len()));
+    }
+    bytes
+        .chunks(8)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let num = u64::from_le_bytes(chunk.try_into().unwrap());
+            Word::from_packed(num.into()).ok_or(ImageError::InvalidWord(index))
+        })
+        .collect()
+}
+
+/// Writes an image, see the [crate docs](crate)
+pub fn write_image(words: &[Word]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.num().to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use septivigntimal::*;
+    use ternary::{tryte::Tryte, word::Word};
+    use terscii::TERSCII;
+
+    use crate::{
+        dump, read_image, undump, write_image, ImageError, Options, ParseDumpError, MAX_WORDS,
+    };
+
+    fn text(words: &[Word], options: &Options) -> String {
+        let mut out = Vec::new();
+        dump(words, options, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn terscii(chars: [TERSCII; 3]) -> Word {
+        chars.map(Tryte::from).into()
+    }
+
+    #[test]
+    fn rows() {
+        let words = [
+            [V, M, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into(),
+            Word::PONE,
+            terscii([TERSCII::c, TERSCII::c, TERSCII::b]),
+            terscii([TERSCII::H, TERSCII::SP, TERSCII::LE]),
+        ];
+        assert_eq!(
+            text(&words, &Options::default()),
+            "000000000: VM0 000 000  A00 000 000  ZXX ZXX ZXY  |......ccb|\n\
+             I00000000: ZYS ZZS ZZY                            |H .|\n"
+        );
+        assert_eq!(
+            text(&words[..2], &Options { cols: 1, start: 30 }),
+            "CA0000000: VM0 000 000  |...|\nFA0000000: A00 000 000  |...|\n"
+        );
+        assert_eq!(text(&[], &Options::default()), "");
+    }
+
+    #[test]
+    fn round_trip() {
+        let words: Vec<Word> = (-40isize..40).map(|i| Word::from(i * 97_654_321)).collect();
+        for cols in [1, 3, 4, 7] {
+            let dumped = text(&words, &Options { cols, start: 0 });
+            assert_eq!(undump(&dumped), Ok(words.clone()), "{cols} columns");
+        }
+        // Rows go back to their addresses
+        let dumped = text(&words[..2], &Options { cols: 3, start: 9 });
+        assert_eq!(undump(&dumped), Ok([Word::ZERO, Word::ZERO, Word::ZERO, words[0], words[1]].to_vec()));
+        // The side column doesn't get in the way, even when it looks like letters
+        let letters = [terscii([TERSCII::VERT, TERSCII::A, TERSCII::Zero])];
+        assert_eq!(undump(&text(&letters, &Options::default())), Ok(letters.to_vec()));
+    }
+
+    #[test]
+    fn undump_errors() {
+        assert_eq!(undump("VM0 000 000"), Err(ParseDumpError::Address { line: 1 }));
+        assert_eq!(undump("\nA00000000: VM0 000 000"), Err(ParseDumpError::Address { line: 2 }));
+        assert_eq!(undump("N00000000: VM0 000 000"), Err(ParseDumpError::Address { line: 1 }));
+        assert_eq!(undump("000000000: VM0 00 000"), Err(ParseDumpError::Group { line: 1 }));
+        assert_eq!(undump("000000000: VM0 001 000"), Err(ParseDumpError::Group { line: 1 }));
+        assert_eq!(undump("000000000: VM0 000"), Err(ParseDumpError::PartialWord { line: 1 }));
+        assert_eq!(undump(""), Ok(vec![]));
+
+        // Near the highest address a word holds, then the last row that fits
+        assert_eq!(undump("0MMMMMMMM: VM0 000 000"), Err(ParseDumpError::TooFar { line: 1 }));
+        let last = to_spec_letters(&Word::from(3 * (MAX_WORDS - 1) as isize));
+        let words = undump(&format!("{last}: VM0 000 000")).unwrap();
+        assert_eq!(words.len(), MAX_WORDS);
+        assert_eq!(
+            undump(&format!("{last}: VM0 000 000  VM0 000 000")),
+            Err(ParseDumpError::TooFar { line: 1 })
+        );
+    }
+
+    #[test]
+    fn image() {
+        let words = [Word::ZERO, Word::MAX, Word::MIN, Word::from(12345isize)];
+        let bytes = write_image(&words);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(read_image(&bytes), Ok(words.to_vec()));
+        assert_eq!(read_image(&bytes[..31]), Err(ImageError::Length(31)));
+        let mut bad = bytes.clone();
+        bad[8..16].fill(0);
+        assert_eq!(read_image(&bad), Err(ImageError::InvalidWord(1)));
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::Parser;
+use jxdump::{dump, read_image, undump, write_image, Options};
+use tobj::{Section, TObj};
+
+/// A septivigntimal hexdump for JX_01 word images
+#[derive(Parser, Debug)]
+#[command(version, about, long_about)]
+struct Config {
+    /// Image to read, stdin if missing
+    input: Option<PathBuf>,
+    /// Read a dump and write the image back
+    #[arg(short, long)]
+    reverse: bool,
+    /// Words per row
+    #[arg(short, long, default_value_t = 3)]
+    cols: usize,
+    /// Words to skip from the start
+    #[arg(short, long, default_value_t = 0)]
+    seek: usize,
+    /// Words to dump, all of them if missing
+    #[arg(short, long)]
+    len: Option<usize>,
+    /// Dump one section of a tobj: text, data, textr, datar or sym
+    #[arg(long, value_parser = parse_section)]
+    section: Option<Section>,
+    /// Where to write, stdout if missing
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+fn parse_section(name: &str) -> Result<Section, String> {
+    Section::from_name(name).ok_or_else(|| format!("no section called {name:?}"))
+}
+
+fn run(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let mut input = Vec::new();
+    match &config.input {
+        Some(path) => input = fs::read(path)?,
+        None => {
+            io::stdin().read_to_end(&mut input)?;
+        }
+    }
+
+    let output = if config.reverse {
+        write_image(&undump(std::str::from_utf8(&input)?)?)
+    } else {
+        let image = read_image(&input)?;
+        let words = match config.section {
+            Some(section) => TObj::parse(&image)?.section(section),
+            None => &image[..],
+        };
+        let words = &words[config.seek.min(words.len())..];
+        let words = &words[..config.len.unwrap_or(words.len()).min(words.len())];
+
+        let options = Options { cols: config.cols, start: config.seek as isize * 3 };
+        let mut text = Vec::new();
+        dump(words, &options, &mut text)?;
+        text
+    };
+
+    match &config.output {
+        Some(path) => fs::write(path, output)?,
+        None => io::stdout().write_all(&output)?,
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let config = Config::parse();
+    match run(&config) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("jxdump: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Writing and reading tribbles, trytes and words as letters, the way
+//! `spec.txt` writes opcodes: `hlt` is `VM0`.
+//!
+//! Each letter is a tribble's [`Tribble::to_digit`], `0` for zero, `A` to
+//! `M` for 1 to 13 and `N` to `Z` for -1 to -13. The lowest tribble comes
+//! first, as in the instruction tables, which is the reverse of `ternary`'s
+//! `{:X}` and `0s` numbers. Parsing is strict: exactly one letter per
+//! tribble, upper case.
+//!
+//! ```
+//! use septivigntimal::*;
+//! use ternary::tryte::Tryte;
+//!
+//! let hlt: Tryte = [V, M, ZERO].into();
+//! assert_eq!(to_spec_letters(&hlt), "VM0");
+//! assert_eq!(from_spec_letters::<Tryte>("VM0"), Ok(hlt));
+//! assert_eq!(format!("{}", SpecLetters(hlt)), "VM0");
+//! // The same digits as a number, most significant first
+//! assert_eq!(format!("{hlt:X}"), "0MV");
+//! ```
+
+use std::fmt;
+
+use ternary::{tryte::Tryte, word::Word};
+
+use crate::*;
+
+/// Why a string isn't the letters of a [`Letters`] type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseSeptivigntimalError {
+    /// Not one letter per tribble
+    Length { expected: usize, found: usize },
+    /// A character that isn't `0` or an upper case letter, and where it was
+    InvalidLetter { letter: char, index: usize },
+}
+
+impl fmt::Display for ParseSeptivigntimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseSeptivigntimalError::Length { expected, found } => {
+                write!(f, "expected {expected} septivigntimal letters, found {found}")
+            }
+            ParseSeptivigntimalError::InvalidLetter { letter, index } => {
+                write!(f, "invalid septivigntimal letter {letter:?} at {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseSeptivigntimalError {}
+
+/// The letter for a tribble
+pub const fn to_letter(tribble: Tribble) -> char {
+    tribble.to_digit()
+}
+
+/// The tribble for a letter, `None` for anything but `0` and upper case letters
+pub fn from_letter(letter: char) -> Option<Tribble> {
+    Tribble::from_digit(letter)
+}
+
+/// Something written as a fixed number of letters, lowest tribble first
+pub trait Letters: Sized {
+    /// How many letters it takes
+    const LEN: usize;
+
+    fn to_tribbles(&self) -> Vec<Tribble>;
+
+    /// Takes exactly [`Letters::LEN`] tribbles
+    fn from_tribbles(tribbles: &[Tribble]) -> Self;
+}
+
+impl Letters for Tribble {
+    const LEN: usize = 1;
+
+    fn to_tribbles(&self) -> Vec<Tribble> {
+        vec![*self]
+    }
+
+    fn from_tribbles(tribbles: &[Tribble]) -> Self {
+        tribbles[0]
+    }
+}
+
+impl Letters for Tryte {
+    const LEN: usize = 3;
+
+    fn to_tribbles(&self) -> Vec<Tribble> {
+        <[Tribble; 3]>::from(*self).to_vec()
+    }
+
+    fn from_tribbles(tribbles: &[Tribble]) -> Self {
+        <[Tribble; 3]>::try_from(tribbles).unwrap().into()
+    }
+}
+
+impl Letters for Word {
+    const LEN: usize = 9;
+
+    fn to_tribbles(&self) -> Vec<Tribble> {
+        <[Tribble; 9]>::from(*self).to_vec()
+    }
+
+    fn from_tribbles(tribbles: &[Tribble]) -> Self {
+        <[Tribble; 9]>::try_from(tribbles).unwrap().into()
+    }
+}
+
+/// The letters of `value`, lowest tribble first, see the [module docs](self)
+pub fn to_spec_letters<T: Letters>(value: &T) -> String {
+    value.to_tribbles().into_iter().map(to_letter).collect()
+}
+
+/// Reads exactly [`Letters::LEN`] letters, lowest tribble first, see the
+/// [module docs](self)
+pub fn from_spec_letters<T: Letters>(s: &str) -> Result<T, ParseSeptivigntimalError> {
+    let found = s.chars().count();
+    if found != T::LEN {
+        return Err(ParseSeptivigntimalError::Length { expected: T::LEN, found });
+    }
+    let tribbles = s
+        .chars()
+        .enumerate()
+        .map(|(index, letter)| {
+            from_letter(letter).ok_or(ParseSeptivigntimalError::InvalidLetter { letter, index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(T::from_tribbles(&tribbles))
+}
+
+/// Displays its value as letters, lowest tribble first. Width and alignment
+/// pad like they do for strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecLetters<T>(pub T);
+
+impl<T: Letters> fmt::Display for SpecLetters<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&to_spec_letters(&self.0))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ternary::{tryte::Tryte, word::Word};
+
+    use crate::{
+        codec::{
+            from_letter, from_spec_letters, to_letter, to_spec_letters,
+            ParseSeptivigntimalError, SpecLetters,
+        },
+        *,
+    };
+
+    #[test]
+    fn letters() {
+        for tribble in Tribble::iter() {
+            assert_eq!(from_letter(to_letter(tribble)), Some(tribble));
+        }
+        assert_eq!(to_letter(ZERO), '0');
+        assert_eq!(to_letter(A), 'A');
+        assert_eq!(to_letter(N), 'N');
+        assert_eq!(from_letter('a'), None);
+        assert_eq!(from_letter('1'), None);
+    }
+
+    #[test]
+    fn spec_opcodes() {
+        // hlt, pptr and a word of lvb, as the spec writes them
+        let hlt: Tryte = [V, M, ZERO].into();
+        assert_eq!(to_spec_letters(&hlt), "VM0");
+        assert_eq!(to_spec_letters(&Tryte::from([V, E, ZERO])), "VE0");
+        let lvb: Word = [V, L, A, F, E, D, C, B, A].into();
+        assert_eq!(to_spec_letters(&lvb), "VLAFEDCBA");
+        assert_eq!(from_spec_letters("VLAFEDCBA"), Ok(lvb));
+        // The lowest tribble comes first
+        assert_eq!(to_spec_letters(&Tryte::from(1isize)), "A00");
+        assert_eq!(to_spec_letters(&Tryte::from(27isize)), "0A0");
+        assert_eq!(to_spec_letters(&Word::MIN), "ZZZZZZZZZ");
+        // and `{:X}` writes the same letters the other way round
+        assert_eq!(to_spec_letters(&lvb).chars().rev().collect::<String>(), format!("{lvb:X}"));
+        assert_eq!(Word::from_septivigntimal("ABCDEFLV").ok(), from_spec_letters("VLFEDCBA0").ok());
+    }
+
+    #[test]
+    fn round_trip() {
+        for tribble in Tribble::iter() {
+            assert_eq!(from_spec_letters::<Tribble>(&to_spec_letters(&tribble)), Ok(tribble));
+        }
+        for value in (-9841isize..=9841).step_by(13) {
+            let tryte = Tryte::from(value);
+            assert_eq!(from_spec_letters(&to_spec_letters(&tryte)), Ok(tryte));
+            let word = Word::from(value * 387_420_489 + value);
+            assert_eq!(from_spec_letters(&to_spec_letters(&word)), Ok(word));
+        }
+    }
+
+    #[test]
+    fn strict() {
+        use ParseSeptivigntimalError::*;
+
+        assert_eq!(from_spec_letters::<Tryte>("VM"), Err(Length { expected: 3, found: 2 }));
+        assert_eq!(from_spec_letters::<Tryte>("VM00"), Err(Length { expected: 3, found: 4 }));
+        assert_eq!(from_spec_letters::<Word>(""), Err(Length { expected: 9, found: 0 }));
+        assert_eq!(from_spec_letters::<Tribble>("AB"), Err(Length { expected: 1, found: 2 }));
+        assert_eq!(from_spec_letters::<Tryte>("Vm0"), Err(InvalidLetter { letter: 'm', index: 1 }));
+        assert_eq!(from_spec_letters::<Tryte>("VM1"), Err(InvalidLetter { letter: '1', index: 2 }));
+        assert_eq!(from_spec_letters::<Tryte>("é00"), Err(InvalidLetter { letter: 'é', index: 0 }));
+        assert_eq!(
+            Length { expected: 3, found: 2 }.to_string(),
+            "expected 3 septivigntimal letters, found 2"
+        );
+    }
+
+    #[test]
+    fn display() {
+        let hlt: Tryte = [V, M, ZERO].into();
+        assert_eq!(SpecLetters(hlt).to_string(), "VM0");
+        assert_eq!(SpecLetters(M).to_string(), "M");
+        assert_eq!(format!("{:>5}|", SpecLetters(hlt)), "  VM0|");
+        assert_eq!(format!("{:<12}|", SpecLetters(Word::ZERO)), "000000000   |");
+    }
+}
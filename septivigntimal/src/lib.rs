@@ -1,50 +1,40 @@
-use ternary::trits::Trit::{self, *};
+pub use ternary::tribble::Tribble;
 
-pub type Tribble = [Trit; 3];
+pub mod codec;
 
-pub const Z:    Tribble = [NOne, NOne, NOne];
-pub const Y:    Tribble = [Zero, NOne, NOne];
-pub const X:    Tribble = [POne, NOne, NOne];
-pub const W:    Tribble = [NOne, Zero, NOne];
-pub const V:    Tribble = [Zero, Zero, NOne];
-pub const U:    Tribble = [POne, Zero, NOne];
-pub const T:    Tribble = [NOne, POne, NOne];
-pub const S:    Tribble = [Zero, POne, NOne];
-pub const R:    Tribble = [POne, POne, NOne];
-pub const Q:    Tribble = [NOne, NOne, Zero];
-pub const P:    Tribble = [Zero, NOne, Zero];
-pub const O:    Tribble = [POne, NOne, Zero];
-pub const N:    Tribble = [NOne, Zero, Zero];
-pub const ZERO: Tribble = [Zero, Zero, Zero];
-pub const A:    Tribble = [POne, Zero, Zero];
-pub const B:    Tribble = [NOne, POne, Zero];
-pub const C:    Tribble = [Zero, POne, Zero];
-pub const D:    Tribble = [POne, POne, Zero];
-pub const E:    Tribble = [NOne, NOne, POne];
-pub const F:    Tribble = [Zero, NOne, POne];
-pub const G:    Tribble = [POne, NOne, POne];
-pub const H:    Tribble = [NOne, Zero, POne];
-pub const I:    Tribble = [Zero, Zero, POne];
-pub const J:    Tribble = [POne, Zero, POne];
-pub const K:    Tribble = [NOne, POne, POne];
-pub const L:    Tribble = [Zero, POne, POne];
-pub const M:    Tribble = [POne, POne, POne];
+pub use codec::{from_spec_letters, to_spec_letters, SpecLetters};
 
-pub const fn to_num(t: Tribble) -> isize {
-    const fn frm(t: Tribble, i: usize) -> isize {
-        match t[i] {
-            NOne => -1,
-            Zero => 0,
-            POne => 1,
-        }
-    }
-    let t: [isize; 3] = [frm(t, 0), frm(t, 1), frm(t, 2)];
-    t[0] + 3 * t[1] + 9 * t[2]
-}
+pub const Z:    Tribble = Tribble::new(-13);
+pub const Y:    Tribble = Tribble::new(-12);
+pub const X:    Tribble = Tribble::new(-11);
+pub const W:    Tribble = Tribble::new(-10);
+pub const V:    Tribble = Tribble::new(-9);
+pub const U:    Tribble = Tribble::new(-8);
+pub const T:    Tribble = Tribble::new(-7);
+pub const S:    Tribble = Tribble::new(-6);
+pub const R:    Tribble = Tribble::new(-5);
+pub const Q:    Tribble = Tribble::new(-4);
+pub const P:    Tribble = Tribble::new(-3);
+pub const O:    Tribble = Tribble::new(-2);
+pub const N:    Tribble = Tribble::new(-1);
+pub const ZERO: Tribble = Tribble::ZERO;
+pub const A:    Tribble = Tribble::new(1);
+pub const B:    Tribble = Tribble::new(2);
+pub const C:    Tribble = Tribble::new(3);
+pub const D:    Tribble = Tribble::new(4);
+pub const E:    Tribble = Tribble::new(5);
+pub const F:    Tribble = Tribble::new(6);
+pub const G:    Tribble = Tribble::new(7);
+pub const H:    Tribble = Tribble::new(8);
+pub const I:    Tribble = Tribble::new(9);
+pub const J:    Tribble = Tribble::new(10);
+pub const K:    Tribble = Tribble::new(11);
+pub const L:    Tribble = Tribble::new(12);
+pub const M:    Tribble = Tribble::new(13);
 
 #[cfg(test)]
 pub mod tests {
-    use ternary::tryte::Tryte;
+    use ternary::{trits::Trit::*, tryte::Tryte};
 
     use crate::*;
 
@@ -56,5 +46,7 @@ pub mod tests {
         assert_eq!(arr, Tryte::PONE);
         let arr: Tryte = [N, ZERO, ZERO].into();
         assert_eq!(arr, Tryte::NONE);
+        assert_eq!(V.trits(), [Zero, Zero, NOne]);
+        assert_eq!(M, Tribble::MAX);
     }
 }
@@ -0,0 +1,129 @@
+//! Packed `Word` arithmetic against walking the trits one at a time,
+//! which is how `Word` used to do everything, or against going through `isize`.
+//!
+//! `cargo bench -p ternary`
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use ternary::{
+    trits::{Trit, TritAddResult},
+    word::Word,
+};
+
+/// The trit at a time implementations, kept as a baseline
+mod naive {
+    use super::*;
+
+    pub fn add(lhs: Word, rhs: Word) -> (Word, Trit) {
+        let mut val = [Trit::Zero; 27];
+        let mut carry = Trit::Zero;
+
+        for (i, (l, r)) in lhs.into_iter().zip(rhs).enumerate() {
+            let TritAddResult { carry: c, result } = (l + r) + carry;
+            val[i] = result;
+            carry = c;
+        }
+        (val.into(), carry)
+    }
+
+    pub fn neg(val: Word) -> Word {
+        let arr: [Trit; 27] = val.into();
+        arr.map(std::ops::Neg::neg).into()
+    }
+
+    pub fn mul_trit(val: Word, trit: Trit) -> Word {
+        let arr: [Trit; 27] = val.into();
+        arr.map(|t| t * trit).into()
+    }
+
+    pub fn shl(val: Word, by: usize) -> Word {
+        let arr: [Trit; 27] = val.into();
+        let mut ret = [Trit::Zero; 27];
+        ret[by..].copy_from_slice(&arr[..27 - by]);
+        ret.into()
+    }
+
+    pub fn mul(lhs: Word, rhs: Word) -> Word {
+        rhs.into_iter()
+            .enumerate()
+            .map(|(i, trit)| shl(mul_trit(lhs, trit), i))
+            .fold(Word::ZERO, |acc, right| add(acc, right).0)
+    }
+
+    pub fn and(lhs: Word, rhs: Word) -> Word {
+        let l: [Trit; 27] = lhs.into();
+        let r: [Trit; 27] = rhs.into();
+        std::array::from_fn::<_, 27, _>(|i| l[i] & r[i]).into()
+    }
+
+    pub fn or(lhs: Word, rhs: Word) -> Word {
+        let l: [Trit; 27] = lhs.into();
+        let r: [Trit; 27] = rhs.into();
+        std::array::from_fn::<_, 27, _>(|i| l[i] | r[i]).into()
+    }
+
+    pub fn to_isize(val: Word) -> isize {
+        let arr: [Trit; 27] = val.into();
+        arr.map(<Trit as Into<isize>>::into)
+            .into_iter()
+            .enumerate()
+            .fold(0, |acc, (i, trit)| acc + (3isize.pow(i as u32) * trit))
+    }
+
+    pub fn div(lhs: Word, rhs: Word) -> Word {
+        to_isize(lhs).div_euclid(to_isize(rhs)).into()
+    }
+
+    pub fn cmp(lhs: Word, rhs: Word) -> std::cmp::Ordering {
+        to_isize(lhs).cmp(&to_isize(rhs))
+    }
+}
+
+fn operands() -> (Word, Word) {
+    (
+        Word::from(1_234_567_890_123isize),
+        Word::from(-987_654_321_987isize),
+    )
+}
+
+fn binary(c: &mut Criterion, name: &str, swar: fn(Word, Word) -> Word, naive: fn(Word, Word) -> Word) {
+    let (lhs, rhs) = operands();
+    assert_eq!(swar(lhs, rhs), naive(lhs, rhs), "{name}");
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("swar", |b| b.iter(|| swar(black_box(lhs), black_box(rhs))));
+    group.bench_function("naive", |b| b.iter(|| naive(black_box(lhs), black_box(rhs))));
+    group.finish();
+}
+
+fn arithmetic(c: &mut Criterion) {
+    binary(c, "add", |l, r| l + r, |l, r| naive::add(l, r).0);
+    binary(c, "sub", |l, r| l - r, |l, r| naive::add(l, naive::neg(r)).0);
+    binary(c, "mul", |l, r| l * r, naive::mul);
+    binary(c, "div", |l, r| (l / r).unwrap(), naive::div);
+    binary(c, "neg", |l, _| -l, |l, _| naive::neg(l));
+    binary(c, "shl", |l, _| l << 5, |l, _| naive::shl(l, 5));
+}
+
+fn logic(c: &mut Criterion) {
+    binary(c, "and", |l, r| l & r, naive::and);
+    binary(c, "or", |l, r| l | r, naive::or);
+}
+
+fn comparison(c: &mut Criterion) {
+    let (lhs, rhs) = operands();
+
+    let mut group = c.benchmark_group("cmp");
+    group.bench_function("swar", |b| b.iter(|| black_box(lhs).cmp(&black_box(rhs))));
+    group.bench_function("naive", |b| b.iter(|| naive::cmp(black_box(lhs), black_box(rhs))));
+    group.finish();
+
+    let mut group = c.benchmark_group("to_isize");
+    group.bench_function("swar", |b| b.iter(|| isize::from(black_box(lhs))));
+    group.bench_function("naive", |b| b.iter(|| naive::to_isize(black_box(lhs))));
+    group.finish();
+}
+
+criterion_group!(benches, arithmetic, logic, comparison);
+criterion_main!(benches);
@@ -0,0 +1,190 @@
+//! Named trit fields inside a [`Ternary`], for register and table entry
+//! layouts. See [`bitfield!`](crate::bitfield!).
+
+use std::ops::Range;
+
+use crate::{
+    ternary::{Storage, Ternary, Width},
+    trits::Trit,
+};
+
+/// Where a field sits: one trit index, or a range of them
+pub trait TritRange {
+    fn trits(self) -> Range<usize>;
+}
+
+impl TritRange for usize {
+    fn trits(self) -> Range<usize> {
+        self..self + 1
+    }
+}
+
+impl TritRange for Range<usize> {
+    fn trits(self) -> Range<usize> {
+        self
+    }
+}
+
+/// A type a field of a `Ternary<N>` can be read as
+pub trait Field<const N: usize>: Sized
+where
+    Width<N>: Storage,
+{
+    fn read(from: Ternary<N>, range: Range<usize>) -> Self;
+    fn write(self, to: &mut Ternary<N>, range: Range<usize>);
+}
+
+/// Single trit fields
+impl<const N: usize> Field<N> for Trit
+where
+    Width<N>: Storage,
+{
+    fn read(from: Ternary<N>, range: Range<usize>) -> Self {
+        debug_assert_eq!(range.len(), 1);
+        from.get_trit(range.start)
+    }
+
+    fn write(self, to: &mut Ternary<N>, range: Range<usize>) {
+        debug_assert_eq!(range.len(), 1);
+        to.set_trit(range.start, self);
+    }
+}
+
+/// Fields read as numbers, their lowest trit the least significant
+impl<const N: usize, const M: usize> Field<N> for Ternary<M>
+where
+    Width<N>: Storage,
+    Width<M>: Storage,
+{
+    fn read(from: Ternary<N>, range: Range<usize>) -> Self {
+        from.get_field(range).resize()
+    }
+
+    fn write(self, to: &mut Ternary<N>, range: Range<usize>) {
+        to.set_field(range, self.resize());
+    }
+}
+
+/// Defines a newtype over a [`Ternary`] with a getter and a setter for
+/// each named field. A field is a [`Trit`] at one index or a [`Ternary`] of
+/// at least as many trits as its range, see [`Field`].
+///
+/// ```
+/// use ternary::{bitfield, trits::Trit, tryte::Tryte, word::Word};
+///
+/// bitfield! {
+///     #[derive(Clone, Copy, Default)]
+///     pub struct Status(Word) {
+///         /// Highest trit
+///         pub get_flag, set_flag: Trit [26];
+///         pub get_low, set_low: Tryte [0..9];
+///     }
+/// }
+///
+/// let mut status = Status::default();
+/// status.set_flag(Trit::POne);
+/// status.set_low(Tryte::from(-5isize));
+/// assert_eq!(status.get_flag(), Trit::POne);
+/// assert_eq!(Word::from(status), Word::from(3isize.pow(26) - 5));
+/// ```
+#[macro_export]
+macro_rules! bitfield {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($inner_vis:vis $repr:ty) {
+            $(
+                $(#[$field_meta:meta])*
+                $field_vis:vis $get:ident, $set:ident: $ty:ty [$at:expr];
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name($inner_vis $repr);
+
+        impl $name {
+            $(
+                $(#[$field_meta])*
+                $field_vis fn $get(&self) -> $ty {
+                    $crate::bitfield::Field::read(self.0, $crate::bitfield::TritRange::trits($at))
+                }
+
+                $(#[$field_meta])*
+                $field_vis fn $set(&mut self, value: $ty) {
+                    $crate::bitfield::Field::write(value, &mut self.0, $crate::bitfield::TritRange::trits($at))
+                }
+            )*
+        }
+
+        impl From<$repr> for $name {
+            fn from(value: $repr) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $repr {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{ternary::Ternary, trits::Trit, tryte::Tryte, word::Word};
+
+    bitfield! {
+        #[derive(Clone, Copy, Default, Debug, PartialEq)]
+        struct Entry(Word) {
+            get_valid, set_valid: Trit [26];
+            get_kind, set_kind: Ternary<2> [24..26];
+            get_frame, set_frame: Word [3..24];
+            get_low, set_low: Tryte [0..3];
+        }
+    }
+
+    #[test]
+    fn fields() {
+        let mut entry = Entry::default();
+        entry.set_valid(Trit::NOne);
+        entry.set_kind(Ternary::from(4isize));
+        entry.set_frame(Word::from(-1_000_000isize));
+        entry.set_low(Tryte::from(13isize));
+
+        assert_eq!(entry.get_valid(), Trit::NOne);
+        assert_eq!(entry.get_kind(), Ternary::from(4isize));
+        assert_eq!(entry.get_frame(), Word::from(-1_000_000isize));
+        assert_eq!(entry.get_low(), Tryte::from(13isize));
+
+        // The setter only touches its own trits
+        entry.set_frame(Word::ZERO);
+        assert_eq!(entry.get_valid(), Trit::NOne);
+        assert_eq!(entry.get_kind(), Ternary::from(4isize));
+        assert_eq!(entry.get_low(), Tryte::from(13isize));
+        let expected = -3isize.pow(26) + 4 * 3isize.pow(24) + 13;
+        assert_eq!(Word::from(entry), Word::from(expected));
+    }
+
+    #[test]
+    fn word_fields() {
+        let mut word = Word::from(123_456isize);
+        assert_eq!(word.get_field(0..27), word);
+        let (field, trits): ([Trit; 27], [Trit; 27]) = (word.get_field(3..27).into(), word.into());
+        assert_eq!(field[..24], trits[3..]);
+        assert_eq!(field[24..], [Trit::Zero; 3]);
+        assert_eq!(word.get_field(5..5), Word::ZERO);
+
+        let low = word.get_field(0..4);
+        word.set_field(10..14, low);
+        assert_eq!(word.get_field(10..14), low);
+        word.set_trit(26, Trit::POne);
+        assert_eq!(word.get_trit(26), Trit::POne);
+        assert_eq!(word.get(26), Some(Trit::POne));
+    }
+
+    #[test]
+    #[should_panic]
+    fn field_out_of_range() {
+        Word::ZERO.get_field(20..28);
+    }
+}
@@ -1,2 +1,47 @@
+use std::fmt;
+
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct DivByZeroError;
+
+impl fmt::Display for DivByZeroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("attempted to divide by zero")
+    }
+}
+
+impl std::error::Error for DivByZeroError {}
+
+/// A number that doesn't fit in the ternary type it was converted to
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct OutOfRangeError;
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("out of range for the ternary type")
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+/// Why a string isn't a ternary number, see the `FromStr` impl on [`crate::ternary::Ternary`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseTernaryError {
+    /// Nothing after the sign and prefix
+    Empty,
+    /// A character that isn't a digit of the string's radix
+    InvalidDigit(char),
+    /// A valid number that doesn't fit
+    OutOfRange,
+}
+
+impl fmt::Display for ParseTernaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseTernaryError::Empty => f.write_str("cannot parse a ternary number from an empty string"),
+            ParseTernaryError::InvalidDigit(c) => write!(f, "invalid digit {c:?} in a ternary number"),
+            ParseTernaryError::OutOfRange => f.write_str("number too large to fit in the ternary type"),
+        }
+    }
+}
+
+impl std::error::Error for ParseTernaryError {}
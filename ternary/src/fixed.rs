@@ -0,0 +1,312 @@
+//! Fixed point numbers, for when a float is more than the job needs.
+//!
+//! A [`Fixed<FRAC>`] is a [`Word`] whose low `FRAC` trits sit after the
+//! ternary point, so it holds `word / 3^FRAC`. Products and quotients are
+//! rescaled by dropping trits, which in balanced ternary rounds to the
+//! nearest. Like the integers, everything wraps on overflow.
+//!
+//! ```
+//! use ternary::fixed::Fixed;
+//!
+//! let x: Fixed<3> = "1T0.01T".parse().unwrap();
+//! assert_eq!(f64::from(x), 6.0 + 2.0 / 27.0);
+//! assert_eq!(format!("{}", x * "10.0".parse().unwrap()), "1T00.1T0");
+//! ```
+
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use crate::{
+    errors::{DivByZeroError, OutOfRangeError, ParseTernaryError},
+    floating::Floating,
+    rounding::Rounding,
+    trits::Trit,
+    word::{DoubleWord, Word},
+    WORD_LEN,
+};
+
+/// A word with `FRAC` fractional trits, see the [module docs](self)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Fixed<const FRAC: usize>(Word);
+
+impl<const FRAC: usize> Fixed<FRAC> {
+    /// Keeps a trit for the integer part, so `1` is representable
+    const FITS: () = assert!(FRAC < WORD_LEN, "a Fixed needs fewer fractional trits than a word");
+
+    pub const ZERO: Self = Fixed(Word::ZERO);
+    pub const MAX: Self = Fixed(Word::MAX);
+    pub const MIN: Self = Fixed(Word::MIN);
+    /// The smallest step, `3^-FRAC`
+    pub const EPSILON: Self = Fixed(Word::PONE);
+
+    /// Reinterprets a word as already scaled by `3^FRAC`
+    pub const fn from_word(word: Word) -> Self {
+        let () = Self::FITS;
+        Fixed(word)
+    }
+
+    /// The scaled word underneath
+    pub const fn to_word(self) -> Word {
+        self.0
+    }
+
+    /// `value` with a zero fraction, wrapping if it needs more than `27 - FRAC` trits
+    pub fn from_integer(value: Word) -> Self {
+        Self::from_word(value << FRAC)
+    }
+
+    /// The nearest integer. There are no ties, a half having no end of trits.
+    pub fn to_integer(self) -> Word {
+        self.0 >> FRAC
+    }
+
+    /// Drops the `FRAC` trits a product has too many of, which rounds it
+    fn narrow(wide: DoubleWord) -> Self {
+        Fixed((wide >> FRAC).into())
+    }
+
+    /// Scaled by `3^FRAC` once more, so a quotient keeps its fraction
+    fn widen(self) -> DoubleWord {
+        DoubleWord::from(self.0) << FRAC
+    }
+
+    /// `3^FRAC`, what the word is scaled by
+    fn scale() -> f64 {
+        3f64.powi(FRAC as i32)
+    }
+}
+
+impl<const FRAC: usize> Neg for Fixed<FRAC> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Fixed(-self.0)
+    }
+}
+
+impl<const FRAC: usize> Add for Fixed<FRAC> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fixed(self.0 + rhs.0)
+    }
+}
+
+impl<const FRAC: usize> Sub for Fixed<FRAC> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Fixed(self.0 - rhs.0)
+    }
+}
+
+impl<const FRAC: usize> Mul for Fixed<FRAC> {
+    type Output = Self;
+
+    /// The full product has `2 * FRAC` fractional trits, and the extra ones are dropped
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::narrow(self.0.wide_mul(rhs.0))
+    }
+}
+
+impl<const FRAC: usize> Div for Fixed<FRAC> {
+    type Output = Result<Self, DivByZeroError>;
+
+    /// Rounds to the nearest, like the product does
+    fn div(self, rhs: Self) -> Self::Output {
+        let (quot, _) = self.widen().div_rem(DoubleWord::from(rhs.0), Rounding::Nearest)?;
+        Ok(Fixed(quot.into()))
+    }
+}
+
+impl<const FRAC: usize> From<Fixed<FRAC>> for f64 {
+    fn from(value: Fixed<FRAC>) -> Self {
+        i128::from(value.0) as f64 / Fixed::<FRAC>::scale()
+    }
+}
+
+/// Rounds to the nearest, failing for NaN and anything out of range
+impl<const FRAC: usize> TryFrom<f64> for Fixed<FRAC> {
+    type Error = OutOfRangeError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        let scaled = (value * Self::scale()).round();
+        if !scaled.is_finite() || scaled.abs() > i128::from(Word::MAX) as f64 {
+            return Err(OutOfRangeError);
+        }
+        Word::try_from(scaled as i128).map(Self::from_word)
+    }
+}
+
+/// Rounds to the nearest once there are more than 21 significant trits
+impl<const FRAC: usize> From<Fixed<FRAC>> for Floating {
+    fn from(value: Fixed<FRAC>) -> Self {
+        // The mantissa's point sits below its top trit, 20 trits up
+        Floating::from_parts(value.0.into(), 20 - FRAC as isize)
+    }
+}
+
+/// Rounds to the nearest, failing for NaN, the infinities and anything out of range
+impl<const FRAC: usize> TryFrom<Floating> for Fixed<FRAC> {
+    type Error = OutOfRangeError;
+
+    fn try_from(value: Floating) -> Result<Self, Self::Error> {
+        value.to_scaled(FRAC as isize).map(Self::from_word).ok_or(OutOfRangeError)
+    }
+}
+
+/// The integer trits without leading zeros, a point, then all `FRAC` fractional trits
+impl<const FRAC: usize> Display for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trits: [Trit; WORD_LEN] = self.0.into();
+        let integer: String = trits[FRAC..].iter().rev().map(|&trit| trit.to_char()).collect();
+        let integer = match integer.trim_start_matches('0') {
+            "" => "0",
+            integer => integer,
+        };
+        let fraction: String = trits[..FRAC].iter().rev().map(|&trit| trit.to_char()).collect();
+        if FRAC == 0 {
+            f.pad(integer)
+        } else {
+            f.pad(&format!("{integer}.{fraction}"))
+        }
+    }
+}
+
+/// Trits with an optional point, like `1T0.01T`. Fractions shorter than
+/// `FRAC` are zero extended, longer ones are an error rather than rounded.
+impl<const FRAC: usize> FromStr for Fixed<FRAC> {
+    type Err = ParseTernaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if integer.is_empty() {
+            return Err(ParseTernaryError::Empty);
+        }
+        if let Some(c) = fraction.chars().find(|c| !matches!(c, 'T' | '0' | '1')) {
+            return Err(ParseTernaryError::InvalidDigit(c));
+        }
+        let extra = fraction.len().saturating_sub(FRAC);
+        if fraction[fraction.len() - extra..].contains(['T', '1']) {
+            return Err(ParseTernaryError::OutOfRange);
+        }
+        let fraction = &fraction[..fraction.len() - extra];
+        let padding = "0".repeat(FRAC - fraction.len());
+        Word::from_trits(&format!("{integer}{fraction}{padding}")).map(Self::from_word)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        errors::{DivByZeroError, OutOfRangeError, ParseTernaryError},
+        fixed::Fixed,
+        floating::Floating,
+        word::Word,
+    };
+
+    type Q9 = Fixed<9>;
+
+    fn q9(value: f64) -> Q9 {
+        value.try_into().unwrap()
+    }
+
+    #[test]
+    fn arithmetic() {
+        let step = 3f64.powi(-9);
+        // Thirds and ninths are exact, where halves and quarters would round
+        assert_eq!(q9(4.0 / 3.0) + q9(2.0 / 9.0), q9(14.0 / 9.0));
+        assert_eq!(q9(1.0) - q9(7.0 / 3.0), q9(-4.0 / 3.0));
+        assert_eq!(-q9(0.75), q9(-0.75));
+        assert_eq!(q9(3.0) * q9(-4.0), q9(-12.0));
+        assert_eq!(Q9::from_integer(7.into()).to_integer(), Word::from(7));
+
+        // The product's extra trits round it to the nearest step
+        for (a, b) in [(1.5, 2.25), (-0.3, 7.1), (100.0, -0.001), (0.5, 0.5)] {
+            let product = f64::from(q9(a) * q9(b));
+            let exact = f64::from(q9(a)) * f64::from(q9(b));
+            assert!((product - exact).abs() <= step / 2.0, "{a} * {b}");
+        }
+        for (a, b) in [(1.0, 3.0), (-2.0, 3.0), (7.5, -0.25), (1000.0, 7.0)] {
+            let quot = f64::from((q9(a) / q9(b)).unwrap());
+            let exact = f64::from(q9(a)) / f64::from(q9(b));
+            assert!((quot - exact).abs() <= step / 2.0, "{a} / {b}");
+        }
+        assert_eq!((q9(1.0) / q9(3.0)).unwrap().to_word(), Word::from(3isize.pow(8)));
+        assert_eq!(q9(1.0) / Q9::ZERO, Err(DivByZeroError));
+
+        // Wraps like the integers do
+        assert_eq!(Q9::MAX + Q9::EPSILON, Q9::MIN);
+        assert!(q9(-1.0) < Q9::EPSILON && Q9::EPSILON < q9(0.001));
+    }
+
+    #[test]
+    fn to_integer() {
+        for (value, expected) in [(2.4, 2), (2.6, 3), (-2.4, -2), (-2.6, -3), (0.4, 0)] {
+            assert_eq!(q9(value).to_integer(), Word::from(expected));
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(q9(0.5).to_word(), Word::from(9842));
+        assert_eq!(f64::from(Q9::EPSILON), 3f64.powi(-9));
+        let max = f64::from(Q9::MAX);
+        assert_eq!(Q9::try_from(max), Ok(Q9::MAX));
+        assert_eq!(Q9::try_from(max * 1.01), Err(OutOfRangeError));
+        assert_eq!(Q9::try_from(f64::NAN), Err(OutOfRangeError));
+        assert_eq!(Q9::try_from(f64::NEG_INFINITY), Err(OutOfRangeError));
+
+        for value in [0.0, 1.0, -1.0, 1.0 / 3.0, 12345.678, -0.001] {
+            let fixed = q9(value);
+            let float = Floating::from(fixed);
+            assert_relative_eq!(f64::from(float), f64::from(fixed), max_relative = 1e-15);
+            assert_eq!(Q9::try_from(float), Ok(fixed));
+        }
+        // A word has more trits than a mantissa, so big values lose their low ones
+        let big = Q9::from_word(Word::MAX);
+        assert_eq!(Q9::try_from(Floating::from(big)), Ok(Q9::from_word(Word::MAX - 364.into())));
+        assert_eq!(Q9::try_from(Floating::NAN), Err(OutOfRangeError));
+        assert_eq!(Q9::try_from(Floating::from(1e13)), Err(OutOfRangeError));
+        assert_eq!(Fixed::<0>::try_from(Floating::from(2.6)), Ok(Fixed::from_integer(3.into())));
+    }
+
+    #[test]
+    fn format() {
+        let x: Fixed<3> = Fixed::from_word(Word::from(6 * 27 + 2));
+        assert_eq!(x.to_string(), "1T0.01T");
+        assert_eq!((-x).to_string(), "T10.0T1");
+        assert_eq!(Fixed::<3>::ZERO.to_string(), "0.000");
+        assert_eq!(Fixed::<3>::EPSILON.to_string(), "0.001");
+        assert_eq!(Fixed::<0>::from_integer(5.into()).to_string(), "1TT");
+        assert_eq!(format!("{:>10}|", x), "   1T0.01T|");
+    }
+
+    #[test]
+    fn parse() {
+        let x: Fixed<3> = Fixed::from_word(Word::from(6 * 27 + 2));
+        assert_eq!("1T0.01T".parse(), Ok(x));
+        assert_eq!("1T0.01T000".parse(), Ok(x));
+        assert_eq!("001T0.01T".parse(), Ok(x));
+        assert_eq!("1T".parse(), Ok(Fixed::<3>::from_integer(2.into())));
+        assert_eq!("0.1".parse(), Ok(Fixed::<3>::from_word(9.into())));
+        assert_eq!("1.".parse(), Ok(Fixed::<3>::from_integer(1.into())));
+
+        assert_eq!("".parse::<Fixed<3>>(), Err(ParseTernaryError::Empty));
+        assert_eq!(".1".parse::<Fixed<3>>(), Err(ParseTernaryError::Empty));
+        assert_eq!("1.012".parse::<Fixed<3>>(), Err(ParseTernaryError::InvalidDigit('2')));
+        assert_eq!("1.0.1".parse::<Fixed<3>>(), Err(ParseTernaryError::InvalidDigit('.')));
+        assert_eq!("12.1".parse::<Fixed<3>>(), Err(ParseTernaryError::InvalidDigit('2')));
+        assert_eq!("0.0001".parse::<Fixed<3>>(), Err(ParseTernaryError::OutOfRange));
+        assert_eq!("1".repeat(25).parse::<Fixed<3>>(), Err(ParseTernaryError::OutOfRange));
+
+        for value in (-9841isize..=9841).step_by(97) {
+            let fixed = Fixed::<5>::from_word(value.into());
+            assert_eq!(fixed.to_string().parse(), Ok(fixed));
+        }
+    }
+}
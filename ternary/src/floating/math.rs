@@ -0,0 +1,597 @@
+//! Elementary functions on [`Floating`], worked out with nothing but integer
+//! arithmetic on balanced ternary so each one can be carried over to JX-01
+//! assembly as it stands.
+//!
+//! The base does most of the range reduction: [`exp`] splits off a power of
+//! 3 to become the exponent, [`ln`] reads one back out of the exponent, and
+//! [`sin`] and [`cos`] take out multiples of π/2. What's left is small enough
+//! for a short series, summed with 38 trits and rounded once at the end.
+//!
+//! | function                            | error                          |
+//! |-------------------------------------|--------------------------------|
+//! | [`sqrt`]                            | 0.5 ulp, correctly rounded     |
+//! | [`exp`], [`ln`], [`atan2`], [`pow`] | under 1 ulp                    |
+//! | [`sin`], [`cos`]                    | under 1 ulp within `±3^20`     |
+//!
+//! An ulp is `3^(e - 20)` for a result with exponent `e`. Past `3^20` the
+//! error in π/2 grows with the multiples of it taken out, and past `3^40` not
+//! even the quadrant is known, so [`sin`] and [`cos`] give NaN.
+
+use std::{
+    cmp::Ordering,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::floating::{Floating, MANTISSA_SIZE, UNIT};
+
+/// Trits kept in a [`Wide`] mantissa, so two of them multiply in an `i128`
+const PREC: isize = 38;
+/// How many trits an `i128` holds with a trit to spare for a sum
+const ROOM: isize = 2 * PREC + 2;
+/// `1` at the scale the series are summed at
+const ONE: i128 = 3i128.pow(PREC as u32);
+/// `π/2 * 3^76`
+const HALF_PI: Wide = Wide { num: 2866389707554420331259061188917016644, scale: 2 * PREC };
+/// `ln 3 * 3^76`
+const LN_3: Wide = Wide { num: 2004748103311713410410689189606816005, scale: 2 * PREC };
+
+/// `num * 3^-scale`, with [`PREC`] trits of mantissa, for the working in
+/// between the argument and the one rounding of the result
+#[derive(Clone, Copy, Debug)]
+struct Wide {
+    num: i128,
+    scale: isize,
+}
+
+/// How many balanced trits it takes to write `num`
+fn len(num: i128) -> isize {
+    let (mut len, mut max) = (0, 0u128);
+    while max < num.unsigned_abs() {
+        max = max * 3 + 1;
+        len += 1;
+    }
+    len
+}
+
+/// `lhs / rhs` rounded to the nearest, without doubling either
+fn div_round(lhs: i128, rhs: i128) -> i128 {
+    let (lhs, rhs) = if rhs < 0 { (-lhs, -rhs) } else { (lhs, rhs) };
+    let (quot, rem) = (lhs.div_euclid(rhs), lhs.rem_euclid(rhs));
+    if rem > rhs - rem { quot + 1 } else { quot }
+}
+
+/// `num * 3^by`, rounding off the dropped trits when `by` is negative
+fn shift(num: i128, by: isize) -> i128 {
+    if by >= 0 {
+        num * 3i128.pow(by as u32)
+    } else if by < -80 {
+        0
+    } else {
+        div_round(num, 3i128.pow(-by as u32))
+    }
+}
+
+impl Wide {
+    const ZERO: Wide = Wide { num: 0, scale: 0 };
+
+    fn new(num: i128, scale: isize) -> Wide {
+        Wide { num, scale }.trim()
+    }
+
+    /// Rounds off any trits past [`PREC`]
+    fn trim(self) -> Wide {
+        let excess = len(self.num) - PREC;
+        if excess > 0 {
+            Wide { num: shift(self.num, -excess), scale: self.scale - excess }
+        } else {
+            self
+        }
+    }
+
+    fn from(x: Floating) -> Wide {
+        let (mantissa, exponent) = x.normal_parts();
+        Wide::new(mantissa, MANTISSA_SIZE as isize - 1 - exponent)
+    }
+
+    fn round(self) -> Floating {
+        Floating::from_parts(self.num, MANTISSA_SIZE as isize - 1 - self.scale)
+    }
+
+    /// The value as a multiple of `3^-scale`, which the caller makes sure fits
+    fn at(self, scale: isize) -> i128 {
+        shift(self.num, scale - self.scale)
+    }
+
+    fn times(self, factor: i128) -> Wide {
+        let this = self.trim();
+        Wide::new(this.num * factor, this.scale)
+    }
+}
+
+impl Add for Wide {
+    type Output = Wide;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = (self.trim(), rhs.trim());
+        if lhs.num == 0 {
+            return rhs;
+        } else if rhs.num == 0 {
+            return lhs;
+        }
+        // Line both up at the finer scale, or as fine as there's room for
+        let scale = lhs
+            .scale
+            .max(rhs.scale)
+            .min(lhs.scale + ROOM - len(lhs.num))
+            .min(rhs.scale + ROOM - len(rhs.num));
+        Wide::new(lhs.at(scale) + rhs.at(scale), scale)
+    }
+}
+
+impl Neg for Wide {
+    type Output = Wide;
+
+    fn neg(self) -> Self::Output {
+        Wide { num: -self.num, scale: self.scale }
+    }
+}
+
+impl Sub for Wide {
+    type Output = Wide;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Wide {
+    type Output = Wide;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = (self.trim(), rhs.trim());
+        Wide::new(lhs.num * rhs.num, lhs.scale + rhs.scale)
+    }
+}
+
+impl Div for Wide {
+    type Output = Wide;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        // Twice the trits in the dividend leaves all of them in the quotient
+        let (lhs, rhs) = (self.trim(), rhs.trim());
+        let up = 2 * PREC - len(lhs.num);
+        Wide::new(div_round(shift(lhs.num, up), rhs.num), lhs.scale + up - rhs.scale)
+    }
+}
+
+/// `Σ x^n / divisor(n)` from `n = 0` until the powers round away, with `x`
+/// at the scale of [`ONE`] and under it
+fn sum_powers(x: i128, divisor: impl Fn(i128) -> i128) -> i128 {
+    let (mut sum, mut power, mut n) = (0, ONE, 0);
+    while power != 0 {
+        sum += div_round(power, divisor(n));
+        power = div_round(power * x, ONE);
+        n += 1;
+    }
+    sum
+}
+
+/// `Σ t(n)` where `t(0) = 1` and `t(n) = t(n - 1) * x / divisor(n)`, until
+/// the terms round away
+fn sum_terms(x: i128, divisor: impl Fn(i128) -> i128) -> i128 {
+    let (mut sum, mut term, mut n) = (0, ONE, 0);
+    while term != 0 {
+        sum += term;
+        n += 1;
+        term = div_round(term * x, ONE * divisor(n));
+    }
+    sum
+}
+
+/// The square root, rounded correctly. NaN for anything negative.
+pub fn sqrt(x: Floating) -> Floating {
+    if x.is_nan() || x < Floating::ZERO {
+        return Floating::NAN;
+    } else if x == Floating::ZERO || x.is_infinite() {
+        return x;
+    }
+
+    // x = mantissa * 3^exponent. Scale the mantissa up to twice the trits
+    // its root needs and leave an even power of 3 to halve.
+    let (mantissa, exponent) = x.normal_parts();
+    let exponent = exponent - (MANTISSA_SIZE as isize - 1);
+    let up = 44 + (exponent - 44).rem_euclid(2);
+    let radicand = mantissa * 3i128.pow(up as u32);
+    let root = radicand.isqrt();
+
+    // The root is an integer or irrational, so never halfway. A third or two
+    // thirds past `root`, whichever side the true root is on, rounds the same.
+    let rest = if root * root == radicand {
+        0
+    } else if 4 * radicand > (2 * root + 1).pow(2) {
+        2
+    } else {
+        1
+    };
+    Floating::from_parts(3 * root + rest, (exponent - up) / 2 - 1 + MANTISSA_SIZE as isize - 1)
+}
+
+/// `e^x`
+pub fn exp(x: Floating) -> Floating {
+    if x.is_nan() {
+        Floating::NAN
+    } else if x.is_infinite() {
+        if x > Floating::ZERO { x } else { Floating::ZERO }
+    } else {
+        exp_wide(Wide::from(x))
+    }
+}
+
+/// [`exp`] of a [`Wide`], so [`pow`] can hand over its product unrounded
+fn exp_wide(x: Wide) -> Floating {
+    // Anything past 3^7 is past the log of every finite value
+    if len(x.num) - x.scale > 7 {
+        return if x.num > 0 { Floating::INFINITY } else { Floating::ZERO };
+    }
+
+    // e^x = 3^k e^r, with r no further from 0 than half of ln 3
+    let (x, ln_3) = (x.at(PREC), LN_3.at(PREC));
+    let k = div_round(x, ln_3);
+    let e_r = sum_terms(x - k * ln_3, |n| n);
+    Floating::from_parts(e_r, k as isize - PREC + MANTISSA_SIZE as isize - 1)
+}
+
+/// The natural logarithm. NaN for anything negative, `-∞` for zero.
+pub fn ln(x: Floating) -> Floating {
+    if x.is_nan() || x < Floating::ZERO {
+        Floating::NAN
+    } else if x == Floating::ZERO {
+        Floating::NEG_INFINITY
+    } else if x.is_infinite() {
+        x
+    } else {
+        ln_wide(x).round()
+    }
+}
+
+/// [`ln`] of a finite positive `x`, unrounded
+fn ln_wide(x: Floating) -> Wide {
+    // x = f 3^e with f in [0.5, 1.5), and ln f = 2 atanh((f - 1) / (f + 1))
+    // where the quotient is within a third of 0. Working it out from the
+    // mantissa keeps every trit of f - 1 when x is close to 1.
+    let (mantissa, exponent) = x.normal_parts();
+    let z = Wide::new(mantissa - UNIT, 0) / Wide::new(mantissa + UNIT, 0);
+    let z2 = (z * z).at(PREC);
+    let ln_f = (z * Wide::new(sum_powers(z2, |n| 2 * n + 1), PREC)).times(2);
+    LN_3.times(exponent as i128) + ln_f
+}
+
+/// `x - q π/2` and `q`, or `None` for anything without a quadrant
+fn reduce(x: Floating) -> Option<(Wide, i128)> {
+    let (mantissa, exponent) = x.normal_parts();
+    if !x.is_finite() || exponent > 2 * (MANTISSA_SIZE as isize - 1) {
+        return None;
+    } else if exponent < MANTISSA_SIZE as isize - 1 - PREC {
+        // Smaller than the last trit of π/2
+        return Some((Wide::from(x), 0));
+    }
+
+    // Take out q π/2 in two halves, the low one at twice the scale, so its
+    // error stays below 3^-56 for q up to 3^20
+    let x = mantissa * 3i128.pow((exponent + PREC - (MANTISSA_SIZE as isize - 1)) as u32);
+    let high = HALF_PI.at(PREC);
+    let low = HALF_PI.num - high * ONE;
+    let q = div_round(x, high);
+    let r = (x - q * high) * ONE - q * low;
+    Some((Wide::new(r, 2 * PREC), q))
+}
+
+/// `sin r` and `cos r` for `r` within π/4 or so of 0
+fn sin_cos(r: Wide) -> (Wide, Wide) {
+    let r2 = (r * r).at(PREC);
+    let sin = r * Wide::new(sum_terms(-r2, |n| 2 * n * (2 * n + 1)), PREC);
+    let cos = Wide::new(sum_terms(-r2, |n| (2 * n - 1) * (2 * n)), PREC);
+    (sin, cos)
+}
+
+/// The sine of `x` radians. NaN for infinities and past `3^40`.
+pub fn sin(x: Floating) -> Floating {
+    let Some((r, q)) = reduce(x) else {
+        return Floating::NAN;
+    };
+    let (sin, cos) = sin_cos(r);
+    match q.rem_euclid(4) {
+        0 => sin,
+        1 => cos,
+        2 => -sin,
+        _ => -cos,
+    }
+    .round()
+}
+
+/// The cosine of `x` radians. NaN for infinities and past `3^40`.
+pub fn cos(x: Floating) -> Floating {
+    let Some((r, q)) = reduce(x) else {
+        return Floating::NAN;
+    };
+    let (sin, cos) = sin_cos(r);
+    match q.rem_euclid(4) {
+        0 => cos,
+        1 => -sin,
+        2 => -cos,
+        _ => sin,
+    }
+    .round()
+}
+
+/// `atan t` for `t` in `[0, 1]`
+fn atan(mut t: Wide) -> Wide {
+    // tan(a / 2) = tan a / (1 + sqrt(1 + tan² a)), twice, takes t under
+    // tan(π/16) ≈ 0.2 where the series is short
+    for _ in 0..2 {
+        let t2 = (t * t).at(PREC);
+        let root = ((ONE + t2) * ONE).isqrt();
+        t = t / Wide::new(ONE + root, PREC);
+    }
+    let t2 = (t * t).at(PREC);
+    (t * Wide::new(sum_powers(-t2, |n| 2 * n + 1), PREC)).times(4)
+}
+
+/// The angle from the positive x axis to `(x, y)`, in `[-π, π]`. Zero has no
+/// sign, so anything on the negative x axis is `π`.
+pub fn atan2(y: Floating, x: Floating) -> Floating {
+    if y.is_nan() || x.is_nan() {
+        return Floating::NAN;
+    }
+
+    // The angle of (|x|, |y|), in [0, π/2]
+    let (abs_y, abs_x) = (if y < Floating::ZERO { -y } else { y }, if x < Floating::ZERO { -x } else { x });
+    let angle = if abs_y.is_infinite() && abs_x.is_infinite() {
+        HALF_PI / Wide::new(2, 0)
+    } else if abs_x.is_infinite() || abs_y == Floating::ZERO {
+        Wide::ZERO
+    } else if abs_y.is_infinite() || abs_x == Floating::ZERO {
+        HALF_PI
+    } else if abs_y <= abs_x {
+        atan(Wide::from(abs_y) / Wide::from(abs_x))
+    } else {
+        HALF_PI - atan(Wide::from(abs_x) / Wide::from(abs_y))
+    };
+
+    let angle = if x < Floating::ZERO { HALF_PI.times(2) - angle } else { angle };
+    if y < Floating::ZERO { -angle } else { angle }.round()
+}
+
+/// Whether `y` is an odd integer, or `None` if it's not an integer at all.
+/// The infinities count as even.
+fn odd(y: Floating) -> Option<bool> {
+    if y.is_infinite() {
+        return Some(false);
+    }
+    // y = mantissa * 3^exponent, and odd times a power of 3 is odd
+    let (mantissa, exponent) = y.normal_parts();
+    let exponent = exponent - (MANTISSA_SIZE as isize - 1);
+    if exponent >= 0 {
+        return Some(mantissa % 2 != 0);
+    } else if -exponent > MANTISSA_SIZE as isize {
+        return None;
+    }
+    let unit = 3i128.pow(-exponent as u32);
+    (mantissa % unit == 0).then(|| mantissa / unit % 2 != 0)
+}
+
+/// `x` to the power `y`. Negative `x` only has powers that are integers, odd
+/// ones negative, and anything to the power 0 or 1 to any power is 1.
+pub fn pow(x: Floating, y: Floating) -> Floating {
+    if y == Floating::ZERO || x == Floating::ONE {
+        return Floating::ONE;
+    } else if x.is_nan() || y.is_nan() {
+        return Floating::NAN;
+    }
+
+    let negative = if x < Floating::ZERO {
+        match odd(y) {
+            Some(odd) => odd,
+            None => return Floating::NAN,
+        }
+    } else {
+        false
+    };
+    let x = if x < Floating::ZERO { -x } else { x };
+
+    let result = if x == Floating::ZERO {
+        if y > Floating::ZERO { Floating::ZERO } else { Floating::INFINITY }
+    } else if x.is_infinite() {
+        if y > Floating::ZERO { Floating::INFINITY } else { Floating::ZERO }
+    } else if y.is_infinite() {
+        match (x.partial_cmp(&Floating::ONE), y > Floating::ZERO) {
+            (Some(Ordering::Equal), _) => Floating::ONE,
+            (Some(Ordering::Greater), true) | (Some(Ordering::Less), false) => Floating::INFINITY,
+            _ => Floating::ZERO,
+        }
+    } else {
+        exp_wide(ln_wide(x) * Wide::from(y))
+    };
+    if negative { -result } else { result }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::f64::consts::{E, PI};
+
+    use crate::floating::{
+        math::{atan2, cos, exp, ln, pow, sin, sqrt},
+        Floating, MANTISSA_SIZE, MIN_EXPONENT, UNIT,
+    };
+
+    /// How far `result` is from `reference`, in ulps of `reference`. The
+    /// exponent comes from the f64 itself, not from converting it, so a
+    /// conversion that rounds to the wrong exponent can't hide here.
+    fn ulps(result: Floating, reference: f64) -> f64 {
+        // A mantissa is in [0.5, 1.5), so the exponent is where 2|x| is in [3^e, 3^(e+1))
+        let exponent = (2.0 * reference.abs()).log(3.0).floor() as isize;
+        // Subnormals all have the ulp of the smallest exponent
+        let exponent = exponent.max(MIN_EXPONENT);
+        let ulp = 3f64.powi((exponent - MANTISSA_SIZE as isize + 1) as i32);
+        (f64::from(result) - reference).abs() / ulp
+    }
+
+    /// `count` values spread over `[low, high)`, evenly on a log scale when
+    /// `low` is positive
+    fn spread(low: f64, high: f64, count: usize) -> impl Iterator<Item = Floating> {
+        // The golden ratio keeps the points from lining up with anything
+        (0..count).map(move |i| {
+            let at = (i as f64 * 0.618_033_988_749_895).fract();
+            Floating::from(if low > 0.0 { (low.ln() + (high.ln() - low.ln()) * at).exp() } else { low + (high - low) * at })
+        })
+    }
+
+    /// Checks `result` is within `bound` ulps of `reference`, give or take
+    /// what the f64 reference gets wrong itself
+    fn check(result: Floating, reference: f64, bound: f64, what: &str) {
+        let error = ulps(result, reference);
+        assert!(error <= bound + 1e-5, "{what}: {} is {error} ulps from {reference}", f64::from(result));
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for x in spread(1e-170, 1e170, 3000) {
+            check(sqrt(x), f64::from(x).sqrt(), 0.5, &format!("sqrt {}", f64::from(x)));
+        }
+        for n in 0..2000 {
+            assert_eq!(sqrt(Floating::from((n * n) as f64)), Floating::from(n as f64));
+        }
+        // Roots right at the top of an exponent, where rounding is lopsided
+        for k in -150..150 {
+            let x = Floating::from(2.25 * 9f64.powi(k));
+            check(sqrt(x), f64::from(x).sqrt(), 0.5, &format!("sqrt {}", f64::from(x)));
+        }
+        assert_eq!(sqrt(Floating::from(1.0 / 9.0)).0, Floating::from(1.0 / 3.0).0);
+        let smallest = Floating::from(3f64.powi(-384));
+        assert_eq!(sqrt(smallest).0, Floating::from(3f64.powi(-192)).0);
+        assert_eq!(sqrt(Floating::INFINITY).0, Floating::INFINITY.0);
+        assert!(sqrt(Floating::from(-1.0)).is_nan());
+        assert!(sqrt(Floating::NAN).is_nan());
+        assert_eq!(sqrt(Floating::ZERO), Floating::ZERO);
+    }
+
+    #[test]
+    fn test_exp() {
+        for x in spread(-420.0, 399.0, 3000).chain(spread(1e-30, 1.0, 500)) {
+            check(exp(x), f64::from(x).exp(), 1.0, &format!("exp {}", f64::from(x)));
+        }
+        assert_eq!(exp(Floating::ZERO), Floating::ONE);
+        assert_eq!(exp(Floating::from(400.0)).0, Floating::INFINITY.0);
+        assert_eq!(exp(Floating::from(-430.0)), Floating::ZERO);
+        assert_eq!(exp(Floating::NEG_INFINITY), Floating::ZERO);
+        assert_eq!(exp(Floating::INFINITY).0, Floating::INFINITY.0);
+        assert!(exp(Floating::NAN).is_nan());
+        check(exp(Floating::ONE), E, 1.0, "e");
+    }
+
+    #[test]
+    fn test_ln() {
+        for x in spread(1e-175, 1e170, 3000).chain(spread(0.5, 1.5, 1000)) {
+            // Close to 1, go by x - 1 since the f64 of x alone isn't near enough
+            let (mantissa, exponent) = x.normal_parts();
+            let reference = if exponent == 0 {
+                ((mantissa - UNIT) as f64 * 3f64.powi(1 - MANTISSA_SIZE as i32)).ln_1p()
+            } else {
+                f64::from(x).ln()
+            };
+            check(ln(x), reference, 1.0, &format!("ln {}", f64::from(x)));
+        }
+        assert_eq!(ln(Floating::ONE), Floating::ZERO);
+        check(ln(Floating::from(3f64.powi(-384))), -384.0 * 3f64.ln(), 1.0, "ln of the smallest subnormal");
+        assert_eq!(ln(Floating::ZERO).0, Floating::NEG_INFINITY.0);
+        assert_eq!(ln(Floating::INFINITY).0, Floating::INFINITY.0);
+        assert!(ln(Floating::from(-1.0)).is_nan());
+        assert!(ln(Floating::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_sin_cos() {
+        // Both conversions of an integer are exact, so the references are
+        // good however close the integer gets to a multiple of π
+        let integers = (1..5000).chain([100_000, 1_000_000_007, 3i64.pow(20) - 1, 3i64.pow(20)]);
+        for x in integers.flat_map(|n| [n as f64, -n as f64]).map(Floating::from).chain(spread(-1.5, 1.5, 3000)) {
+            let value = f64::from(x);
+            check(sin(x), value.sin(), 1.0, &format!("sin {value}"));
+            check(cos(x), value.cos(), 1.0, &format!("cos {value}"));
+        }
+        for x in spread(1e-40, 1e-5, 200) {
+            assert_eq!(sin(x).0, x.0);
+            assert_eq!(cos(x), Floating::ONE);
+        }
+        assert_eq!(sin(Floating::ZERO), Floating::ZERO);
+        assert_eq!(cos(Floating::ZERO), Floating::ONE);
+        assert!(sin(Floating::INFINITY).is_nan());
+        assert!(cos(Floating::NAN).is_nan());
+        assert!(sin(Floating::from(1e20)).is_nan());
+    }
+
+    #[test]
+    fn test_atan2() {
+        let values: Vec<_> = spread(-50.0, 50.0, 60).chain(spread(1e-20, 1e20, 40)).collect();
+        for &y in &values {
+            for &x in &values {
+                let reference = f64::from(y).atan2(f64::from(x));
+                check(atan2(y, x), reference, 1.0, &format!("atan2 {} {}", f64::from(y), f64::from(x)));
+            }
+        }
+
+        let (one, zero, inf) = (Floating::ONE, Floating::ZERO, Floating::INFINITY);
+        for (y, x, reference) in [
+            (zero, zero, 0.0),
+            (zero, one, 0.0),
+            (zero, -one, PI),
+            (one, zero, PI / 2.0),
+            (-one, zero, -PI / 2.0),
+            (one, one, PI / 4.0),
+            (-one, -one, -3.0 * PI / 4.0),
+            (inf, one, PI / 2.0),
+            (one, -inf, PI),
+            (-one, inf, 0.0),
+            (inf, inf, PI / 4.0),
+            (-inf, -inf, -3.0 * PI / 4.0),
+        ] {
+            check(atan2(y, x), reference, 1.0, &format!("atan2 {} {}", f64::from(y), f64::from(x)));
+        }
+        assert!(atan2(Floating::NAN, one).is_nan());
+    }
+
+    #[test]
+    fn test_pow() {
+        for x in spread(1e-3, 1e3, 60) {
+            for y in spread(-50.0, 50.0, 60) {
+                let (x, y) = (f64::from(x), f64::from(y));
+                check(pow(Floating::from(x), Floating::from(y)), x.powf(y), 1.0, &format!("pow {x} {y}"));
+            }
+        }
+        for n in -20..=20 {
+            let reference = (-3f64).powi(n);
+            assert_eq!(pow(Floating::from(-3.0), Floating::from(n as f64)), Floating::from(reference), "-3^{n}");
+            check(pow(Floating::from(2.0), Floating::from(n as f64)), 2f64.powi(n), 1.0, &format!("2^{n}"));
+        }
+
+        let (one, zero, inf, nan) = (Floating::ONE, Floating::ZERO, Floating::INFINITY, Floating::NAN);
+        let (two, half) = (Floating::from(2.0), Floating::from(0.5));
+        assert_eq!(pow(nan, zero), one);
+        assert_eq!(pow(one, nan), one);
+        assert!(pow(nan, one).is_nan());
+        assert!(pow(-two, half).is_nan());
+        assert_eq!(pow(zero, two), zero);
+        assert_eq!(pow(zero, -two).0, inf.0);
+        assert_eq!(pow(two, inf).0, inf.0);
+        assert_eq!(pow(half, inf), zero);
+        assert_eq!(pow(two, -inf), zero);
+        assert_eq!(pow(-one, inf), one);
+        assert_eq!(pow(-inf, Floating::from(3.0)).0, Floating::NEG_INFINITY.0);
+        assert_eq!(pow(-inf, -two), zero);
+        assert_eq!(pow(Floating::from(10.0), Floating::from(400.0)).0, inf.0);
+        assert_eq!(pow(Floating::from(10.0), Floating::from(-400.0)), zero);
+        let n = 3f64.powi(20);
+        let reference = (n * (1.0 / n).ln_1p()).exp();
+        check(pow(Floating::ONE + Floating::EPSILON, Floating::from(n)), reference, 1.0, "(1 + 1/n)^n");
+    }
+}
@@ -0,0 +1,1024 @@
+pub mod math;
+
+use std::{
+    cmp::Ordering,
+    num::FpCategory,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    ternary::Ternary,
+    trits::Trit,
+    word::Word,
+    WORD_LEN,
+};
+
+/// The fundamental ternary floating point type.
+///
+/// This type has no need for a sign bit or bias, due to balanced
+/// ternary already being
+/// a) balanced
+/// b) signed.
+///
+/// This means we can represent our mantissa and exponent transparently.
+/// Suppose we have an exponent of size 6 and mantissa of size 21.
+///
+/// Exponent  Mantissa
+/// +----+ +-------------------+
+/// T110T1 10001T10010T101T10111
+///  |         |
+/// -137   3520429789
+///
+/// An exponent of size 6 gives an accuracy in between IEEE-754 single precision
+/// and IEEE-754 double precision.
+///
+/// The point sits just below the top trit of the mantissa, so the value is
+/// `mantissa / 3^20 * 3^exponent`. Results are normalised so that top trit
+/// isn't zero, which puts the mantissa's magnitude in `[0.5, 1.5)`. Dropping
+/// the trits that don't fit already rounds to the nearest, except at the
+/// bottom of an exponent, where the value below is the top of the exponent
+/// under it. That's only a third of a step away, so it gets checked too.
+///
+/// Two exponents are set aside:
+///
+/// * `364`, all `POne`, holds the special values. A mantissa of `±3^20`, the
+///   top trit alone, is `±∞` and any other mantissa is NaN. [`Floating::NAN`]
+///   has a zero mantissa.
+/// * `-364`, all `NOne`, is the only exponent whose mantissa may have a zero
+///   top trit, which makes it subnormal. Values shrink a trit at a time down
+///   to `3^-384` before they flush to zero.
+///
+/// Zero has no sign, there being nothing for it to keep. Otherwise the
+/// special values propagate like IEEE-754's: NaN in gives NaN out, results
+/// past [`Floating::MAX`] become infinite, `∞ - ∞`, `0 * ∞`, `0 / 0` and
+/// `∞ / ∞` are NaN, and `x / 0` is infinite with the sign of `x`.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Floating(Word);
+
+const EXPONENT_SIZE: usize = 6;
+const MANTISSA_SIZE: usize = WORD_LEN - EXPONENT_SIZE;
+/// The exponent of infinities and NaN, all six trits `POne`
+const SPECIAL_EXPONENT: isize = 364;
+/// The largest exponent of a finite value
+const MAX_EXPONENT: isize = SPECIAL_EXPONENT - 1;
+/// The exponent of the subnormals, and the smallest normal exponent
+const MIN_EXPONENT: isize = -SPECIAL_EXPONENT;
+/// The largest mantissa, all 21 trits `POne`
+const MAX_MANTISSA: i128 = (3i128.pow(MANTISSA_SIZE as u32) - 1) / 2;
+/// Anything at or below this has a zero top trit
+const MAX_UNNORMAL: i128 = MAX_MANTISSA / 3;
+/// The mantissa of `1`, and of the infinities
+const UNIT: i128 = 3i128.pow(MANTISSA_SIZE as u32 - 1);
+/// How far apart two exponents can be before the smaller value can't change
+/// the sum, with room to spare for the rounding
+const ALIGN: isize = 42;
+
+const_assert_eq!(MAX_UNNORMAL, (3i128.pow(MANTISSA_SIZE as u32 - 1) - 1) / 2);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(transparent)]
+pub struct Mantissa(Word);
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(transparent)]
+pub struct Exponent(Word);
+
+impl From<Floating> for (Mantissa, Exponent) {
+    fn from(value: Floating) -> Self {
+        (
+            Mantissa(value.0.get_field(0..MANTISSA_SIZE)),
+            Exponent(value.0.get_field(MANTISSA_SIZE..WORD_LEN)),
+        )
+    }
+}
+
+impl From<(Mantissa, Exponent)> for Floating {
+    fn from(value: (Mantissa, Exponent)) -> Self {
+        let (Mantissa(mantissa), exponent) = value;
+        debug_assert!(exponent.valid_exponent(), "exponent {} doesn't fit", exponent.0);
+        let mut word = Word::ZERO;
+        word.set_field(0..MANTISSA_SIZE, mantissa);
+        word.set_field(MANTISSA_SIZE..WORD_LEN, exponent.0);
+        Floating(word)
+    }
+}
+
+impl From<Floating> for f64 {
+    fn from(value: Floating) -> Self {
+        match value.classify() {
+            FpCategory::Nan => f64::NAN,
+            FpCategory::Infinite if value > Floating::ZERO => f64::INFINITY,
+            FpCategory::Infinite => f64::NEG_INFINITY,
+            _ => {
+                let (mantissa, exponent) = value.parts();
+                // Both fit: the mantissa in 53 bits and 3^±384 well inside f64's range
+                mantissa as f64 * 3f64.powi((exponent - MANTISSA_SIZE as isize + 1) as i32)
+            }
+        }
+    }
+}
+
+/// Rounds to the nearest, with infinities and NaN carried over
+impl From<f64> for Floating {
+    fn from(value: f64) -> Self {
+        if value.is_nan() {
+            return Floating::NAN;
+        } else if value.is_infinite() {
+            return Floating::infinity(value.signum() as i128);
+        } else if value == 0.0 {
+            return Floating::ZERO;
+        }
+
+        // Scale so the mantissa rounds to a normalised integer, then round
+        // once. Scaling by 3 a step at a time only costs an ulp of an f64 per
+        // step, far less than an ulp of the mantissa.
+        let mut exponent = value.abs().log(3.0).round() as isize;
+        if exponent < MIN_EXPONENT - MANTISSA_SIZE as isize - 1 {
+            // Under half the smallest subnormal, and scaling it up would overflow
+            return Floating::ZERO;
+        }
+        let mut scaled = value * 3f64.powi((MANTISSA_SIZE as isize - 1 - exponent) as i32);
+        // The value after `MAX_MANTISSA` is `MAX_MANTISSA + 2`, a third of the
+        // way into the next exponent up, so the halfway point between them is
+        // `MAX_MANTISSA + 1` on this side and `MAX_UNNORMAL + 2/3` on that one
+        while scaled.abs() >= MAX_MANTISSA as f64 + 1.0 {
+            scaled /= 3.0;
+            exponent += 1;
+        }
+        while scaled.abs() < MAX_UNNORMAL as f64 + 2.0 / 3.0 {
+            scaled *= 3.0;
+            exponent -= 1;
+        }
+        if exponent < MIN_EXPONENT {
+            // Subnormals are evenly spaced, so round once at their exponent
+            scaled /= 3f64.powi((MIN_EXPONENT - exponent) as i32);
+            exponent = MIN_EXPONENT;
+        }
+        let mantissa = (scaled.round() as i128).clamp(-MAX_MANTISSA, MAX_MANTISSA);
+        Floating::from_parts(mantissa, exponent)
+    }
+}
+
+impl Neg for Mantissa {
+    type Output = Mantissa;
+
+    fn neg(self) -> Self::Output {
+        Mantissa(-self.0)
+    }
+}
+
+impl Mul for Mantissa {
+    type Output = Mantissa;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Mantissa(self.0 * rhs.0)
+    }
+}
+
+impl Add for Mantissa {
+    type Output = Mantissa;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Mantissa(self.0 + rhs.0)
+    }
+}
+
+impl Add for Exponent {
+    type Output = Exponent;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Exponent(self.0 + rhs.0)
+    }
+}
+
+impl Exponent {
+    fn valid_exponent(self) -> bool {
+        let arr: [Trit; 27] = self.0.into();
+        arr[EXPONENT_SIZE..].iter().all(|&trit| trit == Trit::Zero)
+    }
+
+    fn leading_zeroes(self) -> usize {
+        let arr: [Trit; 27] = self.0.into();
+        let mut count = 0;
+        for i in (0..EXPONENT_SIZE).rev() {
+            if let Trit::Zero = arr[i] {
+                count += 1;
+            } else {
+                return count;
+            }
+        }
+        count
+    }
+}
+
+impl Mantissa {
+    fn leading_zeroes(self) -> usize {
+        let arr: [Trit; 27] = self.0.into();
+        let mut count = 0;
+        for i in (0..WORD_LEN).rev() {
+            if let Trit::Zero = arr[i] {
+                count += 1;
+            } else {
+                return count;
+            }
+        }
+        count
+    }
+
+    fn trailing_zeroes(self) -> usize {
+        let arr: [Trit; 27] = self.0.into();
+        arr.iter().take_while(|&&trit| trit == Trit::Zero).count()
+    }
+}
+
+impl Neg for Floating {
+    type Output = Floating;
+
+    fn neg(self) -> Self::Output {
+        let (mantissa, exponent): (Mantissa, Exponent) = self.into();
+        let mantissa = -mantissa;
+        (mantissa, exponent).into()
+    }
+}
+
+impl Add for Floating {
+    type Output = Floating;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.is_nan() || rhs.is_nan() {
+            return Floating::NAN;
+        } else if self.is_infinite() {
+            // Infinities of opposite signs cancel out to nothing in particular
+            return if rhs.is_infinite() && rhs.signum() != self.signum() { Floating::NAN } else { self };
+        } else if rhs.is_infinite() {
+            return rhs;
+        }
+
+        let (mantissa_0, exponent_0) = self.normal_parts();
+        let (mantissa_1, exponent_1) = rhs.normal_parts();
+        if mantissa_0 == 0 {
+            return rhs.normalize();
+        } else if mantissa_1 == 0 {
+            return self.normalize();
+        }
+
+        // Line the smaller one up under the larger one, keeping every trit of both
+        let ((big, big_exp), (small, small_exp)) = if exponent_0 >= exponent_1 {
+            ((mantissa_0, exponent_0), (mantissa_1, exponent_1))
+        } else {
+            ((mantissa_1, exponent_1), (mantissa_0, exponent_0))
+        };
+        let diff = big_exp - small_exp;
+        if diff > ALIGN {
+            return Floating::from_parts(big, big_exp);
+        }
+        Floating::from_parts(big * 3i128.pow(diff as u32) + small, small_exp)
+    }
+}
+
+impl Sub for Floating {
+    type Output = Floating;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Floating {
+    type Output = Floating;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        if self.is_nan() || rhs.is_nan() {
+            return Floating::NAN;
+        } else if self.is_infinite() || rhs.is_infinite() {
+            let sign = self.signum() * rhs.signum();
+            return if sign == 0 { Floating::NAN } else { Floating::infinity(sign) };
+        }
+
+        // Both mantissas are scaled by 3^20, so their product is scaled by 3^40
+        let (mantissa_0, exponent_0) = self.parts();
+        let (mantissa_1, exponent_1) = rhs.parts();
+        Floating::from_parts(
+            mantissa_0 * mantissa_1,
+            exponent_0 + exponent_1 - (MANTISSA_SIZE as isize - 1),
+        )
+    }
+}
+
+impl Div for Floating {
+    type Output = Floating;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        if self.is_nan() || rhs.is_nan() || (self.is_infinite() && rhs.is_infinite()) {
+            return Floating::NAN;
+        } else if rhs.is_infinite() {
+            return Floating::ZERO;
+        }
+
+        let (mantissa_0, exponent_0) = self.normal_parts();
+        let (mantissa_1, exponent_1) = rhs.normal_parts();
+        if self.is_infinite() || mantissa_1 == 0 {
+            // Zero has no sign to flip the result with
+            let sign = self.signum() * if mantissa_1 == 0 { 1 } else { rhs.signum() };
+            return if sign == 0 { Floating::NAN } else { Floating::infinity(sign) };
+        }
+
+        // Twice as many trits in the quotient as the mantissa keeps, and a
+        // last one that's only zero if the division was exact, so rounding
+        // it when normalising can't land on a halfway point it isn't on
+        let quotient = div_sticky(mantissa_0 * 3i128.pow(ALIGN as u32), mantissa_1);
+        Floating::from_parts(quotient, exponent_0 - exponent_1 - ALIGN + MANTISSA_SIZE as isize - 2)
+    }
+}
+
+/// `3 * lhs / rhs` rounded to the nearest integer, then nudged a trit
+/// towards the true quotient, so it's a multiple of 3 only when the division
+/// is exact and sits on the same side of every halfway point
+fn div_sticky(lhs: i128, rhs: i128) -> i128 {
+    let (lhs, rhs) = if rhs < 0 { (-lhs, -rhs) } else { (lhs, rhs) };
+    let nearest = (2 * lhs + rhs).div_euclid(2 * rhs);
+    3 * nearest + (lhs - nearest * rhs).signum()
+}
+
+impl PartialEq for Floating {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Floating {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        // The infinities' exponent is bigger than any other, so they sort
+        // to either end without a case of their own
+        let (mantissa_0, exponent_0) = self.normal_parts();
+        let (mantissa_1, exponent_1) = other.normal_parts();
+
+        let sign = mantissa_0.signum().cmp(&mantissa_1.signum());
+        if sign != Ordering::Equal || mantissa_0 == 0 {
+            return Some(sign);
+        }
+        // Normalised mantissas of the same exponent cover [0.5, 1.5) times
+        // its power of 3, so a bigger exponent always means a bigger value
+        let magnitude = exponent_0.cmp(&exponent_1).then(mantissa_0.abs().cmp(&mantissa_1.abs()));
+        Some(if mantissa_0 > 0 { magnitude } else { magnitude.reverse() })
+    }
+}
+
+/// Packs `mantissa` and `exponent` a balanced trit at a time, for the constants
+const fn encode(mantissa: i128, exponent: isize) -> Floating {
+    let mut bits = Word::ZERO.0;
+    let mut rest = mantissa;
+    let mut i = 0;
+    while i < WORD_LEN {
+        if i == MANTISSA_SIZE {
+            rest = exponent as i128;
+        }
+        // `NOne` is 0b01, `Zero` 0b10 and `POne` 0b11
+        let trit = (rest + 1).rem_euclid(3) - 1;
+        rest = (rest - trit) / 3;
+        bits = bits & !(0b11 << (2 * i)) | ((trit + 2) as u64) << (2 * i);
+        i += 1;
+    }
+    Floating(Ternary(bits))
+}
+
+impl Floating {
+    pub const ZERO: Floating = Floating(Word::ZERO);
+    pub const ONE: Floating = encode(UNIT, 0);
+    /// Every mantissa trit `POne`, under the largest finite exponent
+    pub const MAX: Floating = encode(MAX_MANTISSA, MAX_EXPONENT);
+    pub const MIN: Floating = encode(-MAX_MANTISSA, MAX_EXPONENT);
+    /// The smallest positive normal value, `3^-364`
+    pub const MIN_POSITIVE: Floating = encode(UNIT, MIN_EXPONENT);
+    /// The gap between `1` and the next value up, `3^-20`
+    pub const EPSILON: Floating = encode(UNIT, 1 - MANTISSA_SIZE as isize);
+    pub const INFINITY: Floating = encode(UNIT, SPECIAL_EXPONENT);
+    pub const NEG_INFINITY: Floating = encode(-UNIT, SPECIAL_EXPONENT);
+    pub const NAN: Floating = encode(0, SPECIAL_EXPONENT);
+}
+
+impl Floating {
+    pub fn is_nan(self) -> bool {
+        let (mantissa, exponent) = self.parts();
+        exponent == SPECIAL_EXPONENT && mantissa.abs() != UNIT
+    }
+
+    pub fn is_infinite(self) -> bool {
+        let (mantissa, exponent) = self.parts();
+        exponent == SPECIAL_EXPONENT && mantissa.abs() == UNIT
+    }
+
+    /// Neither infinite nor NaN
+    pub fn is_finite(self) -> bool {
+        self.parts().1 != SPECIAL_EXPONENT
+    }
+
+    /// Which of the kinds of value in the [type docs](Floating) this is.
+    /// Unnormalised encodings count as what they normalise to.
+    pub fn classify(self) -> FpCategory {
+        let (mantissa, exponent) = self.normal_parts();
+        if exponent == SPECIAL_EXPONENT {
+            if mantissa.abs() == UNIT { FpCategory::Infinite } else { FpCategory::Nan }
+        } else if mantissa == 0 {
+            FpCategory::Zero
+        } else if exponent < MIN_EXPONENT {
+            FpCategory::Subnormal
+        } else {
+            FpCategory::Normal
+        }
+    }
+
+    /// `1`, `0` or `-1`, for NaN as well
+    fn signum(self) -> i128 {
+        self.parts().0.signum()
+    }
+
+    fn infinity(sign: i128) -> Floating {
+        if sign > 0 { Floating::INFINITY } else { Floating::NEG_INFINITY }
+    }
+
+    /// The mantissa and exponent as integers, as they're stored
+    fn parts(self) -> (i128, isize) {
+        let (Mantissa(mantissa), Exponent(exponent)) = self.into();
+        (mantissa.into(), exponent.into())
+    }
+
+    /// [`Floating::parts`] with the mantissa shifted up until its top trit
+    /// isn't zero, which takes a subnormal's exponent below `-364`. The
+    /// special values are left as they are.
+    fn normal_parts(self) -> (i128, isize) {
+        let (mut mantissa, mut exponent) = self.parts();
+        if mantissa == 0 {
+            return (0, if exponent == SPECIAL_EXPONENT { exponent } else { 0 });
+        } else if exponent == SPECIAL_EXPONENT {
+            return (mantissa, exponent);
+        }
+        while mantissa.abs() <= MAX_UNNORMAL {
+            mantissa *= 3;
+            exponent -= 1;
+        }
+        (mantissa, exponent)
+    }
+
+    /// Normalises and rounds `mantissa * 3^(exponent - 20)`, going infinite
+    /// past [`Floating::MAX`] and subnormal under [`Floating::MIN_POSITIVE`]
+    pub(crate) fn from_parts(mut mantissa: i128, mut exponent: isize) -> Floating {
+        if mantissa == 0 {
+            return Floating::ZERO;
+        }
+        let exact = mantissa;
+        let mut dropped = 0;
+        while mantissa.abs() > MAX_MANTISSA || (exponent < MIN_EXPONENT && mantissa != 0) {
+            // Dropping the lowest balanced trit rounds to the nearest, and
+            // dropping them one by one rounds the same as all at once
+            let trit = (mantissa + 1).rem_euclid(3) - 1;
+            mantissa = (mantissa - trit) / 3;
+            exponent += 1;
+            dropped += 1;
+        }
+        if mantissa == 0 {
+            return Floating::ZERO;
+        }
+        // That only looked at values of this exponent. Under the bottom one
+        // is the top of the exponent below, a third of a step away rather
+        // than a whole one, and it may be nearer. Halfway goes up.
+        if dropped > 0 && mantissa.abs() == MAX_UNNORMAL + 1 && exponent > MIN_EXPONENT {
+            let step = 3i128.pow(dropped - 1);
+            let below = mantissa.signum() * MAX_MANTISSA;
+            if (exact - below * step).abs() < (mantissa * 3 * step - exact).abs() {
+                mantissa = below;
+                exponent -= 1;
+            }
+        }
+        while mantissa.abs() <= MAX_UNNORMAL && exponent > MIN_EXPONENT {
+            mantissa *= 3;
+            exponent -= 1;
+        }
+
+        if exponent > MAX_EXPONENT {
+            return Floating::infinity(mantissa);
+        }
+        let mantissa = Word::try_from(mantissa).unwrap();
+        let exponent = Word::from(exponent);
+        (Mantissa(mantissa), Exponent(exponent)).into()
+    }
+
+    /// The same value with its top mantissa trit set, unless it's
+    /// subnormal, zero, infinite or NaN
+    pub fn normalize(self) -> Floating {
+        if !self.is_finite() {
+            return self;
+        }
+        let (mantissa, exponent) = self.parts();
+        Floating::from_parts(mantissa, exponent)
+    }
+
+    /// Reinterprets the trits of a word, like [`f64::from_bits`]
+    pub const fn from_word(word: Word) -> Floating {
+        Floating(word)
+    }
+
+    /// The trits as they're stored, like [`f64::to_bits`]
+    pub const fn to_word(self) -> Word {
+        self.0
+    }
+
+    /// The nearest value to an integer. Words have more trits than the
+    /// mantissa, so anything past `3^21 / 2` may round.
+    pub fn from_integer(value: Word) -> Floating {
+        Floating::from_parts(value.into(), MANTISSA_SIZE as isize - 1)
+    }
+
+    /// The nearest integer, or `None` for NaN and anything that rounds
+    /// outside a word. There are no ties to break, since a half can't be
+    /// written in a finite number of trits.
+    pub fn to_integer(self) -> Option<Word> {
+        self.to_scaled(0)
+    }
+
+    /// [`Floating::to_integer`] of `self * 3^scale`
+    pub(crate) fn to_scaled(self, scale: isize) -> Option<Word> {
+        if !self.is_finite() {
+            return None;
+        }
+        let (mut mantissa, exponent) = self.parts();
+        let exponent = exponent + scale;
+        let shift = exponent - MANTISSA_SIZE as isize + 1;
+        if shift > 0 {
+            // Those are at least 3^27 / 2, and checking first keeps the i128 from overflowing
+            if exponent > WORD_LEN as isize {
+                return None;
+            }
+            mantissa *= 3i128.pow(shift as u32);
+        } else {
+            // A mantissa is under 3^21 / 2, so dropping more trits than that leaves zero
+            for _ in 0..(-shift).min(MANTISSA_SIZE as isize + 1) {
+                let trit = (mantissa + 1).rem_euclid(3) - 1;
+                mantissa = (mantissa - trit) / 3;
+            }
+        }
+        Word::try_from(mantissa).ok()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{f64, num::FpCategory, ops::Neg};
+
+    use crate::{
+        floating::{
+            encode, Exponent, Floating, Mantissa, MANTISSA_SIZE, MAX_EXPONENT, MAX_MANTISSA, MAX_UNNORMAL,
+            SPECIAL_EXPONENT, UNIT,
+        },
+        word::Word,
+    };
+
+    #[test]
+    fn test_add() {
+        let mantissa: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent: Word = 1.into();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+
+        let prod = floating + -floating;
+        let double: f64 = prod.into();
+
+        assert_relative_eq!(double, 0.0, epsilon = f64::EPSILON);
+
+        let mantissa_0: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent_0: Word = 1.into();
+        let three: Floating = (Mantissa(mantissa_0), Exponent(exponent_0)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(three),
+            3.0,
+            epsilon = f64::EPSILON
+        );
+
+        let mantissa_1: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent_1: Word = 2.into();
+        let nine: Floating = (Mantissa(mantissa_1), Exponent(exponent_1)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(nine),
+            9.0,
+            epsilon = f64::EPSILON
+        );
+
+        let twelve = three + nine;
+        let double: f64 = twelve.into();
+
+        assert_relative_eq!(double, 12.0, epsilon = f64::EPSILON);
+
+        let sum = three + three;
+        let double: f64 = sum.into();
+
+        assert_relative_eq!(double, 6.0, epsilon = f64::EPSILON);
+
+        // 17
+        let mantissa_0: Word = "0t1T0T00000000000000000".parse().unwrap();
+        let exponent_0: Word = 3.into();
+        let flt_0: Floating = (Mantissa(mantissa_0), Exponent(exponent_0)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(flt_0),
+            17.0,
+            epsilon = f64::EPSILON
+        );
+
+        // 12
+        let mantissa_1: Word = "0t110000000000000000000".parse().unwrap();
+        let exponent_1: Word = 2.into();
+        let flt_1: Floating = (Mantissa(mantissa_1), Exponent(exponent_1)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(flt_1),
+            12.0,
+            epsilon = f64::EPSILON
+        );
+
+        let sum: f64 = (flt_0 + flt_1).into();
+        assert_relative_eq!(sum, 29.0, epsilon = f64::EPSILON);
+
+        let sum: f64 = (flt_1 + flt_0).into();
+        assert_relative_eq!(sum, 29.0, epsilon = f64::EPSILON);
+
+        let sum: f64 = (flt_0 + -flt_1).into();
+        assert_relative_eq!(sum, 5.0, epsilon = f64::EPSILON);
+
+        let sum: f64 = (flt_1 + -flt_0).into();
+        assert_relative_eq!(sum, -5.0, epsilon = f64::EPSILON);
+
+        let sum: f64 = (flt_1 - flt_0).into();
+        assert_relative_eq!(sum, -5.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_mul() {
+        let mantissa_0: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent_0: Word = 1.into();
+        let three: Floating = (Mantissa(mantissa_0), Exponent(exponent_0)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(three),
+            3.0,
+            epsilon = f64::EPSILON
+        );
+
+        let mantissa_1: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent_1: Word = 2.into();
+        let nine: Floating = (Mantissa(mantissa_1), Exponent(exponent_1)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(nine),
+            9.0,
+            epsilon = f64::EPSILON
+        );
+
+        let flt: f64 = (three * three).into();
+        assert_relative_eq!(flt, 9.0, epsilon = f64::EPSILON);
+        let flt: f64 = (nine * nine).into();
+        assert_relative_eq!(flt, 81.0, epsilon = f64::EPSILON);
+        let flt: f64 = (nine * three).into();
+        assert_relative_eq!(flt, 27.0, epsilon = f64::EPSILON);
+
+        // 12
+        let mantissa_0: Word = "0t110000000000000000000".parse().unwrap();
+        let exponent_0: Word = 2.into();
+        let twelve: Floating = (Mantissa(mantissa_0), Exponent(exponent_0)).into();
+        assert_relative_eq!(
+            <Floating as Into<f64>>::into(twelve),
+            12.0,
+            epsilon = f64::EPSILON
+        );
+
+        let mantissa: Word = "0t10011T111T000T0110TTT".parse().unwrap();
+        let exponent: Word = (1).into();
+        let pi: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let double: f64 = pi.into();
+        assert_relative_eq!(double, f64::consts::PI, epsilon = 0.00000001);
+
+        let pi_mul_3 = pi * three;
+        let val: f64 = pi_mul_3.into();
+        assert_relative_eq!(val, f64::consts::PI * 3., epsilon = 0.00000001);
+
+        let pi_mul_9 = pi * nine;
+        let val: f64 = pi_mul_9.into();
+        // My PI isn't fully accurate yet so unfortunately this needs slightly
+        // less accuracy :(
+        assert_relative_eq!(val, f64::consts::PI * 9., epsilon = 0.0000001);
+
+        let pi_mul_12 = pi * twelve;
+        let val: f64 = pi_mul_12.into();
+        // My PI isn't fully accurate yet so unfortunately this needs slightly
+        // less accuracy :(
+        assert_relative_eq!(val, f64::consts::PI * 12., epsilon = 0.0000001);
+
+        let val: f64 = (pi * pi).into();
+        assert_relative_eq!(val, f64::consts::PI * f64::consts::PI, epsilon = 0.0000001);
+    }
+
+    #[test]
+    fn convert() {
+        let mantissa: Mantissa = Mantissa(3141592.into());
+        let exponent: Exponent = Exponent(20.into());
+        let float: Floating = (mantissa, exponent).into();
+        let tuple: (Mantissa, Exponent) = float.into();
+        assert_eq!(tuple, (mantissa, exponent));
+    }
+
+    #[test]
+    fn conversion() {
+        let mantissa: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent: Word = "0t000000".parse().unwrap();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let double: f64 = floating.into();
+        assert_relative_eq!(double, 1.0, epsilon = f64::EPSILON);
+
+        let mantissa: Word = "0t100000000000000000000".parse().unwrap();
+        let exponent: Word = "0t00000T".parse().unwrap();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let double: f64 = floating.into();
+        assert_relative_eq!(double, 1.0 / 3.0, epsilon = f64::EPSILON);
+
+        let mantissa: Word = "0tT00000000000000000000".parse().unwrap();
+        let exponent: Word = "0t00000T".parse().unwrap();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let double: f64 = floating.into();
+        assert_relative_eq!(double, -1.0 / 3.0, epsilon = f64::EPSILON);
+
+        let mantissa: Word = "0tT00000000000000000000".parse().unwrap();
+        let exponent: Word = "0t0000T0".parse().unwrap();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let double: f64 = floating.into();
+        assert_relative_eq!(double, -1.0 / 27.0, epsilon = f64::EPSILON);
+
+        let mantissa: Word = "0tT00000000000000000000".parse().unwrap();
+        let exponent: Word = "0t0000T0".parse().unwrap();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let floating: Floating = -floating;
+        let double: f64 = floating.into();
+        assert_relative_eq!(double, 1.0 / 27.0, epsilon = f64::EPSILON);
+
+        let mantissa: Word = "0t10011T111T000T0110TTT".parse().unwrap();
+        let exponent: Word = (1).into();
+        let floating: Floating = (Mantissa(mantissa), Exponent(exponent)).into();
+        let double: f64 = floating.into();
+        assert_relative_eq!(double, std::f64::consts::PI, epsilon = 0.00000001);
+    }
+
+    #[test]
+    fn leading_trailing_zeroes() {
+        assert_eq!(27, Mantissa(Word::ZERO).leading_zeroes());
+        assert_eq!(26, Mantissa(Word::PONE).leading_zeroes());
+        assert_eq!(0, Mantissa(Word::MIN).leading_zeroes());
+        let mantissa: Word = "0tTTT0110T000T111T11001".parse().unwrap();
+        assert_eq!(6, Mantissa(mantissa).leading_zeroes());
+        assert_eq!(0, Mantissa(mantissa).trailing_zeroes());
+        let mantissa: Word = "0t000000000000000010000000000".parse().unwrap();
+        assert_eq!(16, Mantissa(mantissa).leading_zeroes());
+        assert_eq!(10, Mantissa(mantissa).trailing_zeroes());
+        let exponent: Word = "0t100000".parse().unwrap();
+        assert_eq!(0, Exponent(exponent).leading_zeroes());
+        let exponent: Word = "0t100".parse().unwrap();
+        assert_eq!(3, Exponent(exponent).leading_zeroes());
+    }
+
+    fn float(value: f64) -> Floating {
+        Floating::from(value)
+    }
+
+    #[test]
+    fn test_div() {
+        let val: f64 = (float(12.0) / float(3.0)).into();
+        assert_relative_eq!(val, 4.0, epsilon = f64::EPSILON);
+        let val: f64 = (float(1.0) / float(3.0)).into();
+        assert_relative_eq!(val, 1.0 / 3.0, epsilon = f64::EPSILON);
+        let val: f64 = (float(-1.0) / float(7.0)).into();
+        assert_relative_eq!(val, -1.0 / 7.0, max_relative = 3e-10);
+        let val: f64 = (float(f64::consts::PI) / float(-f64::consts::E)).into();
+        assert_relative_eq!(val, -f64::consts::PI / f64::consts::E, max_relative = 1e-9);
+
+        assert_eq!(Floating::ZERO / float(2.0), Floating::ZERO);
+        for value in [1.0, -5.0, 1e-20, 123456.789] {
+            assert_eq!(float(value) / float(value), Floating::ONE);
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        let values = [
+            f64::NEG_INFINITY, -1e30, -12.0, -1.4, -0.6, -1e-30, -1e-180, 0.0, 1e-180, 1e-30, 0.6, 1.4, 1.6,
+            12.0, 1e30, f64::INFINITY,
+        ];
+        for a in values {
+            for b in values {
+                assert_eq!(float(a).partial_cmp(&float(b)), a.partial_cmp(&b), "{a} {b}");
+            }
+        }
+        // The same value, normalised or not
+        let unnormal: Floating = (Mantissa(1.into()), Exponent(20.into())).into();
+        assert_eq!(unnormal, Floating::ONE);
+        assert_eq!(unnormal.normalize().0, Floating::ONE.0);
+        assert!(Floating::MIN < Floating::MIN_POSITIVE.neg() && Floating::MIN_POSITIVE < Floating::MAX);
+        assert!(Floating::NEG_INFINITY < Floating::MIN && Floating::MAX < Floating::INFINITY);
+    }
+
+    #[test]
+    fn f64_round_trip() {
+        for value in [1.0, -1.0, 0.5, 1.5, 2.0, 1.0 / 3.0, 0.1, -123.456, 6.02e23, 1.6e-19, 1e170, -1e-170] {
+            let floating = float(value);
+            assert_relative_eq!(f64::from(floating), value, max_relative = 3e-10);
+            // An f64 has the precision to spare, so converting back changes nothing
+            assert_eq!(float(f64::from(floating)).0, floating.0);
+        }
+        let max = f64::from(Floating::MAX);
+        assert_eq!(float(max).0, Floating::MAX.0);
+        assert_eq!(float(-max).0, Floating::MIN.0);
+        assert_eq!(float(max * 2.0).0, Floating::INFINITY.0);
+        assert_eq!(float(f64::NEG_INFINITY).0, Floating::NEG_INFINITY.0);
+        assert_eq!(f64::from(Floating::INFINITY), f64::INFINITY);
+        assert_eq!(f64::from(Floating::NEG_INFINITY), f64::NEG_INFINITY);
+        assert!(float(f64::NAN).is_nan());
+        assert!(f64::from(Floating::NAN).is_nan());
+        assert_eq!(float(1e-300), Floating::ZERO);
+        assert_eq!(f64::from(Floating::EPSILON), 3f64.powi(-20));
+        assert_eq!(f64::from(Floating::MIN_POSITIVE), 3f64.powi(-364));
+        // Subnormals too, down to a single trit
+        for k in 365..=384 {
+            let floating = float(-3f64.powi(-k));
+            assert_eq!(floating.classify(), FpCategory::Subnormal);
+            assert_relative_eq!(f64::from(floating), -3f64.powi(-k), max_relative = 1e-14);
+            assert_eq!(float(f64::from(floating)).0, floating.0);
+        }
+    }
+
+    #[test]
+    fn binade_edges() {
+        // 1.5 is 0.111...1 in balanced ternary, a third of an ulp past the
+        // top of its exponent and two thirds short of the next one up
+        for k in -300..300 {
+            let top = encode(MAX_MANTISSA, k);
+            assert_eq!(float(1.5 * 3f64.powi(k as i32)).0, top.0, "1.5 * 3^{k}");
+            assert_eq!(float(-0.5 * 3f64.powi(k as i32 + 1)).0, (-top).0, "-0.5 * 3^{}", k + 1);
+        }
+        assert_eq!((Floating::ONE / float(2.0 / 3.0)).0, float(1.5).0);
+        assert_eq!((float(4.5) / float(3.0)).0, float(1.5).0);
+        // Halfway between the two is MAX_MANTISSA + 1, and halfway goes up
+        assert_eq!(Floating::from_parts(3 * MAX_MANTISSA + 2, -1).0, encode(MAX_MANTISSA, 0).0);
+        assert_eq!(Floating::from_parts(3 * MAX_MANTISSA + 3, -1).0, encode(MAX_UNNORMAL + 1, 1).0);
+        assert_eq!(Floating::from_parts(-3 * MAX_MANTISSA - 4, -1).0, encode(-MAX_UNNORMAL - 1, 1).0);
+        assert_eq!(float(1.5 + 3f64.powi(-20)).0, encode(MAX_UNNORMAL + 1, 1).0);
+    }
+
+    #[test]
+    fn rounding() {
+        // 1 + 3^-20 is the next value up, half of that rounds to the nearest
+        let next = Floating::ONE + Floating::EPSILON;
+        assert!(next > Floating::ONE);
+        let third = Floating::EPSILON / float(3.0);
+        assert_eq!(Floating::ONE + third, Floating::ONE);
+        assert_eq!(Floating::ONE + third + third, Floating::ONE);
+        assert_eq!(Floating::ONE + (third + third), next);
+        assert_eq!(Floating::ONE - Floating::ONE, Floating::ZERO);
+        assert_eq!((Floating::ONE + Floating::EPSILON) - Floating::ONE, Floating::EPSILON);
+        // Nothing is lost to the rounding of large operands
+        let big = float(1e15);
+        assert_eq!(big + Floating::ONE - big, Floating::ZERO);
+        assert_eq!(big + float(1e-15), big);
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!((Floating::MAX + Floating::MAX).0, Floating::INFINITY.0);
+        assert_eq!((Floating::MAX * float(-3.0)).0, Floating::NEG_INFINITY.0);
+        assert_eq!((Floating::MIN - Floating::MAX).0, Floating::NEG_INFINITY.0);
+        assert_eq!((Floating::MAX / Floating::EPSILON).0, Floating::INFINITY.0);
+        // The next value past MAX would be 2 ulps on, so anything short of
+        // 1 ulp rounds back down to it, and 1 ulp is halfway and goes up
+        let ulp = encode(UNIT, MAX_EXPONENT - MANTISSA_SIZE as isize + 1);
+        let third_ulp = encode(UNIT, MAX_EXPONENT - MANTISSA_SIZE as isize);
+        assert_eq!((Floating::MAX + third_ulp).0, Floating::MAX.0);
+        assert_eq!((Floating::MAX + third_ulp + third_ulp).0, Floating::MAX.0);
+        assert_eq!((Floating::MAX + (third_ulp + third_ulp)).0, Floating::MAX.0);
+        assert_eq!((Floating::MIN - (third_ulp + third_ulp)).0, Floating::MIN.0);
+        let almost = ulp - encode(UNIT, MAX_EXPONENT - MANTISSA_SIZE as isize - 9);
+        assert_eq!((Floating::MAX + almost).0, Floating::MAX.0);
+        assert_eq!((Floating::MIN - almost).0, Floating::MIN.0);
+        assert_eq!((Floating::MAX + ulp).0, Floating::INFINITY.0);
+        assert_eq!((Floating::MIN - ulp).0, Floating::NEG_INFINITY.0);
+        assert_eq!(float(f64::from(Floating::MAX) * (1.0 + 0.9 / 3f64.powi(20) / 1.5)).0, Floating::MAX.0);
+        assert_eq!(float(f64::from(Floating::MAX) * (1.0 + 1.1 / 3f64.powi(20) / 1.5)).0, Floating::INFINITY.0);
+        assert!((Floating::MAX + Floating::MAX).is_infinite());
+        assert!(Floating::MAX.is_finite());
+    }
+
+    #[test]
+    fn underflow() {
+        let smallest = float(3f64.powi(-384));
+        assert_eq!(smallest.classify(), FpCategory::Subnormal);
+        assert_eq!(Floating::MIN_POSITIVE.classify(), FpCategory::Normal);
+        assert_eq!((Floating::MIN_POSITIVE / float(3.0)).classify(), FpCategory::Subnormal);
+        assert_relative_eq!(f64::from(Floating::MIN_POSITIVE / float(3.0)), 3f64.powi(-365), max_relative = 1e-14);
+        assert_eq!((Floating::MIN_POSITIVE / float(3.0) * float(3.0)).0, Floating::MIN_POSITIVE.0);
+        assert_eq!(smallest / float(3.0), Floating::ZERO);
+        assert_eq!(smallest / float(2.5), Floating::ZERO);
+        assert_eq!((smallest / float(1.5)).0, smallest.0);
+        assert_eq!(Floating::MIN_POSITIVE * Floating::MIN_POSITIVE, Floating::ZERO);
+
+        // Values that differ never subtract to zero
+        let next = Floating::MIN_POSITIVE * (Floating::ONE + Floating::EPSILON);
+        assert_eq!((next - Floating::MIN_POSITIVE).0, smallest.0);
+        assert_eq!((Floating::MIN_POSITIVE - next).0, (-smallest).0);
+        // Subnormals add up exactly, and into normals
+        let sum = (0..3).fold(Floating::ZERO, |sum, _| sum + Floating::MIN_POSITIVE / float(3.0));
+        assert_eq!(sum.0, Floating::MIN_POSITIVE.0);
+        assert_eq!(smallest.normalize().0, smallest.0);
+    }
+
+    #[test]
+    fn special_values() {
+        let (inf, neg_inf, nan) = (Floating::INFINITY, Floating::NEG_INFINITY, Floating::NAN);
+        assert_eq!(inf.classify(), FpCategory::Infinite);
+        assert_eq!(neg_inf.classify(), FpCategory::Infinite);
+        assert_eq!(nan.classify(), FpCategory::Nan);
+        assert_eq!(Floating::ZERO.classify(), FpCategory::Zero);
+        assert_eq!(Floating::ONE.classify(), FpCategory::Normal);
+        assert!(nan.is_nan() && !nan.is_infinite() && !nan.is_finite());
+        assert!(inf.is_infinite() && !inf.is_nan() && !inf.is_finite());
+        // Any other mantissa under the special exponent is NaN too
+        assert!(encode(UNIT + 1, SPECIAL_EXPONENT).is_nan());
+        assert!(encode(-1, SPECIAL_EXPONENT).is_nan());
+
+        assert_eq!((-inf).0, neg_inf.0);
+        assert!((-nan).is_nan());
+        assert!(nan != nan);
+        assert_eq!(nan.partial_cmp(&Floating::ONE), None);
+        assert_eq!(inf, inf);
+
+        let one = Floating::ONE;
+        for (result, expected) in [
+            (inf + one, inf),
+            (neg_inf - one, neg_inf),
+            (inf + inf, inf),
+            (inf - neg_inf, inf),
+            (inf * float(-2.0), neg_inf),
+            (neg_inf * neg_inf, inf),
+            (one / Floating::ZERO, inf),
+            (-one / Floating::ZERO, neg_inf),
+            (inf / Floating::ZERO, inf),
+            (neg_inf / float(-2.0), inf),
+            (one / inf, Floating::ZERO),
+            (Floating::MAX / neg_inf, Floating::ZERO),
+        ] {
+            assert_eq!(result.0, expected.0);
+        }
+        for result in [
+            inf - inf,
+            inf + neg_inf,
+            Floating::ZERO * inf,
+            neg_inf * Floating::ZERO,
+            Floating::ZERO / Floating::ZERO,
+            inf / neg_inf,
+            nan + one,
+            one - nan,
+            nan * Floating::ZERO,
+            nan / inf,
+            one / nan,
+        ] {
+            assert!(result.is_nan());
+        }
+        assert!(nan.normalize().is_nan());
+        assert_eq!(inf.normalize().0, inf.0);
+    }
+
+    #[test]
+    fn integers() {
+        let max: isize = Word::MAX.into();
+        for value in [0, 1, -1, 17, -364, 3isize.pow(20), max / 3, max, -max] {
+            let float = Floating::from_integer(value.into());
+            assert_relative_eq!(f64::from(float), value as f64, max_relative = 1e-10);
+            // Everything up to 3^21 / 2 is exact
+            if value.unsigned_abs() <= 5230176601 {
+                assert_eq!(float.to_integer(), Some(value.into()));
+            }
+        }
+        // The low trits of big words round away
+        let big = Floating::from_integer((3isize.pow(22) + 1).into());
+        assert_eq!(big.to_integer(), Some(3isize.pow(22).into()));
+
+        for (value, expected) in [(0.4, 0), (0.6, 1), (-1.4, -1), (-1.6, -2), (1e-100, 0), (12.5, 13)] {
+            assert_eq!(float(value).to_integer(), Some(expected.into()));
+        }
+        // Word::MAX is all POne, so rounding it to 21 trits goes down
+        let edge = Floating::from_integer(Word::MAX);
+        assert_eq!(edge.to_integer(), Some((max - 364).into()));
+        assert_eq!((-edge).to_integer(), Some((364 - max).into()));
+        assert!(float(3.8e12).to_integer().is_some());
+        assert_eq!(float(3.82e12).to_integer(), None);
+        assert_eq!(float(-1e14).to_integer(), None);
+        assert_eq!(Floating::MAX.to_integer(), None);
+        assert_eq!(Floating::INFINITY.to_integer(), None);
+        assert_eq!(Floating::NAN.to_integer(), None);
+
+        let word: Word = "0t1T0T00000000000000000".parse().unwrap();
+        assert_eq!(Floating::from_word(word).to_word(), word);
+        assert_eq!(Floating::ONE.to_word(), Floating::from_integer(1.into()).to_word());
+    }
+}
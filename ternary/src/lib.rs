@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 #![allow(clippy::assign_op_pattern)]
-#![feature(ascii_char, stmt_expr_attributes, test)]
+#![feature(stmt_expr_attributes, test)]
 
 #[macro_use]
 extern crate static_assertions;
@@ -9,13 +9,24 @@ extern crate static_assertions;
 extern crate approx;
 
 pub mod trits;
+pub mod tribble;
+pub mod ternary;
 pub mod tryte;
 pub mod word;
+mod swar;
 pub mod errors;
+pub mod logic;
+pub mod radix;
+pub mod tritvec;
+pub mod bitfield;
+pub mod rounding;
 pub mod prelude;
-// pub mod floating;
-#[cfg(feature = "const_size")]
-pub mod varsize;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(test)]
+mod proptests;
+pub mod floating;
+pub mod fixed;
 
 //== Consts ==//
 const TRYTE_BIT_LEN: usize = 18;
@@ -0,0 +1,294 @@
+//! Balanced ternary logic gates.
+//!
+//! A [`Unary`] gate is a truth table of three outputs, one per input, so
+//! there are 27 of them; [`Unary::from_index`] numbers them all. A
+//! [`Binary`] gate is a 3x3 table, and any table works. Both apply to
+//! single trits or, trit by trit, to a whole [`Ternary`] at once without
+//! unpacking it.
+//!
+//! Tables are indexed by the input, `NOne` first, so `table[0]` is the
+//! output for `NOne` and `table[2]` the output for `POne`.
+
+use std::ops::BitXor;
+
+use crate::{
+    swar::{self, Packed},
+    ternary::{Storage, Ternary, Width},
+    trits::Trit,
+};
+
+use Trit::{NOne as T, POne as P, Zero as O};
+
+type Repr<const N: usize> = <Width<N> as Storage>::Repr;
+
+const TRITS: [Trit; 3] = [T, O, P];
+
+/// Position of a trit in a truth table
+const fn idx(trit: Trit) -> usize {
+    match trit {
+        T => 0,
+        O => 1,
+        P => 2,
+    }
+}
+
+/// One mask per input value, `NOne` first, with the low bit of every trit
+/// that holds it set
+fn split<const N: usize>(value: Ternary<N>) -> [Repr<N>; 3]
+where
+    Width<N>: Storage,
+{
+    let (pos, neg) = swar::planes(value.num(), N);
+    [neg, swar::lows::<Repr<N>>(N) & !(pos | neg), pos]
+}
+
+/// Builds a value from the masks of where the output is `POne` and `NOne`
+fn join<const N: usize>(pos: Repr<N>, neg: Repr<N>) -> Ternary<N>
+where
+    Width<N>: Storage,
+{
+    Ternary(swar::from_planes(pos, neg, N))
+}
+
+/// One of the 27 functions from a trit to a trit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Unary(pub [Trit; 3]);
+
+impl Unary {
+    pub const IDENTITY: Unary = Unary([T, O, P]);
+    pub const NEG: Unary = Unary([P, O, T]);
+    /// Cycles up, `NOne` to `Zero` to `POne` and back around
+    pub const INC: Unary = Unary([O, P, T]);
+    /// Cycles down, `POne` to `Zero` to `NOne` and back around
+    pub const DEC: Unary = Unary([P, T, O]);
+    /// Swaps `Zero` and `POne`
+    pub const SWAP_ZERO_POS: Unary = Unary([T, P, O]);
+    /// Swaps `NOne` and `Zero`
+    pub const SWAP_NEG_ZERO: Unary = Unary([O, T, P]);
+    /// Adds one, stopping at `POne`
+    pub const CLAMP_UP: Unary = Unary([O, P, P]);
+    /// Takes away one, stopping at `NOne`
+    pub const CLAMP_DOWN: Unary = Unary([T, T, O]);
+    pub const IS_NEG: Unary = Unary([P, T, T]);
+    pub const IS_ZERO: Unary = Unary([T, P, T]);
+    pub const IS_POS: Unary = Unary([T, T, P]);
+    pub const ABS: Unary = Unary([P, O, P]);
+
+    /// The gate whose outputs for `NOne`, `Zero` and `POne`, each plus one,
+    /// are the base 3 digits of `index` from most to least significant.
+    /// So `0` is always `NOne`, [`Unary::IDENTITY`] is `5` and `26` is always `POne`.
+    pub const fn from_index(index: u8) -> Option<Unary> {
+        if index >= 27 {
+            return None;
+        }
+        let index = index as usize;
+        Some(Unary([TRITS[index / 9], TRITS[index / 3 % 3], TRITS[index % 3]]))
+    }
+
+    /// Inverse of [`Unary::from_index`]
+    pub const fn index(self) -> u8 {
+        let [n, z, p] = self.0;
+        (idx(n) * 9 + idx(z) * 3 + idx(p)) as u8
+    }
+
+    pub const fn apply(self, trit: Trit) -> Trit {
+        self.0[idx(trit)]
+    }
+
+    /// Applies the gate to every trit of `value`
+    pub fn tritwise<const N: usize>(self, value: Ternary<N>) -> Ternary<N>
+    where
+        Width<N>: Storage,
+    {
+        let (mut pos, mut neg) = (Repr::<N>::ZERO, Repr::<N>::ZERO);
+        for (input, output) in split(value).into_iter().zip(self.0) {
+            match output {
+                P => pos = pos | input,
+                T => neg = neg | input,
+                O => {}
+            }
+        }
+        join(pos, neg)
+    }
+}
+
+/// Any function of two trits, as `table[lhs][rhs]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Binary(pub [[Trit; 3]; 3]);
+
+impl Binary {
+    /// Minimum, the same as `&`
+    pub const AND: Binary = Binary([[T, T, T], [T, O, O], [T, O, P]]);
+    /// Maximum, the same as `|`
+    pub const OR: Binary = Binary([[T, O, P], [O, O, P], [P, P, P]]);
+    /// Sum modulo 3, the ternary XOR and the same as `^`
+    pub const SUM: Binary = Binary([[P, T, O], [T, O, P], [O, P, T]]);
+    pub const MUL: Binary = Binary([[P, O, T], [O, O, O], [T, O, P]]);
+    /// The value both agree on, `Zero` if they don't
+    pub const CONSENSUS: Binary = Binary([[T, O, O], [O, O, O], [O, O, P]]);
+    /// Whichever isn't `Zero`, or `Zero` if they contradict each other
+    pub const ANY: Binary = Binary([[T, T, O], [T, O, P], [O, P, P]]);
+    /// The sign of `lhs - rhs`
+    pub const CMP: Binary = Binary([[O, T, T], [P, O, T], [P, P, O]]);
+
+    pub fn from_fn(f: impl Fn(Trit, Trit) -> Trit) -> Binary {
+        Binary(TRITS.map(|lhs| TRITS.map(|rhs| f(lhs, rhs))))
+    }
+
+    pub const fn apply(self, lhs: Trit, rhs: Trit) -> Trit {
+        self.0[idx(lhs)][idx(rhs)]
+    }
+
+    /// Applies the gate to each pair of trits in the same position
+    pub fn tritwise<const N: usize>(self, lhs: Ternary<N>, rhs: Ternary<N>) -> Ternary<N>
+    where
+        Width<N>: Storage,
+    {
+        let (lhs, rhs) = (split(lhs), split(rhs));
+        let (mut pos, mut neg) = (Repr::<N>::ZERO, Repr::<N>::ZERO);
+        for (l, row) in lhs.into_iter().zip(self.0) {
+            for (r, output) in rhs.into_iter().zip(row) {
+                match output {
+                    P => pos = pos | (l & r),
+                    T => neg = neg | (l & r),
+                    O => {}
+                }
+            }
+        }
+        join(pos, neg)
+    }
+}
+
+impl Trit {
+    /// Sum modulo 3, see [`Binary::SUM`]
+    pub const fn sum(self, rhs: Trit) -> Trit {
+        Binary::SUM.apply(self, rhs)
+    }
+
+    /// See [`Binary::CONSENSUS`]
+    pub const fn consensus(self, rhs: Trit) -> Trit {
+        Binary::CONSENSUS.apply(self, rhs)
+    }
+
+    /// See [`Binary::ANY`]
+    pub const fn any(self, rhs: Trit) -> Trit {
+        Binary::ANY.apply(self, rhs)
+    }
+
+    /// Cycles up, `POne` wraps around to `NOne`
+    pub const fn inc(self) -> Trit {
+        Unary::INC.apply(self)
+    }
+
+    /// Cycles down, `NOne` wraps around to `POne`
+    pub const fn dec(self) -> Trit {
+        Unary::DEC.apply(self)
+    }
+}
+
+impl BitXor for Trit {
+    type Output = Trit;
+
+    fn bitxor(self, rhs: Trit) -> Self::Output {
+        self.sum(rhs)
+    }
+}
+
+impl<const N: usize> Ternary<N>
+where
+    Width<N>: Storage,
+{
+    pub fn consensus(self, rhs: Self) -> Self {
+        Binary::CONSENSUS.tritwise(self, rhs)
+    }
+
+    pub fn any(self, rhs: Self) -> Self {
+        Binary::ANY.tritwise(self, rhs)
+    }
+
+    /// Cycles every trit up
+    pub fn inc(self) -> Self {
+        Unary::INC.tritwise(self)
+    }
+
+    /// Cycles every trit down
+    pub fn dec(self) -> Self {
+        Unary::DEC.tritwise(self)
+    }
+}
+
+/// Tritwise sum modulo 3, no carries
+impl<const N: usize> BitXor for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Binary::SUM.tritwise(self, rhs)
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    type Small = Ternary<4>;
+
+    fn all() -> impl Iterator<Item = Small> + Clone {
+        (-40isize..=40).map(Small::from)
+    }
+
+    #[test]
+    fn unary_index() {
+        for index in 0..27 {
+            assert_eq!(Unary::from_index(index).unwrap().index(), index);
+        }
+        assert_eq!(Unary::from_index(27), None);
+        assert_eq!(Unary::from_index(5), Some(Unary::IDENTITY));
+        for (trit, inc) in [(T, O), (O, P), (P, T)] {
+            assert_eq!(trit.inc(), inc);
+            assert_eq!(inc.dec(), trit);
+            assert_eq!(Unary::NEG.apply(trit), -trit);
+        }
+    }
+
+    #[test]
+    fn tables() {
+        for a in TRITS {
+            for b in TRITS {
+                assert_eq!(Binary::AND.apply(a, b), a & b);
+                assert_eq!(Binary::OR.apply(a, b), a | b);
+                assert_eq!(Binary::MUL.apply(a, b), a * b);
+                assert_eq!(a ^ b, (a + b).result, "{a} ^ {b}");
+                assert_eq!(isize::from(Binary::CMP.apply(a, b)), (isize::from(a) - isize::from(b)).signum());
+                assert_eq!(a.consensus(b), if a == b { a } else { O });
+            }
+            assert_eq!(a.any(O), a);
+            assert_eq!(a.any(a), a);
+            assert_eq!(a.any(-a), O);
+        }
+        assert_eq!(Binary::from_fn(|a, b| a * b), Binary::MUL);
+    }
+
+    #[test]
+    fn tritwise() {
+        let gates = [Binary::AND, Binary::OR, Binary::SUM, Binary::MUL, Binary::CONSENSUS, Binary::ANY, Binary::CMP];
+        for x in all() {
+            for index in 0..27 {
+                let gate = Unary::from_index(index).unwrap();
+                let expected: [Trit; 4] = <[Trit; 4]>::from(x).map(|t| gate.apply(t));
+                assert_eq!(gate.tritwise(x), expected.into());
+            }
+            for y in all() {
+                for gate in gates {
+                    let (a, b) = (<[Trit; 4]>::from(x), <[Trit; 4]>::from(y));
+                    let expected: [Trit; 4] = std::array::from_fn(|i| gate.apply(a[i], b[i]));
+                    assert_eq!(gate.tritwise(x, y), expected.into());
+                }
+                assert_eq!(x & y, Binary::AND.tritwise(x, y));
+                assert_eq!(x | y, Binary::OR.tritwise(x, y));
+            }
+        }
+    }
+}
@@ -1,4 +1,9 @@
-pub use crate::errors::DivByZeroError;
+pub use crate::fixed::Fixed;
+pub use crate::errors::{DivByZeroError, OutOfRangeError, ParseTernaryError};
+pub use crate::rounding::Rounding;
+pub use crate::ternary::Ternary;
+pub use crate::tribble::Tribble;
 pub use crate::trits::Trit;
 pub use crate::tryte::Tryte;
-pub use crate::word::Word;
+pub use crate::word::{DoubleWord, Word};
+pub use crate::tritvec::{TritSlice, TritVec};
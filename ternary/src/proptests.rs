@@ -0,0 +1,216 @@
+//! Every operation on [`Word`] and [`Tryte`] checked against plain integer
+//! arithmetic on `i128`, wrapped into the balanced range the same way the
+//! packed trits wrap.
+
+use std::cmp::Ordering;
+
+use proptest::prelude::*;
+
+use crate::{
+    rounding::Rounding,
+    ternary::{Storage, Ternary, Width},
+    trits::Trit,
+    tryte::Tryte,
+    word::Word,
+};
+
+const fn pow3(n: usize) -> i128 {
+    3i128.pow(n as u32)
+}
+
+/// The largest value of `n` trits, all of them `POne`
+const fn max(n: usize) -> i128 {
+    (pow3(n) - 1) / 2
+}
+
+/// What `n` trits of `value` hold once everything above them is dropped
+fn wrap(value: i128, n: usize) -> i128 {
+    (value + max(n)).rem_euclid(pow3(n)) - max(n)
+}
+
+/// Balanced digits, least significant first
+fn digits(mut value: i128, n: usize) -> Vec<i128> {
+    (0..n)
+        .map(|_| {
+            let digit = (value + 1).rem_euclid(3) - 1;
+            value = (value - digit) / 3;
+            digit
+        })
+        .collect()
+}
+
+fn undigits(digits: &[i128]) -> i128 {
+    digits.iter().rev().fold(0, |acc, digit| acc * 3 + digit)
+}
+
+fn model<const N: usize>(value: Ternary<N>) -> i128
+where
+    Width<N>: Storage,
+{
+    i128::from(value)
+}
+
+fn ternary<const N: usize>(value: i128) -> Ternary<N>
+where
+    Width<N>: Storage,
+{
+    Ternary::try_from(value).unwrap()
+}
+
+/// `MIN`, `MAX`, `0`, `±1`, and runs of `POne`, `NOne` and `±3^k` that carry
+/// or borrow through every trit above them
+fn edges(n: usize) -> Vec<i128> {
+    let mut edges = vec![0, 1, -1, max(n), -max(n)];
+    for k in 0..n {
+        for value in [pow3(k), max(k), max(k) + 1, pow3(k) - 1] {
+            edges.extend([value, -value]);
+        }
+    }
+    edges
+}
+
+fn value(n: usize) -> impl Strategy<Value = i128> {
+    prop_oneof![
+        proptest::sample::select(edges(n)),
+        -max(n)..=max(n),
+    ]
+}
+
+fn check_binary<const N: usize>(a: i128, b: i128) -> Result<(), TestCaseError>
+where
+    Width<N>: Storage,
+{
+    let (x, y) = (ternary::<N>(a), ternary::<N>(b));
+
+    prop_assert_eq!(model(x + y), wrap(a + b, N));
+    prop_assert_eq!(model(x - y), wrap(a - b, N));
+    prop_assert_eq!(model(x * y), wrap(a * b, N));
+
+    let (sum, carry) = x.overflowing_add(y);
+    prop_assert_eq!(model(sum) + isize::from(carry) as i128 * pow3(N), a + b);
+    let (diff, borrow) = x.overflowing_sub(y);
+    prop_assert_eq!(model(diff) + isize::from(borrow) as i128 * pow3(N), a - b);
+    let fits = |v: i128| (v.abs() <= max(N)).then(|| ternary::<N>(v));
+    prop_assert_eq!(x.checked_add(y), fits(a + b));
+    prop_assert_eq!(x.checked_sub(y), fits(a - b));
+    prop_assert_eq!(x.checked_mul(y), fits(a * b));
+    prop_assert_eq!(model(x.saturating_mul(y)), (a * b).clamp(-max(N), max(N)));
+
+    if b == 0 {
+        prop_assert!((x / y).is_err());
+        prop_assert!((x % y).is_err());
+    } else {
+        prop_assert_eq!(model((x / y).unwrap()), wrap(a.div_euclid(b), N));
+        prop_assert_eq!(model((x % y).unwrap()), a.rem_euclid(b));
+
+        let (quot, rem) = x.div_rem(y, Rounding::Truncate).unwrap();
+        prop_assert_eq!(model(quot), wrap(a / b, N));
+        prop_assert_eq!(model(rem), a % b);
+
+        let (quot, rem) = x.div_rem(y, Rounding::Nearest).unwrap();
+        let (quot, rem) = (model(quot), model(rem));
+        prop_assert!(2 * rem.abs() <= b.abs(), "{} rem {} of {}", a, rem, b);
+        prop_assert_eq!(wrap(quot * b + rem, N), a);
+    }
+
+    let (da, db) = (digits(a, N), digits(b, N));
+    let and: Vec<_> = da.iter().zip(&db).map(|(l, r)| *l.min(r)).collect();
+    let or: Vec<_> = da.iter().zip(&db).map(|(l, r)| *l.max(r)).collect();
+    prop_assert_eq!(model(x & y), undigits(&and));
+    prop_assert_eq!(model(x | y), undigits(&or));
+
+    prop_assert_eq!(x.cmp(&y), a.cmp(&b));
+    prop_assert_eq!(x == y, a == b);
+    Ok(())
+}
+
+fn check_unary<const N: usize>(a: i128) -> Result<(), TestCaseError>
+where
+    Width<N>: Storage,
+{
+    let x = ternary::<N>(a);
+
+    prop_assert_eq!(model(-x), -a);
+    prop_assert_eq!(model(x + Trit::POne), wrap(a + 1, N));
+    prop_assert_eq!(model(x + Trit::NOne), wrap(a - 1, N));
+    prop_assert_eq!(model(x * Trit::NOne), -a);
+    prop_assert_eq!(x.cmp(&(x + Trit::POne)), if a == max(N) { Ordering::Greater } else { Ordering::Less });
+
+    for k in 0..=N {
+        prop_assert_eq!(model(x << k), wrap(a * pow3(k), N), "{} << {}", a, k);
+        // Dropping the low trits rounds to the nearest, never a tie with an odd divisor
+        prop_assert_eq!(model(x >> k), (a + max(k)).div_euclid(pow3(k)), "{} >> {}", a, k);
+    }
+
+    let d = digits(a, N);
+    for k in 0..N {
+        let mut up = d.clone();
+        up.rotate_right(k);
+        prop_assert_eq!(model(x.rot(k as isize)), undigits(&up));
+        let mut down = d.clone();
+        down.rotate_left(k);
+        prop_assert_eq!(model(x.rot(-(k as isize))), undigits(&down));
+        prop_assert_eq!(x.rot((k + 2 * N) as isize), x.rot(k as isize));
+    }
+    prop_assert_eq!(model(x << (N + 1)), 0);
+    prop_assert_eq!(model(x >> (N + 1)), 0);
+
+    let trits: [Trit; N] = x.into();
+    prop_assert_eq!(&trits.map(|t| isize::from(t) as i128)[..], &d[..]);
+    prop_assert_eq!(Ternary::<N>::from(trits), x);
+    for (i, digit) in d.iter().enumerate() {
+        prop_assert_eq!(x.get(i).map(|t| isize::from(t) as i128), Some(*digit));
+    }
+    prop_assert_eq!(x.get(N), None);
+
+    prop_assert_eq!(isize::from(x) as i128, a);
+    prop_assert_eq!(Ternary::<N>::from(a as isize), x);
+    prop_assert_eq!(i64::try_from(x).map(i128::from), Ok(a));
+    prop_assert_eq!(Ternary::<N>::try_from(a as i64), Ok(x));
+    prop_assert_eq!(x.to_string().len(), N);
+    prop_assert_eq!(format!("0t{x:#}").parse(), Ok(x));
+    prop_assert_eq!(format!("0s{x:X}").parse(), Ok(x));
+    prop_assert_eq!(a.to_string().parse(), Ok(x));
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn word_binary(a in value(27), b in value(27)) {
+        check_binary::<27>(a, b)?;
+    }
+
+    #[test]
+    fn word_unary(a in value(27)) {
+        check_unary::<27>(a)?;
+    }
+
+    #[test]
+    fn word_out_of_range(a in max(27) + 1..=i64::MAX as i128) {
+        prop_assert!(Word::try_from(a).is_err());
+        prop_assert!(Word::try_from(-a).is_err());
+        prop_assert_eq!(model(Word::from(a as isize)), wrap(a, 27));
+    }
+
+    #[test]
+    fn tryte_binary(a in value(9), b in value(9)) {
+        check_binary::<9>(a, b)?;
+    }
+}
+
+/// All 19683 trytes, and each next to its neighbours
+#[test]
+fn every_tryte() {
+    let mut last = None;
+    for a in -max(9)..=max(9) {
+        check_unary::<9>(a).unwrap();
+        let tryte = Tryte::try_from(a).unwrap();
+        if let Some(last) = last {
+            assert!(last < tryte);
+            assert_eq!(last + Trit::POne, tryte);
+        }
+        last = Some(tryte);
+    }
+    assert_eq!(last, Some(Tryte::MAX));
+    assert_eq!(Tryte::MAX + Trit::POne, Tryte::MIN);
+}
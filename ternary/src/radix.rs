@@ -0,0 +1,227 @@
+//! Writing and reading [`Ternary`] values as text.
+//!
+//! | format   | `Tryte::from(5)` |
+//! |----------|------------------|
+//! | `{}`     | `0000001TT`      |
+//! | `{:#}`   | `1TT`            |
+//! | `{:X}`   | `00E`            |
+//! | `{:#X}`  | `E`              |
+//! | `{:?}`   | `Tryte(5)`       |
+//!
+//! `{:X}` is septivigntimal, base 27 with one digit per three trits: `0`,
+//! then `A` to `M` for 1 to 13 and `N` to `Z` for -1 to -13, the digits of
+//! [`Tribble::to_digit`], most significant first. Width, fill
+//! and alignment work like they do for the integers, so `{:012}` pads with
+//! zero trits.
+//!
+//! Parsing takes an optional sign, then `0t` and trits, `0s` and
+//! septivigntimal digits, or plain decimal.
+
+use std::{
+    fmt::{self, Display, UpperHex},
+    str::FromStr,
+};
+
+use crate::{
+    errors::ParseTernaryError,
+    ternary::{Storage, Ternary, Width},
+    tribble::Tribble,
+    trits::Trit,
+};
+
+/// Drops the leading zeros the `#` flag asks to leave out
+fn strip(digits: &str) -> &str {
+    match digits.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    }
+}
+
+impl<const N: usize> Display for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits: String = self.into_iter().map(Trit::to_char).rev().collect();
+        let digits = if f.alternate() { strip(&digits) } else { &digits };
+        f.pad_integral(true, "", digits)
+    }
+}
+
+/// Septivigntimal, see the [module docs](self)
+impl<const N: usize> UpperHex for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trits: [Trit; N] = self.into();
+        let digits: String = trits
+            .chunks(3)
+            .map(|chunk| {
+                let value = chunk.iter().rev().fold(0, |acc, &trit| acc * 3 + isize::from(trit));
+                Tribble::new(value).to_digit()
+            })
+            .rev()
+            .collect();
+        let digits = if f.alternate() { strip(&digits) } else { &digits };
+        f.pad_integral(true, "", digits)
+    }
+}
+
+/// A trit for each remainder of a balanced digit, and what's carried
+fn balanced(value: isize) -> (Trit, isize) {
+    match value.rem_euclid(3) {
+        0 => (Trit::Zero, value.div_euclid(3)),
+        1 => (Trit::POne, value.div_euclid(3)),
+        _ => (Trit::NOne, value.div_euclid(3) + 1),
+    }
+}
+
+impl<const N: usize> Ternary<N>
+where
+    Width<N>: Storage,
+{
+    /// Reads `T`, `0` and `1`, most significant first, without a prefix.
+    /// Shorter strings are zero extended.
+    pub fn from_trits(s: &str) -> Result<Self, ParseTernaryError> {
+        if s.is_empty() {
+            return Err(ParseTernaryError::Empty);
+        }
+        let mut trits = [Trit::Zero; N];
+        for (i, c) in s.chars().rev().enumerate() {
+            let trit = match c {
+                'T' => Trit::NOne,
+                '0' => Trit::Zero,
+                '1' => Trit::POne,
+                _ => return Err(ParseTernaryError::InvalidDigit(c)),
+            };
+            match trits.get_mut(i) {
+                Some(slot) => *slot = trit,
+                None if trit == Trit::Zero => {}
+                None => return Err(ParseTernaryError::OutOfRange),
+            }
+        }
+        Ok(trits.into())
+    }
+
+    /// Reads septivigntimal digits, most significant first like `{:X}`
+    /// writes them, without a prefix. Lower case letters work too. The
+    /// `septivigntimal` crate reads the lowest tribble first instead, the way
+    /// `spec.txt` writes instructions.
+    pub fn from_septivigntimal(s: &str) -> Result<Self, ParseTernaryError> {
+        if s.is_empty() {
+            return Err(ParseTernaryError::Empty);
+        }
+        let mut trits = [Trit::Zero; N];
+        for (i, c) in s.chars().rev().enumerate() {
+            let digit = Tribble::from_digit(c.to_ascii_uppercase())
+                .ok_or(ParseTernaryError::InvalidDigit(c))?;
+            let mut value = digit.value();
+            for j in 3 * i..3 * i + 3 {
+                let (trit, rest) = balanced(value);
+                value = rest;
+                match trits.get_mut(j) {
+                    Some(slot) => *slot = trit,
+                    None if trit == Trit::Zero => {}
+                    None => return Err(ParseTernaryError::OutOfRange),
+                }
+            }
+        }
+        Ok(trits.into())
+    }
+}
+
+/// `0t` trits, `0s` septivigntimal or decimal, any of them signed
+impl<const N: usize> FromStr for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Err = ParseTernaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let value = if let Some(trits) = s.strip_prefix("0t") {
+            Self::from_trits(trits)?
+        } else if let Some(digits) = s.strip_prefix("0s") {
+            Self::from_septivigntimal(digits)?
+        } else {
+            if s.is_empty() {
+                return Err(ParseTernaryError::Empty);
+            }
+            if let Some(c) = s.chars().find(|c| !c.is_ascii_digit()) {
+                return Err(ParseTernaryError::InvalidDigit(c));
+            }
+            // All digits, so the only way this fails is being too big
+            let value: i128 = s.parse().map_err(|_| ParseTernaryError::OutOfRange)?;
+            Self::try_from(value).map_err(|_| ParseTernaryError::OutOfRange)?
+        };
+
+        Ok(if negative { -value } else { value })
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{errors::ParseTernaryError, ternary::Ternary, tryte::Tryte, word::Word};
+
+    #[test]
+    fn format() {
+        let five = Tryte::from(5isize);
+        assert_eq!(format!("{five}"), "0000001TT");
+        assert_eq!(format!("{five:#}"), "1TT");
+        assert_eq!(format!("{five:X}"), "00E");
+        assert_eq!(format!("{five:#X}"), "E");
+        assert_eq!(format!("{five:?}"), "Tryte(5)");
+        assert_eq!(format!("{five:#6}|"), "   1TT|");
+        assert_eq!(format!("{five:<#6}|"), "1TT   |");
+        assert_eq!(format!("{five:#06}"), "0001TT");
+        assert_eq!(format!("{:#}", Tryte::ZERO), "0");
+        assert_eq!(format!("{:#X}", Tryte::ZERO), "0");
+        assert_eq!(format!("{:X}", Tryte::MAX), "MMM");
+        assert_eq!(format!("{:X}", Tryte::MIN), "ZZZ");
+        assert_eq!(format!("{:X}", Word::from(-1isize)), "00000000N");
+        // Widths that aren't a multiple of three still get whole digits
+        assert_eq!(format!("{:X}", Ternary::<4>::MAX), "AM");
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("5".parse(), Ok(Tryte::from(5isize)));
+        assert_eq!("-5".parse(), Ok(Tryte::from(-5isize)));
+        assert_eq!("+9841".parse(), Ok(Tryte::MAX));
+        assert_eq!("0t1TT".parse(), Ok(Tryte::from(5isize)));
+        assert_eq!("-0t1TT".parse(), Ok(Tryte::from(-5isize)));
+        assert_eq!("0sE".parse(), Ok(Tryte::from(5isize)));
+        assert_eq!("0se".parse(), Ok(Tryte::from(5isize)));
+        assert_eq!("0sZZZ".parse(), Ok(Tryte::MIN));
+        assert_eq!("0t0000000000001TT".parse(), Ok(Tryte::from(5isize)));
+
+        assert_eq!("".parse::<Tryte>(), Err(ParseTernaryError::Empty));
+        assert_eq!("-".parse::<Tryte>(), Err(ParseTernaryError::Empty));
+        assert_eq!("0t".parse::<Tryte>(), Err(ParseTernaryError::Empty));
+        assert_eq!("0t102".parse::<Tryte>(), Err(ParseTernaryError::InvalidDigit('2')));
+        assert_eq!("0s1".parse::<Tryte>(), Err(ParseTernaryError::InvalidDigit('1')));
+        assert_eq!("12a".parse::<Tryte>(), Err(ParseTernaryError::InvalidDigit('a')));
+        assert_eq!("9842".parse::<Tryte>(), Err(ParseTernaryError::OutOfRange));
+        assert_eq!("0t1000000000".parse::<Tryte>(), Err(ParseTernaryError::OutOfRange));
+        assert_eq!("0sA000".parse::<Tryte>(), Err(ParseTernaryError::OutOfRange));
+        assert_eq!("0sE".parse::<Ternary<2>>(), Err(ParseTernaryError::OutOfRange));
+        assert_eq!("99999999999999999999999999999999999999999".parse::<Word>(), Err(ParseTernaryError::OutOfRange));
+    }
+
+    #[test]
+    fn round_trip() {
+        for value in (-9841isize..=9841).step_by(7) {
+            let tryte = Tryte::from(value);
+            assert_eq!(format!("0t{tryte}").parse(), Ok(tryte));
+            assert_eq!(format!("0t{tryte:#}").parse(), Ok(tryte));
+            assert_eq!(format!("0s{tryte:X}").parse(), Ok(tryte));
+            assert_eq!(format!("0s{tryte:#X}").parse(), Ok(tryte));
+            assert_eq!(value.to_string().parse(), Ok(tryte));
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/// Which way division rounds a quotient that isn't whole
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Towards negative infinity for a positive divisor, so the remainder is never negative.
+    /// Matches `isize::div_euclid`.
+    #[default]
+    Euclidean,
+    /// Towards zero, so the remainder takes the sign of the dividend. Matches `isize`'s `/`.
+    Truncate,
+    /// To the nearest quotient, so the remainder is at most half the divisor either way.
+    /// This is what balanced ternary long division gives without any correction.
+    Nearest,
+}
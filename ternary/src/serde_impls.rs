@@ -0,0 +1,191 @@
+//! Human readable formats get the balanced ternary strings `Display` writes,
+//! like `"1T0"`. Binary formats get the packed bits as an integer just wide
+//! enough for them, and `Trit`s as `-1`, `0` or `1`.
+
+use std::fmt;
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, Unexpected, Visitor},
+};
+
+use crate::{
+    swar::Packed,
+    ternary::{Storage, Ternary, Width},
+    trits::Trit,
+};
+
+type Repr<const N: usize> = <Width<N> as Storage>::Repr;
+
+impl Serialize for Trit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_char(self.to_char())
+        } else {
+            serializer.serialize_i8(isize::from(*self) as i8)
+        }
+    }
+}
+
+struct TritVisitor;
+
+impl Visitor<'_> for TritVisitor {
+    type Value = Trit;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("one of 'T', '0' and '1', or -1, 0 and 1")
+    }
+
+    fn visit_char<E: de::Error>(self, v: char) -> Result<Trit, E> {
+        match v {
+            'T' => Ok(Trit::NOne),
+            '0' => Ok(Trit::Zero),
+            '1' => Ok(Trit::POne),
+            _ => Err(E::invalid_value(Unexpected::Char(v), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Trit, E> {
+        let mut chars = v.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => self.visit_char(c),
+            _ => Err(E::invalid_value(Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Trit, E> {
+        match v {
+            -1 => Ok(Trit::NOne),
+            0 => Ok(Trit::Zero),
+            1 => Ok(Trit::POne),
+            _ => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Trit, E> {
+        match v {
+            0 => Ok(Trit::Zero),
+            1 => Ok(Trit::POne),
+            _ => Err(E::invalid_value(Unexpected::Unsigned(v), &self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Trit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_char(TritVisitor)
+        } else {
+            deserializer.deserialize_i8(TritVisitor)
+        }
+    }
+}
+
+impl<const N: usize> Serialize for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+        let num = self.num().to_u128();
+        match <Repr<N> as Packed>::BITS {
+            32 => serializer.serialize_u32(num as u32),
+            64 => serializer.serialize_u64(num as u64),
+            _ => serializer.serialize_u128(num),
+        }
+    }
+}
+
+struct TernaryVisitor<const N: usize>;
+
+impl<const N: usize> Visitor<'_> for TernaryVisitor<N>
+where
+    Width<N>: Storage,
+{
+    type Value = Ternary<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "at most {N} trits of 'T', '0' and '1', or {N} packed trits")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Ternary<N>, E> {
+        Ternary::from_trits(v).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Ternary<N>, E> {
+        self.visit_u128(v.into())
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Ternary<N>, E> {
+        Ternary::from_packed(v)
+            .ok_or_else(|| E::invalid_value(Unexpected::Other("invalid packed trits"), &self))
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_str(TernaryVisitor);
+        }
+        match <Repr<N> as Packed>::BITS {
+            32 => deserializer.deserialize_u32(TernaryVisitor),
+            64 => deserializer.deserialize_u64(TernaryVisitor),
+            _ => deserializer.deserialize_u128(TernaryVisitor),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{trits::Trit, tryte::Tryte, word::{DoubleWord, Word}};
+
+    #[test]
+    fn json() {
+        assert_eq!(serde_json::to_string(&Trit::NOne).unwrap(), r#""T""#);
+        assert_eq!(serde_json::to_string(&Tryte::from(5isize)).unwrap(), r#""0000001TT""#);
+        assert_eq!(serde_json::from_str::<Tryte>(r#""1TT""#).unwrap(), Tryte::from(5isize));
+        assert_eq!(serde_json::from_str::<Trit>(r#""1""#).unwrap(), Trit::POne);
+        assert!(serde_json::from_str::<Tryte>(r#""12""#).is_err());
+        assert!(serde_json::from_str::<Tryte>(r#""1111111111""#).is_err());
+        assert!(serde_json::from_str::<Trit>(r#""10""#).is_err());
+
+        for value in [0isize, 1, -1, 12345, -98765, 3isize.pow(26)] {
+            let word = Word::from(value);
+            let json = serde_json::to_string(&word).unwrap();
+            assert_eq!(json, format!("\"{word}\""));
+            assert_eq!(serde_json::from_str::<Word>(&json).unwrap(), word);
+        }
+    }
+
+    #[test]
+    fn bincode() {
+        assert_eq!(bincode::serialize(&Trit::NOne).unwrap(), [0xFF]);
+        assert_eq!(bincode::deserialize::<Trit>(&[1]).unwrap(), Trit::POne);
+        assert!(bincode::deserialize::<Trit>(&[2]).is_err());
+
+        assert_eq!(bincode::serialize(&Tryte::ZERO).unwrap().len(), 4);
+        assert_eq!(bincode::serialize(&Word::ZERO).unwrap().len(), 8);
+        assert_eq!(bincode::serialize(&DoubleWord::ZERO).unwrap().len(), 16);
+        // A 00 pair and bits past the last trit
+        assert!(bincode::deserialize::<Tryte>(&0b101010101010101000u32.to_le_bytes()).is_err());
+        assert!(bincode::deserialize::<Tryte>(&(Tryte::ZERO.num() | 1 << 20).to_le_bytes()).is_err());
+
+        for value in [0isize, 1, -1, 12345, -98765, 3isize.pow(26)] {
+            let word = Word::from(value);
+            let bytes = bincode::serialize(&word).unwrap();
+            assert_eq!(bytes, word.num().to_le_bytes());
+            assert_eq!(bincode::deserialize::<Word>(&bytes).unwrap(), word);
+
+            let wide = DoubleWord::from(word) * DoubleWord::from(word);
+            assert_eq!(bincode::deserialize::<DoubleWord>(&bincode::serialize(&wide).unwrap()).unwrap(), wide);
+        }
+        // The carry of the last addition isn't part of the value
+        let carried = Word::MAX + Word::PONE;
+        assert_eq!(bincode::deserialize::<Word>(&bincode::serialize(&carried).unwrap()).unwrap(), Word::MIN);
+    }
+}
@@ -0,0 +1,543 @@
+//! Branch-free arithmetic on packed trits.
+//!
+//! Every trit takes two bits, `NOne = 0b01`, `Zero = 0b10` and `POne = 0b11`,
+//! with trit 0 in the lowest bits. That encoding is the trit plus two, which
+//! gives us a few things for free:
+//! - comparing two packed values as unsigned integers compares them as
+//!   balanced numbers, since each pair orders the same way as its trit
+//! - negating only flips the high bit of the pairs that have their low bit set
+//! - subtracting `0b01` from every pair gives the unbalanced (`0..=2`) digits
+//!   of the number plus `111...1`
+//!
+//! Addition works on those unbalanced digits. Adding a balanced `b` is the
+//! same as adding its positive trits and then subtracting its negative ones,
+//! and both of those only ever carry (or borrow) one, so each of them is a
+//! plain binary carry chain that a single integer add resolves.
+//!
+//! All functions assume their inputs hold `n` valid trits and nothing above,
+//! with room for a carry above those.
+
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+use crate::{rounding::Rounding, trits::Trit};
+
+pub trait Packed:
+    Copy
+    + Eq
+    + Ord
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+{
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+    /// `0b01` in every pair
+    const LOWS: Self;
+
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn is_odd(self) -> bool;
+    fn from_trit(trit: Trit) -> Self;
+    fn leading_zeros(self) -> usize;
+    fn to_u128(self) -> u128;
+    /// Keeps the low bits
+    fn from_u128(x: u128) -> Self;
+}
+
+macro_rules! packed {
+    ($($ty:ty),*) => {
+        $(impl Packed for $ty {
+            const BITS: usize = <$ty>::BITS as usize;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const LOWS: Self = <$ty>::MAX / 3;
+
+            #[inline(always)]
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$ty>::wrapping_add(self, rhs)
+            }
+
+            #[inline(always)]
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$ty>::wrapping_sub(self, rhs)
+            }
+
+            #[inline(always)]
+            fn is_odd(self) -> bool {
+                self & 1 == 1
+            }
+
+            #[inline(always)]
+            fn from_trit(trit: Trit) -> Self {
+                trit as Self
+            }
+
+            #[inline(always)]
+            fn leading_zeros(self) -> usize {
+                <$ty>::leading_zeros(self) as usize
+            }
+
+            #[inline(always)]
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            #[inline(always)]
+            fn from_u128(x: u128) -> Self {
+                x as Self
+            }
+        })*
+    };
+}
+
+packed!(u32, u64, u128);
+
+/// `0b01` in each of the low `n` pairs
+#[inline(always)]
+pub(crate) fn lows<T: Packed>(n: usize) -> T {
+    T::LOWS >> (T::BITS - 2 * n)
+}
+
+/// Every trit `Zero`
+#[inline(always)]
+pub(crate) fn zero<T: Packed>(n: usize) -> T {
+    lows::<T>(n) << 1
+}
+
+/// Both bits of each of the low `n` pairs
+#[inline(always)]
+fn full<T: Packed>(n: usize) -> T {
+    lows::<T>(n) | (lows::<T>(n) << 1)
+}
+
+/// Multiplies by `3^by`, dropping whatever goes off the top
+#[inline(always)]
+pub(crate) fn shl<T: Packed>(x: T, by: usize, n: usize) -> T {
+    ((x << (2 * by)) & full(n)) | (zero::<T>(n) & !(full::<T>(n) << (2 * by)))
+}
+
+/// Divides by `3^by`, rounding to nearest
+#[inline(always)]
+pub(crate) fn shr<T: Packed>(x: T, by: usize, n: usize) -> T {
+    (x >> (2 * by)) | (zero::<T>(n) & !(full::<T>(n) >> (2 * by)))
+}
+
+/// Splits into one mask of the `POne` trits and one of the `NOne` trits,
+/// each with a set low bit per trit
+#[inline(always)]
+pub(crate) fn planes<T: Packed>(x: T, n: usize) -> (T, T) {
+    let l = lows(n);
+    let lo = x & l;
+    let hi = (x >> 1) & l;
+    (hi & lo, !hi & lo)
+}
+
+/// Inverse of [`planes`]
+#[inline(always)]
+pub(crate) fn from_planes<T: Packed>(pos: T, neg: T, n: usize) -> T {
+    ((lows::<T>(n) & !neg) << 1) | pos | neg
+}
+
+#[inline(always)]
+pub(crate) fn neg<T: Packed>(x: T, n: usize) -> T {
+    x ^ ((x & lows(n)) << 1)
+}
+
+/// Tritwise minimum, the ternary AND
+#[inline(always)]
+pub(crate) fn min<T: Packed>(a: T, b: T, n: usize) -> T {
+    let (pa, na) = planes(a, n);
+    let (pb, nb) = planes(b, n);
+    from_planes(pa & pb, na | nb, n)
+}
+
+/// Tritwise maximum, the ternary OR
+#[inline(always)]
+pub(crate) fn max<T: Packed>(a: T, b: T, n: usize) -> T {
+    let (pa, na) = planes(a, n);
+    let (pb, nb) = planes(b, n);
+    from_planes(pa | pb, na & nb, n)
+}
+
+/// Scales by a single trit
+#[inline(always)]
+pub(crate) fn mul_trit<T: Packed>(x: T, trit: Trit, n: usize) -> T {
+    match trit {
+        Trit::POne => x,
+        Trit::Zero => zero(n),
+        Trit::NOne => neg(x, n),
+    }
+}
+
+/// Sign of the most significant non zero trit
+#[inline(always)]
+pub(crate) fn sign<T: Packed>(x: T, n: usize) -> Trit {
+    match x.cmp(&zero(n)) {
+        std::cmp::Ordering::Less => Trit::NOne,
+        std::cmp::Ordering::Equal => Trit::Zero,
+        std::cmp::Ordering::Greater => Trit::POne,
+    }
+}
+
+/// Index of the most significant non zero trit
+#[inline(always)]
+pub(crate) fn top_trit<T: Packed>(x: T, n: usize) -> Option<usize> {
+    let non_zero = (x ^ zero(n)) & full(n);
+    if non_zero == T::ZERO {
+        None
+    } else {
+        Some((T::BITS - 1 - non_zero.leading_zeros()) / 2)
+    }
+}
+
+#[inline(always)]
+pub(crate) fn abs<T: Packed>(x: T, n: usize) -> T {
+    mul_trit(x, sign(x, n), n)
+}
+
+/// Unbalanced digits in base `3^(2^k)` lanes of `2^(k+1)` bits, merged
+/// pairwise into lanes twice as wide
+macro_rules! merge {
+    ($x:ident: $ty:ty, $($bits:literal => $mul:literal),*) => {{
+        $(let mask = <$ty>::MAX / ((1 << $bits) + 1);
+        let $x = ($x & mask) + (($x >> $bits) & mask) * $mul;)*
+        $x
+    }};
+}
+
+/// The number held in `n` packed trits. Neighbouring digits are merged
+/// pairwise, base 3 into base 9 into base 81 and so on, so there's no loop.
+#[inline(always)]
+pub(crate) fn to_i128<T: Packed>(x: T, n: usize) -> i128 {
+    // Unbalanced digits, each one in 0..=2
+    let x = x.wrapping_sub(lows(n)).to_u128();
+    let digits = if T::BITS <= 64 {
+        let x = x as u64;
+        merge!(x: u64, 2 => 3, 4 => 9, 8 => 81, 16 => 6561, 32 => 43_046_721) as i128
+    } else {
+        merge!(x: u128, 2 => 3, 4 => 9, 8 => 81, 16 => 6561, 32 => 43_046_721, 64 => 1_853_020_188_851_841) as i128
+    };
+    // Take away the 111...1 that made the digits unbalanced
+    digits - (3i128.pow(n as u32) - 1) / 2
+}
+
+/// The unbalanced digits of every number under `3^5`, two bits each
+const DIGITS_5: [u16; 243] = {
+    let mut table = [0; 243];
+    let mut i = 0;
+    while i < 243 {
+        let (mut x, mut at) = (i, 0);
+        while at < 10 {
+            table[i] |= ((x % 3) as u16) << at;
+            x /= 3;
+            at += 2;
+        }
+        i += 1;
+    }
+    table
+};
+
+/// Unbalanced digits of `x`, two bits each, five at a time from a table
+#[inline(always)]
+fn spread(mut x: u64) -> u64 {
+    let mut digits = 0;
+    let mut at = 0;
+    while x != 0 {
+        digits |= (DIGITS_5[(x % 243) as usize] as u64) << at;
+        x /= 243;
+        at += 10;
+    }
+    digits
+}
+
+/// `x` as `q * 3^k + r` with `|r| <= (3^k - 1) / 2`, as `(q, r)`. An `i128`
+/// division is a slow library call, so a float estimates the quotient and
+/// the integers correct it.
+#[inline(always)]
+fn split(mut x: i128, k: usize) -> (i128, i128) {
+    let span = 3i128.pow(k as u32);
+    let half = (span - 1) / 2;
+    let mut q = 0;
+    while x < -half || x > half {
+        // Each pass leaves a few parts in 2^52 of what was there, and anything
+        // past halfway is at least one more
+        let step = match (x as f64 / span as f64).round() as i128 {
+            0 => x.signum(),
+            step => step,
+        };
+        q += step;
+        x -= step * span;
+    }
+    (q, x)
+}
+
+/// Inverse of [`to_i128`], keeping the low `n` trits of `x`. The digits are
+/// spread 27 trits at a time, as many as a `u64` holds.
+#[inline(always)]
+pub(crate) fn from_i128<T: Packed>(mut x: i128, n: usize) -> T {
+    let mut packed = T::ZERO;
+    let mut at = 0;
+    while at < n {
+        let k = (n - at).min(27);
+        let (high, low) = split(x, k);
+        // Adding 111...1 makes the digits unbalanced, which to_i128 takes away
+        let digits = spread((low + (3i128.pow(k as u32) - 1) / 2) as u64);
+        packed = packed | T::from_u128(digits as u128) << (2 * at);
+        x = high;
+        at += k;
+    }
+    packed.wrapping_add(lows(n))
+}
+
+/// Carries of a binary adder whose positions are the low bit of every pair.
+/// `generate` starts a carry, `propagate` passes one along and the high bit
+/// of each pair always passes it on to the next trit.
+/// Returns the carries into and out of every trit, and whether the top one carried.
+#[inline(always)]
+fn carry_chain<T: Packed>(generate: T, propagate: T, carry: bool, n: usize) -> (T, T, bool) {
+    let l = lows(n);
+    let x = generate | propagate | (l << 1);
+    let sum = x
+        .wrapping_add(generate)
+        .wrapping_add(if carry { T::ONE } else { T::ZERO });
+    let carries = sum ^ x ^ generate;
+    (carries & l, (carries >> 2) & l, (carries >> (2 * n)).is_odd())
+}
+
+#[inline(always)]
+fn times_three<T: Packed>(x: T) -> T {
+    (x << 1).wrapping_add(x)
+}
+
+/// `a + b + carry`, wrapped to `n` trits, and the trit carried out
+#[inline(always)]
+pub(crate) fn add<T: Packed>(a: T, b: T, carry: Trit, n: usize) -> (T, Trit) {
+    let l = lows(n);
+    let (pos, neg) = planes(b, n);
+
+    // a's unbalanced digits plus b's positive trits, so every digit is 0..=3.
+    // A 3 carries, and a 2 carries if it gets one.
+    let s = a.wrapping_sub(l).wrapping_add(pos);
+    let (lo, hi) = (s & l, (s >> 1) & l);
+    let (carry_in, carry_out, over) = carry_chain(lo & hi, hi & !lo, carry == Trit::POne, n);
+    // No digit leaves 0..=2 once the carries are settled, so no pair spills
+    // into the next one and the whole word can be fixed up at once
+    let s = s.wrapping_add(carry_in).wrapping_sub(times_three(carry_out));
+
+    // Now take away b's negative trits. A 0 borrows if it loses one, and so
+    // does a digit equal to what it loses if it's also borrowed from.
+    let (lo, hi) = (s & l, (s >> 1) & l);
+    let is_zero = !lo & !hi & l;
+    let is_one = lo & !hi;
+    let (borrow_in, borrow_out, under) = carry_chain(
+        is_zero & neg,
+        (is_zero & !neg) | (is_one & neg),
+        carry == Trit::NOne,
+        n,
+    );
+    let s = s
+        .wrapping_add(times_three(borrow_out))
+        .wrapping_sub(neg)
+        .wrapping_sub(borrow_in);
+
+    let carry = match (over, under) {
+        (true, false) => Trit::POne,
+        (false, true) => Trit::NOne,
+        _ => Trit::Zero,
+    };
+    (s.wrapping_add(l), carry)
+}
+
+/// Truncating product, one shifted add per non zero trit of `b`
+#[inline]
+pub(crate) fn mul<T: Packed>(a: T, b: T, n: usize) -> T {
+    let (mut pos, mut neg) = planes(b, n);
+    let minus = self::neg(a, n);
+    let mut acc = zero(n);
+    let mut shifted = (a, minus);
+    let mask = full::<T>(n);
+
+    while pos != T::ZERO || neg != T::ZERO {
+        if pos.is_odd() {
+            acc = add(acc, shifted.0, Trit::Zero, n).0;
+        } else if neg.is_odd() {
+            acc = add(acc, shifted.1, Trit::Zero, n).0;
+        }
+        pos = pos >> 2;
+        neg = neg >> 2;
+        // Shifting up a trit pulls a Zero in at the bottom
+        shifted = (
+            ((shifted.0 << 2) & mask) | (T::ONE << 1),
+            ((shifted.1 << 2) & mask) | (T::ONE << 1),
+        );
+    }
+    acc
+}
+
+/// Long division of a `trits` wide `a` by a non zero `b`, both already
+/// extended to `n` trits. `n` needs three trits more than `trits`, for the
+/// shifted divisor and twice the remainder.
+///
+/// Balanced ternary divides naturally to the nearest quotient: each trit,
+/// from the top, is whichever of `-1`, `0` and `1` leaves the smallest
+/// remainder, which ends up no more than half of `b` either way. The other
+/// roundings are then at most one step of `b` away.
+pub(crate) fn div_rem<T: Packed>(a: T, b: T, trits: usize, rounding: Rounding, n: usize) -> (T, T) {
+    let b_sign = sign(b, n);
+    let mut quot = zero(n);
+    let mut rem = a;
+
+    // The quotient can't reach past one trit above the difference in lengths,
+    // and starting there keeps the remainder within half a step
+    let top = match (top_trit(a, n), top_trit(b, n)) {
+        (Some(a), Some(b)) => (a + 1).checked_sub(b).map(|top| top.min(trits - 1)),
+        _ => None,
+    };
+
+    if let Some(top) = top {
+        let mut step = shl(abs(b, n), top, n);
+
+        for _ in 0..=top {
+            let rem_sign = sign(rem, n);
+            let mag = mul_trit(rem, rem_sign, n);
+            // Only take a step if it gets strictly closer, ties keep the remainder
+            let trit = if add(mag, mag, Trit::Zero, n).0 > step {
+                rem = add(rem, mul_trit(step, -rem_sign, n), Trit::Zero, n).0;
+                rem_sign * b_sign
+            } else {
+                Trit::Zero
+            };
+            quot = ((quot << 2) & full(n)) | T::from_trit(trit);
+            // The bottom trit of every step but the last is Zero, so this is exact
+            step = shr(step, 1, n);
+        }
+    }
+
+    let rem_sign = sign(rem, n);
+    let fix = match rounding {
+        Rounding::Nearest => false,
+        Rounding::Truncate => rem_sign != Trit::Zero && rem_sign != sign(a, n),
+        Rounding::Euclidean => rem_sign == Trit::NOne,
+    };
+    if fix {
+        // Move the remainder across zero by one b, and the quotient to match
+        let dir = rem_sign * b_sign;
+        quot = add(quot, zero(n), dir, n).0;
+        rem = add(rem, mul_trit(b, -dir, n), Trit::Zero, n).0;
+    }
+
+    (quot, rem)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const N: usize = 4;
+    const RANGE: isize = 40;
+
+    fn pack(mut value: isize) -> u32 {
+        let mut ret = 0;
+        for i in 0..N {
+            let (trit, carry) = match value.rem_euclid(3) {
+                0 => (Trit::Zero, 0),
+                1 => (Trit::POne, 0),
+                _ => (Trit::NOne, 1),
+            };
+            ret |= (trit as u32) << (2 * i);
+            value = value.div_euclid(3) + carry;
+        }
+        ret
+    }
+
+    fn unpack(x: u32) -> isize {
+        (0..N).rev().fold(0, |acc, i| acc * 3 + ((x >> (2 * i)) & 0b11) as isize - 2)
+    }
+
+    #[test]
+    fn add_matches_isize() {
+        for a in -RANGE..=RANGE {
+            for b in -RANGE..=RANGE {
+                for (carry, c) in [(Trit::NOne, -1), (Trit::Zero, 0), (Trit::POne, 1)] {
+                    let (sum, out) = add(pack(a), pack(b), carry, N);
+                    let total = unpack(sum) + 81 * isize::from(out);
+                    assert_eq!(total, a + b + c, "{a} + {b} + {c}");
+                    assert_eq!(sum, pack(a + b + c));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mul_matches_isize() {
+        for a in -RANGE..=RANGE {
+            for b in -RANGE..=RANGE {
+                assert_eq!(mul(pack(a), pack(b), N), pack(a * b), "{a} * {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn to_i128_matches() {
+        for a in -RANGE..=RANGE {
+            assert_eq!(to_i128(pack(a), N), a as i128);
+        }
+        // The low i + 1 trits all POne, up to a whole Word of them
+        let mut x = zero::<u64>(27);
+        for i in 0..27 {
+            x |= 0b11 << (2 * i);
+            assert_eq!(to_i128(x, 27), (3i128.pow(i as u32 + 1) - 1) / 2);
+            assert_eq!(to_i128(neg(x, 27), 27), -(3i128.pow(i as u32 + 1) - 1) / 2);
+        }
+        // And past 64 bits
+        let mut x = zero::<u128>(60);
+        for i in 0..60 {
+            x |= 0b11 << (2 * i);
+            assert_eq!(to_i128(x, 60), (3i128.pow(i as u32 + 1) - 1) / 2);
+            assert_eq!(to_i128(neg(x, 60), 60), -(3i128.pow(i as u32 + 1) - 1) / 2);
+        }
+    }
+
+    #[test]
+    fn from_i128_matches() {
+        for a in -RANGE..=RANGE {
+            assert_eq!(from_i128::<u32>(a as i128, N), pack(a));
+            // Wrapping past either end
+            let span = 3i128.pow(N as u32);
+            assert_eq!(from_i128::<u32>(a as i128 + span, N), pack(a));
+            assert_eq!(from_i128::<u32>(a as i128 - 7 * span, N), pack(a));
+        }
+        // Either side of halfway, where the estimated quotient is closest to wrong
+        for n in [9, 27, 54] {
+            let span = 3i128.pow(n as u32);
+            let half = (span - 1) / 2;
+            for x in [half, half + 1, -half, -half - 1, 3 * span + half + 1, i128::MAX, i128::MIN] {
+                let r = x.rem_euclid(span);
+                let r = if r > half { r - span } else { r };
+                assert_eq!(to_i128(from_i128::<u128>(x, n), n), r, "{x} in {n} trits");
+            }
+        }
+    }
+
+    #[test]
+    fn tritwise() {
+        for a in -RANGE..=RANGE {
+            assert_eq!(neg(pack(a), N), pack(-a));
+            assert_eq!(isize::from(sign(pack(a), N)), a.signum());
+            for b in -RANGE..=RANGE {
+                assert_eq!(pack(a).cmp(&pack(b)), a.cmp(&b));
+
+                let (x, y) = (pack(a), pack(b));
+                for i in 0..N {
+                    let trit = |v: u32| (v >> (2 * i)) & 0b11;
+                    assert_eq!(trit(min(x, y, N)), trit(x).min(trit(y)));
+                    assert_eq!(trit(max(x, y, N)), trit(x).max(trit(y)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,817 @@
+use core::fmt;
+use std::{
+    hash::Hash,
+    ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Range, Rem, Shl, Shr, Sub},
+};
+
+use crate::{
+    errors::{DivByZeroError, OutOfRangeError},
+    rounding::Rounding,
+    swar::{self, Packed},
+    trits::Trit,
+};
+
+/// Picks the integer a [`Ternary<N>`] packs its trits into
+pub struct Width<const N: usize>;
+
+/// Backing storage for `N` trits, two bits each plus two for the carry out
+/// of the last addition. Implemented for every `N` from 1 to 60.
+pub trait Storage {
+    type Repr: Packed + Hash;
+    /// Room for three more trits than `Repr`, which division works in
+    type Wide: Packed;
+
+    const MASK: Self::Repr;
+    const ZERO: Self::Repr;
+    const PONE: Self::Repr;
+    const NONE: Self::Repr;
+    const TWO: Self::Repr;
+    const MIN: Self::Repr;
+    const MAX: Self::Repr;
+}
+
+macro_rules! storage {
+    ($ty:ty, $wide:ty: $($n:literal)*) => {$(
+        impl Storage for Width<$n> {
+            type Repr = $ty;
+            type Wide = $wide;
+
+            const MASK: $ty = Self::MAX;
+            const ZERO: $ty = Self::MIN << 1;
+            const PONE: $ty = Self::ZERO | 0b01;
+            const NONE: $ty = Self::ZERO ^ 0b11;
+            const TWO: $ty = (Self::ZERO & !0b1111) | 0b1101;
+            const MIN: $ty = <$ty>::MAX / 3 >> (<$ty>::BITS as usize - 2 * $n);
+            const MAX: $ty = Self::MIN | Self::ZERO;
+        }
+    )*};
+}
+
+storage!(u32, u64: 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15);
+storage!(u64, u128: 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31);
+storage!(u128, u128: 32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60);
+
+type Repr<const N: usize> = <Width<N> as Storage>::Repr;
+type Wide<const N: usize> = <Width<N> as Storage>::Wide;
+
+/// [`Packed::to_u128`] for `const fn`s, which can't call trait methods. Every
+/// `Repr` is a `u32`, `u64` or `u128`, told apart by size.
+const fn repr_to_u128<const N: usize>(repr: &Repr<N>) -> u128
+where
+    Width<N>: Storage,
+{
+    let ptr = repr as *const Repr<N>;
+    // SAFETY: `Repr<N>` is the unsigned integer of the same size
+    unsafe {
+        match size_of::<Repr<N>>() {
+            4 => *ptr.cast::<u32>() as u128,
+            8 => *ptr.cast::<u64>() as u128,
+            _ => *ptr.cast::<u128>(),
+        }
+    }
+}
+
+/// [`Packed::from_u128`] for `const fn`s, keeping the low bits
+const fn repr_from_u128<const N: usize>(bits: u128) -> Repr<N>
+where
+    Width<N>: Storage,
+{
+    let mut repr = <Width<N>>::ZERO;
+    let ptr = &mut repr as *mut Repr<N>;
+    // SAFETY: as in `repr_to_u128`
+    unsafe {
+        match size_of::<Repr<N>>() {
+            4 => *ptr.cast::<u32>() = bits as u32,
+            8 => *ptr.cast::<u64>() = bits as u64,
+            _ => *ptr.cast::<u128>() = bits,
+        }
+    }
+    repr
+}
+
+/// `N` balanced trits, bit packed the same way for every width
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct Ternary<const N: usize>(pub(crate) Repr<N>)
+where
+    Width<N>: Storage;
+
+impl<const N: usize> fmt::Debug for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match N {
+            9 => "Tryte",
+            27 => "Word",
+            54 => "DoubleWord",
+            _ => "Ternary",
+        };
+        f.debug_tuple(name).field(&i128::from(*self)).finish()
+    }
+}
+
+impl<const N: usize> Default for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> Hash for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.num().hash(state);
+    }
+}
+
+impl<const N: usize> IntoIterator for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Item = Trit;
+
+    type IntoIter = <[Trit; N] as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let ret: [Trit; N] = self.into();
+        ret.into_iter()
+    }
+}
+
+impl<const N: usize> From<[Trit; N]> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn from(value: [Trit; N]) -> Self {
+        Ternary(
+            value
+                .into_iter()
+                .enumerate()
+                .map(|(i, trit)| Repr::<N>::from_trit(trit) << (2 * i))
+                .fold(Repr::<N>::ZERO, std::ops::BitOr::bitor),
+        )
+    }
+}
+
+impl<const N: usize> From<Trit> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn from(value: Trit) -> Self {
+        Ternary((<Width<N>>::ZERO & !Repr::<N>::from_trit(Trit::POne)) | Repr::<N>::from_trit(value))
+    }
+}
+
+impl<const N: usize> From<Ternary<N>> for [Trit; N]
+where
+    Width<N>: Storage,
+{
+    fn from(value: Ternary<N>) -> Self {
+        // One read of the bits and a table lookup a trit, rather than a shift
+        // of the whole repr and a match for each. Decoding every instruction
+        // comes through here.
+        let bits = value.num().to_u128();
+        std::array::from_fn(|i| TRITS[(bits >> (2 * i)) as usize & Trit::TRIT_BIT_MASK as usize])
+    }
+}
+
+impl<const N: usize> From<&Ternary<N>> for [Trit; N]
+where
+    Width<N>: Storage,
+{
+    fn from(value: &Ternary<N>) -> Self {
+        (*value).into()
+    }
+}
+
+impl<const N: usize> From<Ternary<N>> for i128
+where
+    Width<N>: Storage,
+{
+    fn from(value: Ternary<N>) -> Self {
+        swar::to_i128(value.num(), N)
+    }
+}
+
+/// Wraps around for widths past 40 trits, which don't fit
+impl<const N: usize> From<Ternary<N>> for isize
+where
+    Width<N>: Storage,
+{
+    fn from(value: Ternary<N>) -> Self {
+        i128::from(value) as isize
+    }
+}
+
+/// Wraps around if `value` doesn't fit in `N` trits, `try_from` an `i64` checks instead
+impl<const N: usize> From<isize> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn from(value: isize) -> Self {
+        Ternary(swar::from_i128(value as i128, N))
+    }
+}
+
+impl<const N: usize> TryFrom<i128> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Error = OutOfRangeError;
+
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        if value.unsigned_abs() > i128::from(Self::MAX).unsigned_abs() {
+            return Err(OutOfRangeError);
+        }
+        Ok(Ternary(swar::from_i128(value, N)))
+    }
+}
+
+impl<const N: usize> TryFrom<i64> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Error = OutOfRangeError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        i128::from(value).try_into()
+    }
+}
+
+impl<const N: usize> TryFrom<Ternary<N>> for i64
+where
+    Width<N>: Storage,
+{
+    type Error = OutOfRangeError;
+
+    fn try_from(value: Ternary<N>) -> Result<Self, Self::Error> {
+        i128::from(value).try_into().map_err(|_| OutOfRangeError)
+    }
+}
+
+impl<const N: usize> Add for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (sum, carry) = swar::add(self.num(), rhs.num(), Trit::Zero, N);
+        Ternary(sum | (Repr::<N>::from_trit(carry) << (2 * N)))
+    }
+}
+
+impl<const N: usize> Add<Trit> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Trit) -> Self::Output {
+        // Adding a single trit is just a carry into the bottom
+        let (sum, carry) = swar::add(self.num(), <Width<N>>::ZERO, rhs, N);
+        Ternary(sum | (Repr::<N>::from_trit(carry) << (2 * N)))
+    }
+}
+
+impl<const N: usize> Sub for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const N: usize> Neg for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Ternary(swar::neg(self.num(), N))
+    }
+}
+
+impl<const N: usize> Shl<usize> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    /// Shifting by `N` or more trits leaves zero
+    fn shl(self, rhs: usize) -> Self::Output {
+        Ternary(swar::shl(self.num(), rhs.min(N), N))
+    }
+}
+
+impl<const N: usize> Shr<usize> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    /// Shifting by `N` or more trits leaves zero
+    fn shr(self, rhs: usize) -> Self::Output {
+        Ternary(swar::shr(self.num(), rhs.min(N), N))
+    }
+}
+
+impl<const N: usize> Mul<Trit> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Trit) -> Self::Output {
+        Ternary(swar::mul_trit(self.num(), rhs, N))
+    }
+}
+
+impl<const N: usize> Mul for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // Up to 40 trits the product fits an i128, and the machine's multiply
+        // and one conversion back beat a shifted add for every trit
+        if N <= 40 {
+            Ternary(swar::from_i128(i128::from(self) * i128::from(rhs), N))
+        } else {
+            Ternary(swar::mul(self.num(), rhs.num(), N))
+        }
+    }
+}
+
+impl<const N: usize> Div for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Result<Self, DivByZeroError>;
+
+    /// Euclidean, see [`Ternary::div_rem`] for the other roundings
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs, Rounding::Euclidean).map(|(quot, _)| quot)
+    }
+}
+
+impl<const N: usize> Rem for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Result<Self, DivByZeroError>;
+
+    /// Euclidean, see [`Ternary::div_rem`] for the other roundings
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs, Rounding::Euclidean).map(|(_, rem)| rem)
+    }
+}
+
+impl<const N: usize> BitAnd for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Ternary(swar::min(self.num(), rhs.num(), N))
+    }
+}
+
+impl<const N: usize> BitOr for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Ternary(swar::max(self.num(), rhs.num(), N))
+    }
+}
+
+impl<const N: usize> PartialEq for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.num() == other.num()
+    }
+}
+
+impl<const N: usize> Eq for Ternary<N> where Width<N>: Storage {}
+
+impl<const N: usize> PartialOrd for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // The packed encoding orders the same way as the numbers it holds
+        self.num().cmp(&other.num())
+    }
+}
+
+/// [`trit`] of every two bits
+const TRITS: [Trit; 4] = [trit(0b00), trit(0b01), trit(0b10), trit(0b11)];
+
+/// The trit in the low two bits, anything that isn't a valid trit reads as `Zero`
+pub(crate) const fn trit(bits: u8) -> Trit {
+    match bits & Trit::TRIT_BIT_MASK {
+        0b01 => Trit::NOne,
+        0b11 => Trit::POne,
+        _ => Trit::Zero,
+    }
+}
+
+/// The bits of the lowest `range.len()` trits
+fn field_mask<const N: usize>(range: &Range<usize>) -> u128 {
+    assert!(
+        range.start <= range.end && range.end <= N,
+        "trit range {range:?} out of range for {N} trits"
+    );
+    (1 << (2 * range.len())) - 1
+}
+
+impl<const N: usize> Ternary<N>
+where
+    Width<N>: Storage,
+{
+    pub const PONE: Self = Ternary(<Width<N>>::PONE);
+    pub const ZERO: Self = Ternary(<Width<N>>::ZERO);
+    pub const NONE: Self = Ternary(<Width<N>>::NONE);
+    pub const TWO: Self = Ternary(<Width<N>>::TWO);
+    pub const MIN: Self = Ternary(<Width<N>>::MIN);
+    pub const MAX: Self = Ternary(<Width<N>>::MAX);
+    pub const LEN: usize = N;
+
+    /// Truncates to the low `M` trits, or zero extends to them
+    pub fn resize<const M: usize>(self) -> Ternary<M>
+    where
+        Width<M>: Storage,
+    {
+        let value = self.num().to_u128();
+        let extended = if M > N {
+            value | (<Width<M>>::ZERO.to_u128() & !<Width<N>>::MASK.to_u128())
+        } else {
+            value
+        };
+        Ternary(Repr::<M>::from_u128(extended) & <Width<M>>::MASK)
+    }
+
+    /// Rotates towards the most significant trit, like `<<`, for positive `val`
+    pub fn rot(&self, val: isize) -> Self {
+        let mut trits: [Trit; N] = self.into();
+        // Index 0 is the least significant trit, and turning back by `val`
+        // lands where turning forward by `N - val` does
+        trits.rotate_right(val.rem_euclid(N as isize) as usize);
+        trits.into()
+    }
+
+    /// Adds `rhs` and an incoming carry, returning the wrapped sum and the trit carried out
+    pub fn carrying_add(self, rhs: Self, carry: Trit) -> (Self, Trit) {
+        let (sum, carry) = swar::add(self.num(), rhs.num(), carry, N);
+        (Ternary(sum), carry)
+    }
+
+    /// Adds `rhs`, returning the wrapped sum and the trit carried out
+    pub fn overflowing_add(self, rhs: Self) -> (Self, Trit) {
+        self.carrying_add(rhs, Trit::Zero)
+    }
+
+    /// Subtracts `rhs` and adds the carry out of a lower subtraction.
+    /// Balanced carries are signed, so a borrow is just a carry of `NOne`.
+    pub fn borrowing_sub(self, rhs: Self, borrow: Trit) -> (Self, Trit) {
+        self.carrying_add(-rhs, borrow)
+    }
+
+    /// Subtracts `rhs`, returning the wrapped difference and the trit carried out
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, Trit) {
+        self.carrying_add(-rhs, Trit::Zero)
+    }
+
+    /// Multiplies by `rhs`, returning the wrapped product and the sign of what was cut off
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, Trit) {
+        let product = self * rhs;
+        let overflowed = if N <= 40 {
+            // Any two values multiply within an i128
+            i128::from(self) * i128::from(rhs) != i128::from(product)
+        } else {
+            // Only a product that didn't wrap divides back exactly
+            self != Self::ZERO && product.div_rem(self, Rounding::Nearest) != Ok((rhs, Self::ZERO))
+        };
+        let sign = if overflowed { self.get_sign() * rhs.get_sign() } else { Trit::Zero };
+        (product, sign)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_add(rhs) {
+            (sum, Trit::Zero) => Some(sum),
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_sub(rhs) {
+            (difference, Trit::Zero) => Some(difference),
+            _ => None,
+        }
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        match self.overflowing_mul(rhs) {
+            (product, Trit::Zero) => Some(product),
+            _ => None,
+        }
+    }
+
+    /// Euclidean, `None` if `rhs` is zero. Balanced ranges are symmetric, so nothing else can overflow.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        (self / rhs).ok()
+    }
+
+    /// Euclidean, `None` if `rhs` is zero
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        (self % rhs).ok()
+    }
+
+    /// Same as `+`, without the carry kept above the trits
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        self.overflowing_add(rhs).0
+    }
+
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        self.overflowing_sub(rhs).0
+    }
+
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        self * rhs
+    }
+
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self::saturate(self.overflowing_add(rhs))
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self::saturate(self.overflowing_sub(rhs))
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Self::saturate(self.overflowing_mul(rhs))
+    }
+
+    fn saturate((value, overflow): (Self, Trit)) -> Self {
+        match overflow {
+            Trit::POne => Self::MAX,
+            Trit::Zero => value,
+            Trit::NOne => Self::MIN,
+        }
+    }
+
+    /// Quotient and remainder, with the quotient rounded as asked
+    pub fn div_rem(self, rhs: Self, rounding: Rounding) -> Result<(Self, Self), DivByZeroError> {
+        if rhs == Self::ZERO {
+            return Err(DivByZeroError);
+        }
+
+        // A few trits wider, so the shifted divisor and the doubled remainder fit
+        let top = swar::zero::<Wide<N>>(N + 3) & !Wide::<N>::from_u128(<Width<N>>::MASK.to_u128());
+        let widen = |x: Self| Wide::<N>::from_u128(x.num().to_u128()) | top;
+        let (quot, rem) = swar::div_rem(widen(self), widen(rhs), N, rounding, N + 3);
+        let narrow = |x: Wide<N>| Ternary(Repr::<N>::from_u128(x.to_u128()) & <Width<N>>::MASK);
+        Ok((narrow(quot), narrow(rem)))
+    }
+
+    pub const fn get(&self, idx: usize) -> Option<Trit> {
+        if idx < N {
+            Some(trit((repr_to_u128::<N>(&self.0) >> (2 * idx)) as u8))
+        } else {
+            None
+        }
+    }
+
+    /// Like [`Ternary::get`], but panics if `idx` isn't below `N`
+    pub fn get_trit(&self, idx: usize) -> Trit {
+        self.get(idx).unwrap_or_else(|| panic!("trit index {idx} out of range for {N} trits"))
+    }
+
+    /// Panics if `idx` isn't below `N`
+    pub fn set_trit(&mut self, idx: usize, trit: Trit) {
+        self.set_field(idx..idx + 1, Self::from(trit));
+    }
+
+    /// The trits in `range` moved down to the bottom, with zeros above them.
+    /// Panics if `range` doesn't fit in `N` trits.
+    pub fn get_field(&self, range: Range<usize>) -> Self {
+        let low = field_mask::<N>(&range);
+        let bits = (self.num().to_u128() >> (2 * range.start)) & low;
+        Ternary(Repr::<N>::from_u128(bits | Self::ZERO.num().to_u128() & !low))
+    }
+
+    /// Replaces the trits in `range` with the lowest trits of `value`.
+    /// Panics if `range` doesn't fit in `N` trits.
+    pub fn set_field(&mut self, range: Range<usize>, value: Self) {
+        let low = field_mask::<N>(&range);
+        let cleared = self.num().to_u128() & !(low << (2 * range.start));
+        self.0 = Repr::<N>::from_u128(cleared | (value.num().to_u128() & low) << (2 * range.start));
+    }
+
+    pub fn pow_isize(lhs: Self, rhs: isize) -> Self {
+        if rhs < 0 {
+            Self::ZERO
+        } else if rhs == 1 || lhs == Self::PONE {
+            lhs
+        } else {
+            let mut ret = Self::PONE;
+            let mut count = rhs;
+            while count > 0 {
+                ret = ret * lhs;
+                count -= 1;
+            }
+            ret
+        }
+    }
+
+    /// The trit carried out of the addition that made this value, if it came from one
+    pub fn get_carry(&self) -> Trit {
+        trit((self.0 >> (2 * N)).to_u128() as u8)
+    }
+
+    pub fn get_sign(&self) -> Trit {
+        swar::sign(self.num(), N)
+    }
+
+    pub fn get_parity(&self) -> Trit {
+        trit(self.num().to_u128() as u8)
+    }
+
+    /// The packed trits, without the carry
+    pub const fn num(&self) -> Repr<N> {
+        let mask = repr_to_u128::<N>(&<Width<N>>::MASK);
+        repr_from_u128::<N>(repr_to_u128::<N>(&self.0) & mask)
+    }
+
+    /// Unpacks what [`Ternary::num`] gave, or `None` if a pair of bits is `0b00`,
+    /// which isn't a trit, or there are bits past the last trit
+    pub fn from_packed(bits: u128) -> Option<Self> {
+        let mask = <Width<N>>::MASK.to_u128();
+        // Every pair needs one of its bits set
+        let empty = !(bits | (bits >> 1)) & <Width<N>>::MIN.to_u128();
+        if bits & !mask != 0 || empty != 0 {
+            return None;
+        }
+        Some(Ternary(Packed::from_u128(bits)))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{rounding::Rounding, ternary::Ternary, trits::Trit, tryte::Tryte, word::{DoubleWord, Word}};
+
+    /// Every value of a few trits, against `isize`
+    #[test]
+    fn small_widths() {
+        type T = Ternary<4>;
+        let max: isize = T::MAX.into();
+        assert_eq!(max, 40);
+        for a in -max..=max {
+            let x = T::from(a);
+            assert_eq!(isize::from(x), a);
+            assert_eq!(isize::from(-x), -a);
+            assert_eq!(T::from_trits(&x.to_string()), Ok(x));
+            for b in -max..=max {
+                let y = T::from(b);
+                let wrap = |v: isize| isize::from(T::from(v));
+                assert_eq!(isize::from(x + y), wrap(a + b), "{a} + {b}");
+                assert_eq!(isize::from(x * y), wrap(a * b), "{a} * {b}");
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                if b != 0 {
+                    let (q, r) = x.div_rem(y, Rounding::Truncate).unwrap();
+                    assert_eq!((isize::from(q), isize::from(r)), (wrap(a / b), a % b), "{a} / {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_modes() {
+        type T = Ternary<4>;
+        let max: isize = T::MAX.into();
+        let fits = |v: isize| (-max..=max).contains(&v).then(|| T::from(v));
+        let saturate = |v: isize| T::from(v.clamp(-max, max));
+        for a in -max..=max {
+            let x = T::from(a);
+            for b in -max..=max {
+                let y = T::from(b);
+                assert_eq!(x.checked_add(y), fits(a + b), "{a} + {b}");
+                assert_eq!(x.checked_sub(y), fits(a - b), "{a} - {b}");
+                assert_eq!(x.checked_mul(y), fits(a * b), "{a} * {b}");
+                assert_eq!(x.saturating_add(y), saturate(a + b), "{a} + {b}");
+                assert_eq!(x.saturating_sub(y), saturate(a - b), "{a} - {b}");
+                assert_eq!(x.saturating_mul(y), saturate(a * b), "{a} * {b}");
+                assert_eq!(x.wrapping_add(y), T::from(a + b));
+                assert_eq!(x.wrapping_mul(y), T::from(a * b));
+                let carry = x.overflowing_mul(y).1;
+                assert_eq!(isize::from(carry), (a * b).signum() * isize::from(fits(a * b).is_none()));
+            }
+        }
+        assert_eq!(T::PONE.checked_div(T::ZERO), None);
+        assert_eq!(T::from(7).checked_rem(T::from(-3)), Some(T::PONE));
+    }
+
+    #[test]
+    fn wide_overflowing_mul() {
+        type T = Ternary<45>;
+        let big = T::from(3isize.pow(30));
+        assert_eq!(big.overflowing_mul(T::from(3isize.pow(14))).1, Trit::Zero);
+        assert_eq!(big.overflowing_mul(T::from(3isize.pow(15))), (T::ZERO, Trit::POne));
+        assert_eq!(big.overflowing_mul(-T::from(3isize.pow(15) + 1)), (-big, Trit::NOne));
+        assert_eq!(T::ZERO.overflowing_mul(T::MAX), (T::ZERO, Trit::Zero));
+        assert_eq!(T::MAX.saturating_mul(T::TWO), T::MAX);
+    }
+
+    #[test]
+    fn try_from() {
+        use crate::{errors::OutOfRangeError, word::Word};
+
+        let max = (3i64.pow(27) - 1) / 2;
+        assert_eq!(Word::try_from(max), Ok(Word::MAX));
+        assert_eq!(Word::try_from(-max), Ok(Word::MIN));
+        assert_eq!(Word::try_from(max + 1), Err(OutOfRangeError));
+        assert_eq!(Word::try_from(-max - 1), Err(OutOfRangeError));
+        assert_eq!(Word::try_from(i64::MIN), Err(OutOfRangeError));
+        assert_eq!(DoubleWord::try_from(i128::from(max) * 3i128.pow(27)).map(i128::from), Ok(i128::from(max) * 3i128.pow(27)));
+        assert_eq!(i64::try_from(Word::MAX), Ok(max));
+        assert_eq!(i64::try_from(DoubleWord::MAX), Err(OutOfRangeError));
+    }
+
+    #[test]
+    fn resize() {
+        let x: Ternary<5> = (-100).into();
+        assert_eq!(isize::from(x.resize::<40>()), -100);
+        assert_eq!(x.resize::<40>().resize::<5>(), x);
+        // -100 is TT10T
+        assert_eq!(x.to_string(), "TT10T");
+        assert_eq!(isize::from(x.resize::<2>()), -1);
+        assert_eq!(x.resize::<3>().to_string(), "10T");
+    }
+
+    #[test]
+    fn const_accessors() {
+        const TOP: Option<Trit> = Word::MIN.get(26);
+        const PAST: Option<Trit> = Word::MIN.get(27);
+        const BITS: u64 = Word::PONE.num();
+        assert_eq!(TOP, Some(Trit::NOne));
+        assert_eq!(PAST, None);
+        assert_eq!(BITS, Word::PONE.0 & (u64::MAX >> 10));
+
+        // One width for each integer the trits are packed into
+        let x = Tryte::from(-5);
+        assert_eq!(x.num(), x.0 & (u32::MAX >> 14));
+        assert_eq!(x.get(1), Some(Trit::POne));
+        let (q, _) = DoubleWord::MIN.overflowing_add(DoubleWord::NONE);
+        assert_eq!(q.num(), q.0 & (u128::MAX >> 20));
+        assert_eq!(DoubleWord::MIN.get(53), Some(Trit::NOne));
+    }
+
+    #[test]
+    fn double_word() {
+        let x = DoubleWord::from(3isize.pow(25)) * DoubleWord::from(3isize.pow(25)) + DoubleWord::from(12345isize);
+        let y: i128 = x.into();
+        assert_eq!(y, 3i128.pow(50) + 12345);
+        assert_eq!(i128::from(DoubleWord::MAX), (3i128.pow(54) - 1) / 2);
+        assert_eq!(DoubleWord::MAX.overflowing_add(DoubleWord::PONE), (DoubleWord::MIN, Trit::POne));
+
+        let (q, r) = x.div_rem(DoubleWord::from(7isize), Rounding::Euclidean).unwrap();
+        assert_eq!((i128::from(q), i128::from(r)), (y.div_euclid(7), y.rem_euclid(7)));
+    }
+
+    #[test]
+    fn packed() {
+        for value in [Word::ZERO, Word::MAX, Word::MIN, Word::from(-12345isize)] {
+            assert_eq!(Word::from_packed(value.num().into()), Some(value));
+        }
+        // 0b00 isn't a trit, and a word has nothing past bit 54
+        assert_eq!(Word::from_packed(0), None);
+        assert_eq!(Word::from_packed(Word::ZERO.num() as u128 & !0b11), None);
+        assert_eq!(Word::from_packed(Word::ZERO.num() as u128 | 1 << 54), None);
+        assert_eq!(Tryte::from_packed(Tryte::PONE.num().into()), Some(Tryte::PONE));
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::{errors::OutOfRangeError, trits::Trit};
+
+/// The digit of each of [`Tribble::ALL`]. Everything that writes tribbles as
+/// letters goes through this, whichever order it puts them in.
+const DIGITS: [char; 27] = [
+    'Z', 'Y', 'X', 'W', 'V', 'U', 'T', 'S', 'R', 'Q', 'P', 'O', 'N', '0', 'A', 'B', 'C', 'D',
+    'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+];
+
+/// 3 trits, lowest first, holding -13 to 13. Opcodes, registers and control
+/// fields are each a tribble.
+///
+/// Arithmetic wraps around, like it does on the wider types.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Tribble(pub [Trit; 3]);
+
+impl Tribble {
+    pub const ZERO: Tribble = Tribble([Trit::Zero; 3]);
+    pub const MIN: Tribble = Tribble([Trit::NOne; 3]);
+    pub const MAX: Tribble = Tribble([Trit::POne; 3]);
+
+    /// Every tribble, from -13 up to 13
+    pub const ALL: [Tribble; 27] = {
+        let mut all = [Tribble::ZERO; 27];
+        let mut i = 0;
+        while i < 27 {
+            all[i] = Tribble::new(i as isize - 13);
+            i += 1;
+        }
+        all
+    };
+
+    /// Panics if `value` is outside -13..=13, which fails the build for a `const`
+    pub const fn new(value: isize) -> Tribble {
+        assert!(-13 <= value && value <= 13, "a tribble holds -13 to 13");
+        let mut trits = [Trit::Zero; 3];
+        let mut value = value;
+        let mut i = 0;
+        while i < 3 {
+            trits[i] = match value.rem_euclid(3) {
+                0 => Trit::Zero,
+                1 => Trit::POne,
+                _ => Trit::NOne,
+            };
+            // Rounds to the nearest, so a digit of T carries one up
+            value = (value + 1).div_euclid(3);
+            i += 1;
+        }
+        Tribble(trits)
+    }
+
+    pub const fn from_trits(trits: [Trit; 3]) -> Tribble {
+        Tribble(trits)
+    }
+
+    pub const fn trits(self) -> [Trit; 3] {
+        self.0
+    }
+
+    pub const fn value(self) -> isize {
+        const fn trit(trit: Trit) -> isize {
+            match trit {
+                Trit::NOne => -1,
+                Trit::Zero => 0,
+                Trit::POne => 1,
+            }
+        }
+        trit(self.0[0]) + 3 * trit(self.0[1]) + 9 * trit(self.0[2])
+    }
+
+    /// Where it is in [`Tribble::ALL`], for tables with an entry per tribble
+    pub const fn index(self) -> usize {
+        (self.value() + 13) as usize
+    }
+
+    /// Every tribble, from -13 up to 13
+    pub fn iter() -> impl DoubleEndedIterator<Item = Tribble> {
+        Tribble::ALL.into_iter()
+    }
+
+    /// The septivigntimal digit, `0`, `A` to `M` for 1 to 13 or `N` to `Z`
+    /// for -1 to -13
+    pub const fn to_digit(self) -> char {
+        DIGITS[self.index()]
+    }
+
+    /// Reads a digit [`Tribble::to_digit`] writes, upper case only
+    pub fn from_digit(digit: char) -> Option<Tribble> {
+        DIGITS.iter().position(|&d| d == digit).map(|i| Tribble::ALL[i])
+    }
+
+    /// Wraps any value into -13..=13
+    const fn wrapping(value: isize) -> Tribble {
+        Tribble::new((value + 13).rem_euclid(27) - 13)
+    }
+}
+
+impl PartialOrd for Tribble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tribble {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value().cmp(&other.value())
+    }
+}
+
+impl Add for Tribble {
+    type Output = Tribble;
+    fn add(self, rhs: Tribble) -> Self::Output {
+        Tribble::wrapping(self.value() + rhs.value())
+    }
+}
+
+impl Sub for Tribble {
+    type Output = Tribble;
+    fn sub(self, rhs: Tribble) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for Tribble {
+    type Output = Tribble;
+    fn neg(self) -> Self::Output {
+        Tribble(self.0.map(Neg::neg))
+    }
+}
+
+impl Mul for Tribble {
+    type Output = Tribble;
+    fn mul(self, rhs: Tribble) -> Self::Output {
+        Tribble::wrapping(self.value() * rhs.value())
+    }
+}
+
+impl From<[Trit; 3]> for Tribble {
+    fn from(value: [Trit; 3]) -> Self {
+        Tribble(value)
+    }
+}
+
+impl From<Tribble> for [Trit; 3] {
+    fn from(value: Tribble) -> Self {
+        value.0
+    }
+}
+
+impl From<Tribble> for isize {
+    fn from(value: Tribble) -> Self {
+        value.value()
+    }
+}
+
+impl TryFrom<isize> for Tribble {
+    type Error = OutOfRangeError;
+
+    fn try_from(value: isize) -> Result<Self, Self::Error> {
+        if (-13..=13).contains(&value) {
+            Ok(Tribble::new(value))
+        } else {
+            Err(OutOfRangeError)
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{errors::OutOfRangeError, tribble::Tribble, trits::Trit::*};
+
+    #[test]
+    fn values() {
+        for (i, tribble) in Tribble::iter().enumerate() {
+            assert_eq!(tribble.value(), i as isize - 13);
+            assert_eq!(tribble.index(), i);
+            assert_eq!(Tribble::try_from(tribble.value()), Ok(tribble));
+        }
+        assert_eq!(Tribble::new(0), Tribble::ZERO);
+        assert_eq!(Tribble::new(-13), Tribble::MIN);
+        assert_eq!(Tribble::new(13), Tribble::MAX);
+        assert_eq!(Tribble::new(5), Tribble([NOne, NOne, POne]));
+        assert_eq!(Tribble::new(-3), Tribble([Zero, NOne, Zero]));
+        assert_eq!(Tribble::try_from(14), Err(OutOfRangeError));
+        assert_eq!(Tribble::try_from(-14), Err(OutOfRangeError));
+    }
+
+    #[test]
+    fn digits() {
+        for tribble in Tribble::iter() {
+            assert_eq!(Tribble::from_digit(tribble.to_digit()), Some(tribble));
+        }
+        assert_eq!(Tribble::ZERO.to_digit(), '0');
+        assert_eq!(Tribble::new(1).to_digit(), 'A');
+        assert_eq!(Tribble::new(-1).to_digit(), 'N');
+        assert_eq!(Tribble::from_digit('a'), None);
+        assert_eq!(Tribble::from_digit('1'), None);
+    }
+
+    #[test]
+    fn order() {
+        assert!(Tribble::ALL.is_sorted());
+        assert!(Tribble::MIN < Tribble::ZERO && Tribble::ZERO < Tribble::MAX);
+        // Not the order of the trits read as a number
+        assert!(Tribble([POne, POne, Zero]) < Tribble([NOne, NOne, POne]));
+        assert_eq!(Tribble::iter().max(), Some(Tribble::MAX));
+    }
+
+    #[test]
+    fn arithmetic() {
+        for a in Tribble::iter() {
+            assert_eq!(-a, Tribble::new(-a.value()));
+            assert_eq!(a - a, Tribble::ZERO);
+            for b in Tribble::iter() {
+                let wrap = |value: isize| (value + 13).rem_euclid(27) - 13;
+                assert_eq!((a + b).value(), wrap(a.value() + b.value()));
+                assert_eq!((a - b).value(), wrap(a.value() - b.value()));
+                assert_eq!((a * b).value(), wrap(a.value() * b.value()));
+            }
+        }
+        assert_eq!(Tribble::MAX + Tribble::new(1), Tribble::MIN);
+    }
+}
@@ -165,22 +165,6 @@ impl Trit {
             },
         }
     }
-
-    fn cycle_1_0(self) -> Trit {
-        match self {
-            Trit::NOne => Trit::NOne,
-            Trit::Zero => Trit::POne,
-            Trit::POne => Trit::Zero,
-        }
-    }
-
-    fn cycle_n1_0(self) -> Trit {
-        match self {
-            Trit::NOne => Trit::Zero,
-            Trit::Zero => Trit::NOne,
-            Trit::POne => Trit::POne,
-        }
-    }
 }
 
 #[cfg(test)]
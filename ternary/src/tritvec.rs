@@ -0,0 +1,495 @@
+//! Growable runs of packed trits, for trit fields that don't line up with a
+//! [`Ternary`] width.
+//!
+//! Trits are packed two bits each like [`Ternary`] packs them, 32 to a
+//! `u64`, and index `0` is the least significant trit. Reading or writing a
+//! range moves whole runs of bits at a time rather than one trit at a time.
+
+use std::{
+    fmt,
+    iter::FusedIterator,
+    ops::Range,
+};
+
+use crate::{
+    errors::OutOfRangeError,
+    swar::Packed,
+    ternary::{Storage, Ternary, Width, trit},
+    trits::Trit,
+};
+
+/// Trits in each `u64` of storage
+const PER_WORD: usize = 32;
+
+/// The low `2 * trits` bits
+fn mask(trits: usize) -> u64 {
+    if trits == PER_WORD { u64::MAX } else { (1 << (2 * trits)) - 1 }
+}
+
+/// The packed trits `start..start + len`, at most 64 of them
+fn read(words: &[u64], start: usize, len: usize) -> u128 {
+    let mut bits = 0u128;
+    let mut done = 0;
+    while done < len {
+        let (word, offset) = ((start + done) / PER_WORD, (start + done) % PER_WORD);
+        let take = (PER_WORD - offset).min(len - done);
+        let chunk = (words[word] >> (2 * offset)) & mask(take);
+        bits |= (chunk as u128) << (2 * done);
+        done += take;
+    }
+    bits
+}
+
+/// Replaces the packed trits `start..start + len` with the low trits of `bits`
+fn write(words: &mut [u64], start: usize, len: usize, bits: u128) {
+    let mut done = 0;
+    while done < len {
+        let (word, offset) = ((start + done) / PER_WORD, (start + done) % PER_WORD);
+        let take = (PER_WORD - offset).min(len - done);
+        let chunk = (bits >> (2 * done)) as u64 & mask(take);
+        words[word] = words[word] & !(mask(take) << (2 * offset)) | chunk << (2 * offset);
+        done += take;
+    }
+}
+
+fn check_range(range: &Range<usize>, len: usize) {
+    assert!(
+        range.start <= range.end && range.end <= len,
+        "trit range {range:?} out of bounds for length {len}"
+    );
+}
+
+/// A borrowed run of trits, from [`TritVec::as_slice`] or [`TritVec::slice`]
+#[derive(Clone, Copy)]
+pub struct TritSlice<'a> {
+    words: &'a [u64],
+    start: usize,
+    len: usize,
+}
+
+impl<'a> TritSlice<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, idx: usize) -> Option<Trit> {
+        (idx < self.len).then(|| trit(read(self.words, self.start + idx, 1) as u8))
+    }
+
+    /// Panics if `range` goes past the end
+    pub fn slice(&self, range: Range<usize>) -> TritSlice<'a> {
+        check_range(&range, self.len);
+        TritSlice { words: self.words, start: self.start + range.start, len: range.len() }
+    }
+
+    /// Reads `range` as a number, its first trit the least significant.
+    /// Panics if `range` goes past the end or is longer than `N`.
+    pub fn get_field<const N: usize>(&self, range: Range<usize>) -> Ternary<N>
+    where
+        Width<N>: Storage,
+    {
+        self.slice(range).try_into().expect("trit field wider than the ternary type")
+    }
+
+    pub fn iter(&self) -> Iter<'a> {
+        Iter { slice: *self }
+    }
+
+    pub fn to_vec(&self) -> TritVec {
+        let mut vec = TritVec::new();
+        vec.extend_from_slice(*self);
+        vec
+    }
+}
+
+/// A growable, packed sequence of trits
+#[derive(Clone, Default)]
+pub struct TritVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl TritVec {
+    pub const fn new() -> Self {
+        TritVec { words: Vec::new(), len: 0 }
+    }
+
+    pub fn with_capacity(trits: usize) -> Self {
+        TritVec { words: Vec::with_capacity(trits.div_ceil(PER_WORD)), len: 0 }
+    }
+
+    /// `len` zero trits
+    pub fn zeros(len: usize) -> Self {
+        let mut vec = TritVec::with_capacity(len);
+        vec.resize(len, Trit::Zero);
+        vec
+    }
+
+    /// All of `parts` one after the other, the first at the lowest indices
+    pub fn concat(parts: &[TritSlice]) -> Self {
+        let mut vec = TritVec::with_capacity(parts.iter().map(TritSlice::len).sum());
+        for part in parts {
+            vec.extend_from_slice(*part);
+        }
+        vec
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn as_slice(&self) -> TritSlice<'_> {
+        TritSlice { words: &self.words, start: 0, len: self.len }
+    }
+
+    /// Panics if `range` goes past the end
+    pub fn slice(&self, range: Range<usize>) -> TritSlice<'_> {
+        self.as_slice().slice(range)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<Trit> {
+        self.as_slice().get(idx)
+    }
+
+    /// Panics if `idx` is past the end
+    pub fn set(&mut self, idx: usize, trit: Trit) {
+        assert!(idx < self.len, "trit index {idx} out of bounds for length {}", self.len);
+        write(&mut self.words, idx, 1, trit as u128);
+    }
+
+    /// See [`TritSlice::get_field`]
+    pub fn get_field<const N: usize>(&self, range: Range<usize>) -> Ternary<N>
+    where
+        Width<N>: Storage,
+    {
+        self.as_slice().get_field(range)
+    }
+
+    /// Writes the low `range.len()` trits of `value` over `range`, dropping
+    /// the rest. Panics if `range` goes past the end or is longer than `N`.
+    pub fn set_field<const N: usize>(&mut self, range: Range<usize>, value: Ternary<N>)
+    where
+        Width<N>: Storage,
+    {
+        check_range(&range, self.len);
+        assert!(range.len() <= N, "trit field wider than the ternary type");
+        write(&mut self.words, range.start, range.len(), value.num().to_u128());
+    }
+
+    /// Copies `src` over the trits from `start`. Panics if it doesn't fit.
+    pub fn set_range(&mut self, start: usize, src: TritSlice) {
+        check_range(&(start..start + src.len), self.len);
+        let mut done = 0;
+        while done < src.len {
+            let take = (src.len - done).min(PER_WORD);
+            let bits = read(src.words, src.start + done, take);
+            write(&mut self.words, start + done, take, bits);
+            done += take;
+        }
+    }
+
+    pub fn push(&mut self, trit: Trit) {
+        if self.len.is_multiple_of(PER_WORD) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, trit);
+    }
+
+    pub fn pop(&mut self) -> Option<Trit> {
+        let trit = self.get(self.len.checked_sub(1)?)?;
+        self.truncate(self.len - 1);
+        Some(trit)
+    }
+
+    /// Drops everything from `len` on, if there's that much
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        self.len = len;
+        self.words.truncate(len.div_ceil(PER_WORD));
+    }
+
+    pub fn resize(&mut self, len: usize, trit: Trit) {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+        let start = self.len;
+        self.words.resize(len.div_ceil(PER_WORD), 0);
+        self.len = len;
+        let fill = u64::MAX / 0b11 * trit as u64;
+        let mut done = start;
+        while done < len {
+            let take = (len - done).min(PER_WORD);
+            write(&mut self.words, done, take, fill as u128);
+            done += take;
+        }
+    }
+
+    pub fn extend_from_slice(&mut self, src: TritSlice) {
+        let start = self.len;
+        self.resize(start + src.len, Trit::Zero);
+        self.set_range(start, src);
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        self.as_slice().iter()
+    }
+}
+
+/// The trits of a [`TritSlice`], least significant first
+#[derive(Clone)]
+pub struct Iter<'a> {
+    slice: TritSlice<'a>,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Trit;
+
+    fn next(&mut self) -> Option<Trit> {
+        let trit = self.slice.get(0)?;
+        self.slice = self.slice.slice(1..self.slice.len);
+        Some(trit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len, Some(self.slice.len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Trit> {
+        let last = self.slice.len.checked_sub(1)?;
+        let trit = self.slice.get(last);
+        self.slice = self.slice.slice(0..last);
+        trit
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+impl FusedIterator for Iter<'_> {}
+
+impl<'a> IntoIterator for TritSlice<'a> {
+    type Item = Trit;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TritVec {
+    type Item = Trit;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Trit> for TritVec {
+    fn from_iter<I: IntoIterator<Item = Trit>>(iter: I) -> Self {
+        let mut vec = TritVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl Extend<Trit> for TritVec {
+    fn extend<I: IntoIterator<Item = Trit>>(&mut self, iter: I) {
+        for trit in iter {
+            self.push(trit);
+        }
+    }
+}
+
+impl From<&[Trit]> for TritVec {
+    fn from(value: &[Trit]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+impl From<TritSlice<'_>> for TritVec {
+    fn from(value: TritSlice<'_>) -> Self {
+        value.to_vec()
+    }
+}
+
+/// All `N` trits, leading zeros included
+impl<const N: usize> From<Ternary<N>> for TritVec
+where
+    Width<N>: Storage,
+{
+    fn from(value: Ternary<N>) -> Self {
+        let mut vec = TritVec::zeros(N);
+        vec.set_field(0..N, value);
+        vec
+    }
+}
+
+/// Zero extends slices shorter than `N`, longer ones don't fit
+impl<const N: usize> TryFrom<TritSlice<'_>> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Error = OutOfRangeError;
+
+    fn try_from(value: TritSlice<'_>) -> Result<Self, Self::Error> {
+        if value.len > N {
+            return Err(OutOfRangeError);
+        }
+        let above = !0u128 << (2 * value.len);
+        let bits = read(value.words, value.start, value.len) | Self::ZERO.num().to_u128() & above;
+        Ok(Ternary(Packed::from_u128(bits)))
+    }
+}
+
+impl<const N: usize> TryFrom<&TritVec> for Ternary<N>
+where
+    Width<N>: Storage,
+{
+    type Error = OutOfRangeError;
+
+    fn try_from(value: &TritVec) -> Result<Self, Self::Error> {
+        value.as_slice().try_into()
+    }
+}
+
+impl PartialEq for TritSlice<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for TritSlice<'_> {}
+
+impl PartialEq for TritVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for TritVec {}
+
+/// Most significant first, like [`Ternary`]
+impl fmt::Display for TritSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits: String = self.iter().rev().map(Trit::to_char).collect();
+        f.pad(&digits)
+    }
+}
+
+impl fmt::Debug for TritSlice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TritSlice({self})")
+    }
+}
+
+impl fmt::Display for TritVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_slice(), f)
+    }
+}
+
+impl fmt::Debug for TritVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TritVec({})", self.as_slice())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use crate::{
+        errors::OutOfRangeError,
+        trits::Trit,
+        tritvec::{TritSlice, TritVec},
+        tryte::Tryte,
+        word::Word,
+    };
+
+    fn trits(n: usize) -> Vec<Trit> {
+        (0..n).map(|i| [Trit::POne, Trit::NOne, Trit::Zero, Trit::NOne][i % 4]).collect()
+    }
+
+    #[test]
+    fn push_pop() {
+        let expected = trits(100);
+        let mut vec = TritVec::new();
+        for (i, &trit) in expected.iter().enumerate() {
+            vec.push(trit);
+            assert_eq!(vec.len(), i + 1);
+        }
+        assert_eq!(vec.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(vec.iter().rev().collect::<Vec<_>>(), expected.iter().copied().rev().collect::<Vec<_>>());
+        assert_eq!(vec, TritVec::from(&expected[..]));
+        for &trit in expected.iter().rev() {
+            assert_eq!(vec.pop(), Some(trit));
+        }
+        assert_eq!(vec.pop(), None);
+        assert!(vec.is_empty());
+
+        let mut vec = TritVec::zeros(70);
+        vec.set(69, Trit::POne);
+        vec.truncate(40);
+        vec.resize(70, Trit::Zero);
+        assert_eq!(vec, TritVec::zeros(70));
+        vec.resize(75, Trit::NOne);
+        assert_eq!(vec.get(74), Some(Trit::NOne));
+        assert_eq!(vec.get(69), Some(Trit::Zero));
+        assert_eq!(vec.get(75), None);
+    }
+
+    #[test]
+    fn fields() {
+        let word = Word::from(-123_456_789isize);
+        let mut vec = TritVec::zeros(80);
+        // Straddles the first and second `u64`
+        vec.set_field(20..47, word);
+        assert_eq!(vec.get_field::<27>(20..47), word);
+        assert_eq!(vec.slice(20..47).to_string(), word.to_string());
+        assert_eq!(vec.get_field::<27>(0..20), Word::ZERO);
+        assert_eq!(vec.get_field::<27>(47..74), Word::ZERO);
+        // A field narrower than the value keeps the low trits
+        vec.set_field(0..3, Tryte::from(14isize));
+        assert_eq!(vec.get_field::<9>(0..3), Tryte::from(14isize - 27));
+        // and a wider one zero extends
+        assert_eq!(vec.get_field::<9>(20..26), Tryte::from(isize::from(word.resize::<6>())));
+
+        assert_eq!(TritVec::from(word).get_field::<27>(0..27), word);
+        assert_eq!(Word::try_from(&TritVec::from(word)), Ok(word));
+        assert_eq!(Tryte::try_from(vec.slice(0..10)), Err(OutOfRangeError));
+    }
+
+    #[test]
+    fn splicing() {
+        let source = TritVec::from(&trits(90)[..]);
+        let mut vec = TritVec::zeros(100);
+        vec.set_range(5, source.slice(3..80));
+        assert_eq!(vec.slice(5..82), source.slice(3..80));
+        assert_eq!(vec.slice(0..5), TritVec::zeros(5).as_slice());
+        assert_eq!(vec.slice(82..100), TritVec::zeros(18).as_slice());
+
+        let parts: [TritSlice; 3] = [source.slice(0..7), source.slice(40..90), source.slice(1..2)];
+        let joined = TritVec::concat(&parts);
+        let expected: Vec<Trit> = parts.iter().flat_map(|part| part.iter()).collect();
+        assert_eq!(joined.len(), 58);
+        assert_eq!(joined.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(joined.slice(7..57).slice(10..20), source.slice(50..60));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        TritVec::zeros(10).set_field(5..12, Word::ZERO);
+    }
+}
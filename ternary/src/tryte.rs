@@ -1,89 +1,13 @@
-use core::fmt;
-use std::{
-    fmt::Debug,
-    hash::Hash,
-    ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Rem, Shl, Shr, Sub},
-    str::FromStr,
-};
-
 use crate::{
-    trits::{Trit, TritAddResult},
+    ternary::{Storage, Ternary, Width},
+    tribble::Tribble,
+    trits::Trit,
     word::Word,
     *,
 };
 
-/// I'm manually bit packing this because dependencies are annoying
-#[repr(transparent)]
-#[derive(Clone, Copy)]
-pub struct Tryte(pub(crate) u32);
-
-impl fmt::Debug for Tryte {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Word").field(&<Self as Into<isize>>::into(*self)).finish()
-    }
-}
-
-impl Hash for Tryte {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        state.write_u32(self.num());
-    }
-}
-
-impl FromStr for Tryte {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() > 9 {
-            return Err(());
-        }
-        match s.as_ascii() {
-            None => Err(()),
-            Some(s) => {
-                for i in s.iter().map(|asciichar| asciichar.to_char()) {
-                    if i != 'T' && i != '0' && i != '1' {
-                        return Err(());
-                    }
-                }
-                let mut ret: [Trit; 9] = Tryte::ZERO.into();
-                for (i, char) in s.iter().map(|char| char.to_char()).rev().enumerate() {
-                    ret[i] = match char {
-                        'T' => Trit::NOne,
-                        '0' => Trit::Zero,
-                        '1' => Trit::POne,
-                        _ => unreachable!(),
-                    }
-                }
-                Ok(ret.into())
-            }
-        }
-    }
-}
-
-impl IntoIterator for Tryte {
-    type Item = Trit;
-
-    type IntoIter = <[Trit; 9] as IntoIterator>::IntoIter;
-
-    fn into_iter(self) -> Self::IntoIter {
-        let ret: [Trit; 9] = self.into();
-        ret.into_iter()
-    }
-}
-
-impl From<[Trit; 9]> for Tryte {
-    fn from(value: [Trit; 9]) -> Self {
-        Tryte(
-            value
-                .into_iter()
-                .enumerate()
-                .map(|(i, trit)| -> u32 {
-                    let val = trit as u32;
-                    val << (2 * i)
-                })
-                .fold(0, std::ops::BitOr::bitor),
-        )
-    }
-}
+/// 9 trits, the smallest addressable unit
+pub type Tryte = Ternary<TRYTE_LEN>;
 
 impl From<[[Trit; 3]; 3]> for Tryte {
     fn from(value: [[Trit; 3]; 3]) -> Self {
@@ -91,32 +15,6 @@ impl From<[[Trit; 3]; 3]> for Tryte {
     }
 }
 
-impl From<Trit> for Tryte {
-    fn from(value: Trit) -> Self {
-        [
-            value,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-        ]
-        .into()
-    }
-}
-
-impl From<Tryte> for [Trit; 9] {
-    fn from(value: Tryte) -> Self {
-        let value = value.0;
-        [0u32, 1, 2, 3, 4, 5, 6, 7, 8].map(|i| unsafe {
-            std::mem::transmute::<u8, Trit>(((value >> (2 * i)) & TRIT_BIT_MASK as u32) as u8)
-        })
-    }
-}
-
 impl From<Tryte> for [[Trit; 3]; 3] {
     fn from(value: Tryte) -> Self {
         let value: [Trit; 9] = value.into();
@@ -126,235 +24,27 @@ impl From<Tryte> for [[Trit; 3]; 3] {
     }
 }
 
-impl From<Word> for Tryte {
-    fn from(value: Word) -> Self {
-        Tryte(value.0 as u32 & TRYTE_BIT_MASK)
+impl From<[Tribble; 3]> for Tryte {
+    fn from(value: [Tribble; 3]) -> Self {
+        Tryte::from_tribbles(value)
     }
 }
 
-impl From<Tryte> for isize {
+impl From<Tryte> for [Tribble; 3] {
     fn from(value: Tryte) -> Self {
-        let arr: [Trit; 9] = value.into();
-        arr.map(<Trit as Into<isize>>::into)
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (i, trit)| acc + (3isize.pow(i as u32) * trit))
-    }
-}
-
-impl From<isize> for Tryte {
-    fn from(mut value: isize) -> Self {
-        let mut sum: Tryte = [Trit::Zero; 9].into();
-        let neg = value.is_negative();
-
-        if neg {
-            value = -value;
-        }
-
-        for i in 0..(isize::BITS - 1) {
-            let bit: Tryte = match (value >> i) & 1 {
-                0 => Trit::Zero,
-                1 => Trit::POne,
-                _ => unreachable!(),
-            }
-            .into();
-
-            sum = sum + (bit * Tryte::pow_isize(Tryte::TWO, i as isize));
-        }
-
-        if neg {
-            sum = -sum;
-        }
-
-        sum
-    }
-}
-
-impl Add for Tryte {
-    type Output = Tryte;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        let mut val: [Trit; 9] = [Trit::Zero; 9];
-        let mut carry = Trit::Zero;
-
-        for (i, (l, r)) in self.into_iter().zip(rhs).enumerate() {
-            let TritAddResult { carry: c, result } = (l + r) + carry;
-            val[i] = result;
-            carry = c;
-        }
-        let mut ret: Tryte = val.into();
-        ret.0 = ret.0 | ((carry as u32) << TRYTE_BIT_LEN);
-        ret
-    }
-}
-
-impl Add<Trit> for Tryte {
-    type Output = Tryte;
-
-    fn add(self, rhs: Trit) -> Self::Output {
-        let val: Tryte = [
-            rhs,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-        ]
-        .into();
-
-        self + val
-    }
-}
-
-impl Sub for Tryte {
-    type Output = Tryte;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        self + -rhs
-    }
-}
-
-impl Neg for Tryte {
-    type Output = Tryte;
-
-    fn neg(self) -> Self::Output {
-        let arr: [Trit; 9] = self.into();
-        arr.map(std::ops::Neg::neg).into()
-    }
-}
-
-impl Shl<usize> for Tryte {
-    type Output = Tryte;
-
-    fn shl(self, rhs: usize) -> Self::Output {
-        Tryte(
-            #[rustfmt::ignore]
-            ((self.0 << (2 * rhs)) & TRYTE_BIT_MASK)
-                | (Tryte::ZERO.0 >> (TRYTE_BIT_LEN - (2 * rhs))),
-        )
-    }
-}
-
-impl Shr<usize> for Tryte {
-    type Output = Tryte;
-
-    fn shr(self, rhs: usize) -> Self::Output {
-        Tryte(
-            ((self.num() >> (2 * rhs)) & TRYTE_BIT_MASK)
-                | (Tryte::ZERO.0 << (TRYTE_BIT_LEN - (2 * rhs))),
-        )
-    }
-}
-
-impl Mul<Trit> for Tryte {
-    type Output = Tryte;
-
-    fn mul(self, rhs: Trit) -> Self::Output {
-        let arr: [Trit; 9] = self.into();
-        arr.map(|trit| trit * rhs).into()
-    }
-}
-
-impl Mul for Tryte {
-    type Output = Tryte;
-
-    fn mul(self, rhs: Self) -> Self::Output {
-        rhs.into_iter()
-            .enumerate()
-            .map(|(i, trit)| (self * trit) << i)
-            .fold(Tryte::ZERO, |acc, right| acc + right)
-    }
-}
-
-impl Div for Tryte {
-    type Output = Option<Tryte>;
-
-    fn div(self, rhs: Self) -> Self::Output {
-        if rhs == Tryte::ZERO {
-            None
-        } else {
-            let lhs: isize = self.into();
-            let rhs: isize = rhs.into();
-            Some(lhs.div_euclid(rhs).into())
-        }
-    }
-}
-
-impl Rem for Tryte {
-    type Output = Option<Tryte>;
-
-    fn rem(self, rhs: Self) -> Self::Output {
-        if rhs == Tryte::ZERO {
-            None
-        } else {
-            let lhs: isize = self.into();
-            let rhs: isize = rhs.into();
-            Some(lhs.rem_euclid(rhs).into())
-        }
-    }
-}
-
-impl BitAnd for Tryte {
-    type Output = Tryte;
-
-    fn bitand(self, rhs: Self) -> Self::Output {
-        let mut ret: [Trit; 9] = Tryte::ZERO.into();
-        let arr0: [Trit; 9] = self.into();
-        let arr1: [Trit; 9] = rhs.into();
-        for i in 0..9 {
-            ret[i] = arr0[i] & arr1[i];
-        }
-        ret.into()
-    }
-}
-
-impl BitOr for Tryte {
-    type Output = Tryte;
-
-    fn bitor(self, rhs: Self) -> Self::Output {
-        let mut ret: [Trit; 9] = Tryte::ZERO.into();
-        let arr0: [Trit; 9] = self.into();
-        let arr1: [Trit; 9] = rhs.into();
-        for i in 0..9 {
-            ret[i] = arr0[i] | arr1[i];
-        }
-        ret.into()
-    }
-}
-
-impl PartialEq for Tryte {
-    fn eq(&self, other: &Self) -> bool {
-        self.num() == other.num()
-    }
-}
-
-impl Eq for Tryte {}
-
-impl PartialOrd for Tryte {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+        <[[Trit; 3]; 3]>::from(value).map(Tribble)
     }
 }
 
-impl Ord for Tryte {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let lhs: isize = (*self).into();
-        let rhs: isize = (*other).into();
-        lhs.cmp(&rhs)
+/// Keeps the low tryte
+impl From<Word> for Tryte {
+    fn from(value: Word) -> Self {
+        value.resize()
     }
 }
 
 impl Tryte {
-    pub const PONE: Tryte = Tryte(0b101010101010101011);
-    pub const ZERO: Tryte = Tryte(0b101010101010101010);
-    pub const NONE: Tryte = Tryte(0b101010101010101001);
-    pub const TWO: Tryte = Tryte(0b101010101010101101);
-    pub const MIN: Tryte = Tryte(0b010101010101010101);
-    pub const MAX: Tryte = Tryte(0b111111111111111111);
-    pub const TRYTE_BIT_MASK: u32 = 0b111111111111111111;
+    pub const TRYTE_BIT_MASK: u32 = <Width<TRYTE_LEN>>::MASK;
     pub const TRYTE_SIZE: usize = 3usize.pow(9);
 
     pub unsafe fn from_num(num: u32) -> Tryte {
@@ -363,14 +53,6 @@ impl Tryte {
         }
     }
 
-    pub const fn get(&self, idx: usize) -> Option<Trit> {
-        if idx < 9 {
-            unsafe { std::mem::transmute((self.0 >> (2 * idx)) as u8 & TRIT_BIT_MASK) }
-        } else {
-            None
-        }
-    }
-
     pub const fn isize(&self) -> isize {
         let value = self.0;
         let mut arr = [Trit::Zero; 9];
@@ -416,31 +98,12 @@ impl Tryte {
             inner |= val << (2 * count);
             count += 1;
         }
-        Tryte(inner)
-    }
-
-    pub fn pow_isize(lhs: Tryte, rhs: isize) -> Tryte {
-        if rhs < 0 {
-            [Trit::Zero; 9].into()
-        } else if rhs == 1 || lhs == Self::PONE {
-            lhs
-        } else {
-            let mut ret = Self::PONE;
-            let mut count = rhs;
-            while count > 0 {
-                ret = ret * lhs;
-                count -= 1;
-            }
-            ret
-        }
+        Ternary(inner)
     }
 
-    pub fn carry(&self) -> Trit {
-        unsafe { std::mem::transmute((self.0 >> TRYTE_BIT_LEN) as u8 & TRIT_BIT_MASK) }
-    }
-
-    pub fn num(&self) -> u32 {
-        (self.0) & TRYTE_BIT_MASK
+    /// Lowest tribble first
+    pub const fn from_tribbles(value: [Tribble; 3]) -> Tryte {
+        Tryte::from_arr_2d([value[0].0, value[1].0, value[2].0])
     }
 }
 
@@ -465,7 +128,7 @@ pub mod test {
         let ones: Tryte = [PONE; 9].into();
         let one: Tryte = [PONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into();
         let res = ones + one;
-        let carry = res.carry();
+        let carry = res.get_carry();
         let num = res.num();
         let cmp = res.0;
         let res: [Trit; 9] = res.into();
@@ -477,7 +140,7 @@ pub mod test {
         let nones: Tryte = [NONE; 9].into();
         let none: Tryte = [NONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into();
         let res = nones + none;
-        let carry = res.carry();
+        let carry = res.get_carry();
         let num = res.num();
         let cmp = res.0;
         let res: [Trit; 9] = res.into();
@@ -489,7 +152,7 @@ pub mod test {
         let zeros: Tryte = [ZERO; 9].into();
         let one: Tryte = [NONE, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO, ZERO].into();
         let res = zeros + one;
-        let carry = res.carry();
+        let carry = res.get_carry();
         let res: [Trit; 9] = res.into();
         let exp: [Trit; 9] = one.into();
         assert_eq!(res, exp);
@@ -633,12 +296,36 @@ pub mod test {
 
     #[test]
     fn from_str() {
-        assert_eq!(Tryte::ZERO, "0".parse().unwrap());
-        assert_eq!(Tryte::NONE, "T".parse().unwrap());
-        assert_eq!(Tryte::PONE, "1".parse().unwrap());
+        assert_eq!(Tryte::ZERO, "0t0".parse().unwrap());
+        assert_eq!(Tryte::NONE, "0tT".parse().unwrap());
+        assert_eq!(Tryte::PONE, "0t1".parse().unwrap());
         use Trit::*;
         let tryte: Tryte = [NOne, POne, Zero, Zero, Zero, POne, NOne, POne, Zero].into();
-        assert_eq!(tryte, "01T10001T".parse().unwrap());
+        assert_eq!(tryte, "0t01T10001T".parse().unwrap());
+    }
+
+    #[test]
+    fn div_rem() {
+        use crate::{errors::DivByZeroError, rounding::Rounding};
+
+        assert_eq!(Tryte::PONE / Tryte::ZERO, Err(DivByZeroError));
+        assert_eq!(Tryte::PONE % Tryte::ZERO, Err(DivByZeroError));
+
+        for b in [1isize, -1, 2, -2, 3, 5, -7, 18, -243, 9841, -9841] {
+            for a in (-9841isize..=9841).step_by(97).chain([9841]) {
+                let (x, y): (Tryte, Tryte) = (a.into(), b.into());
+                let check = |rounding, quot: isize, rem: isize| {
+                    let (q, r) = x.div_rem(y, rounding).unwrap();
+                    assert_eq!((q.isize(), r.isize()), (quot, rem), "{a} / {b} {rounding:?}");
+                };
+                check(Rounding::Euclidean, a.div_euclid(b), a.rem_euclid(b));
+                check(Rounding::Truncate, a / b, a % b);
+
+                let (q, r) = x.div_rem(y, Rounding::Nearest).unwrap();
+                assert_eq!(q.isize() * b + r.isize(), a, "{a} / {b} nearest");
+                assert!(2 * r.isize().abs() <= b.abs(), "{a} / {b} nearest");
+            }
+        }
     }
 
     extern crate test;
@@ -652,8 +339,8 @@ pub mod test {
         let i_tryte: Tryte = (-9840).into();
         let r_tryte: Tryte = 18.into();
         b.iter(|| {
-            assert_eq!(div, (i_tryte / r_tryte).unwrap().into());
-            assert_eq!(rem, (i_tryte % r_tryte).unwrap().into());
+            assert_eq!(div, isize::from((i_tryte / r_tryte).unwrap()));
+            assert_eq!(rem, isize::from((i_tryte % r_tryte).unwrap()));
         });
     }
 }
@@ -1,108 +1,21 @@
-use core::fmt;
-use std::{
-    fmt::Display, hash::Hash, hint::unreachable_unchecked, ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Rem, Shl, Shr, Sub}, str::FromStr
-};
-
 use crate::{
-    trits::{Trit, TritAddResult},
+    swar,
+    ternary::{trit, Storage, Ternary, Width},
+    tribble::Tribble,
+    trits::Trit,
     tryte::Tryte,
     *,
 };
 
-/// I'm manually bit packing this because dependencies are annoying
-#[repr(transparent)]
-#[derive(Clone, Copy)]
-pub struct Word(pub(crate) u64);
-
-impl fmt::Debug for Word {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Word").field(&<Self as Into<isize>>::into(*self)).finish()
-    }
-}
-
-impl Default for Word {
-    fn default() -> Self {
-        Word::ZERO
-    }
-}
-
-impl Hash for Word {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        state.write_u64(self.num());
-    }
-}
-
-impl IntoIterator for Word {
-    type Item = Trit;
-
-    type IntoIter = <[Trit; 27] as IntoIterator>::IntoIter;
+/// 27 trits, the machine word
+pub type Word = Ternary<WORD_LEN>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        let ret: [Trit; 27] = self.into();
-        ret.into_iter()
-    }
-}
-
-impl Display for Word {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(
-            &self
-                .into_iter()
-                .map(Trit::to_char)
-                .rev()
-                .collect::<String>(),
-        )
-    }
-}
-
-impl FromStr for Word {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() > 27 {
-            return Err(());
-        }
-        match s.as_ascii() {
-            None => Err(()),
-            Some(s) => {
-                for i in s.iter().map(|asciichar| asciichar.to_char()) {
-                    if i != 'T' && i != '0' && i != '1' {
-                        return Err(());
-                    }
-                }
-                let mut ret: [Trit; 27] = Word::ZERO.into();
-                for (i, char) in s.iter().map(|char| char.to_char()).rev().enumerate() {
-                    ret[i] = match char {
-                        'T' => Trit::NOne,
-                        '0' => Trit::Zero,
-                        '1' => Trit::POne,
-                        _ => unreachable!(),
-                    }
-                }
-                Ok(ret.into())
-            }
-        }
-    }
-}
-
-impl From<[Trit; 27]> for Word {
-    fn from(value: [Trit; 27]) -> Self {
-        Word(
-            value
-                .into_iter()
-                .enumerate()
-                .map(|(i, trit)| -> u64 {
-                    let val = trit as u64;
-                    val << (2 * i)
-                })
-                .fold(0, std::ops::BitOr::bitor),
-        )
-    }
-}
+/// Two words, wide enough for any product of two of them
+pub type DoubleWord = Ternary<{ 2 * WORD_LEN }>;
 
 impl From<[Tryte; 3]> for Word {
     fn from(value: [Tryte; 3]) -> Self {
-        Word(
+        Ternary(
             value
                 .into_iter()
                 .enumerate()
@@ -118,9 +31,9 @@ impl From<[Tryte; 3]> for Word {
 impl From<Word> for [Tryte; 3] {
     fn from(value: Word) -> Self {
         let mut zero = [Tryte::ZERO; 3];
-        zero[0] = Tryte((value.0 as u32) & TRYTE_BIT_MASK);
-        zero[1] = Tryte(((value.0 >> 18) as u32) & TRYTE_BIT_MASK);
-        zero[2] = Tryte(((value.0 >> 36) as u32) & TRYTE_BIT_MASK);
+        zero[0] = Ternary((value.0 as u32) & TRYTE_BIT_MASK);
+        zero[1] = Ternary(((value.0 >> 18) as u32) & TRYTE_BIT_MASK);
+        zero[2] = Ternary(((value.0 >> 36) as u32) & TRYTE_BIT_MASK);
         zero
     }
 }
@@ -131,73 +44,6 @@ impl From<[[Trit; 3]; 9]> for Word {
     }
 }
 
-impl From<Tryte> for Word {
-    fn from(value: Tryte) -> Self {
-        Word(value.num() as u64 | WORD_ZERO_TOP)
-    }
-}
-
-impl From<Trit> for Word {
-    fn from(value: Trit) -> Self {
-        [
-            value,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-        ]
-        .into()
-    }
-}
-
-impl From<Word> for [Trit; 27] {
-    fn from(value: Word) -> Self {
-        let value = value.0;
-        [
-            0u64, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
-            23, 24, 25, 26,
-        ]
-        .map(|i| unsafe {
-            std::mem::transmute::<u8, Trit>(((value >> (2 * i)) & TRIT_BIT_MASK as u64) as u8)
-        })
-    }
-}
-
-impl From<&Word> for [Trit; 27] {
-    fn from(value: &Word) -> Self {
-        let value = value.0;
-        [
-            0u64, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
-            23, 24, 25, 26,
-        ]
-        .map(|i| unsafe {
-            std::mem::transmute::<u8, Trit>(((value >> (2 * i)) & TRIT_BIT_MASK as u64) as u8)
-        })
-    }
-}
-
 impl From<Word> for [[Trit; 3]; 9] {
     fn from(value: Word) -> Self {
         let value: [Trit; 27] = value.into();
@@ -205,310 +51,79 @@ impl From<Word> for [[Trit; 3]; 9] {
     }
 }
 
-impl From<Word> for isize {
-    fn from(value: Word) -> Self {
-        let arr: [Trit; 27] = value.into();
-        arr.map(<Trit as Into<isize>>::into)
-            .into_iter()
-            .enumerate()
-            .fold(0, |acc, (i, trit)| acc + (3isize.pow(i as u32) * trit))
+impl From<[Tribble; 9]> for Word {
+    fn from(value: [Tribble; 9]) -> Self {
+        value.map(Tribble::trits).into()
     }
 }
 
-impl From<isize> for Word {
-    fn from(mut value: isize) -> Self {
-        let mut sum: Word = [Trit::Zero; 27].into();
-        let neg = value.is_negative();
-
-        if neg {
-            value = -value;
-        }
-
-        for i in 0..(isize::BITS - 1) {
-            let bit: Word = match (value >> i) & 1 {
-                0 => Trit::Zero,
-                1 => Trit::POne,
-                _ => unreachable!(),
-            }
-            .into();
-
-            sum = sum + (bit * Word::pow_isize(Word::TWO, i as isize));
-        }
-
-        if neg {
-            sum = -sum;
-        }
-
-        sum
-    }
-}
-
-impl Add for Word {
-    type Output = Word;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        let mut val: [Trit; 27] = [Trit::Zero; 27];
-        let mut carry = Trit::Zero;
-
-        for (i, (l, r)) in self.into_iter().zip(rhs).enumerate() {
-            let TritAddResult { carry: c, result } = (l + r) + carry;
-            val[i] = result;
-            carry = c;
-        }
-        let mut ret: Word = val.into();
-        ret.0 = ret.0 | ((carry as u64) << WORD_BIT_LEN);
-        ret
+/// The tribble in every six bits. Instructions are decoded a tribble at a
+/// time, so a word splits into them with a lookup each.
+const TRIBBLES: [Tribble; 64] = {
+    let mut all = [Tribble::ZERO; 64];
+    let mut bits = 0;
+    while bits < 64 {
+        all[bits] = Tribble([trit(bits as u8), trit((bits >> 2) as u8), trit((bits >> 4) as u8)]);
+        bits += 1;
     }
-}
-
-impl Add<Trit> for Word {
-    type Output = Word;
-
-    fn add(self, rhs: Trit) -> Self::Output {
-        let val: Word = [
-            rhs,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-            Trit::Zero,
-        ]
-        .into();
-
-        self + val
-    }
-}
-
-impl Sub for Word {
-    type Output = Word;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        self + -rhs
-    }
-}
-
-impl Neg for Word {
-    type Output = Word;
-
-    fn neg(self) -> Self::Output {
-        let arr: [Trit; 27] = self.into();
-        arr.map(std::ops::Neg::neg).into()
-    }
-}
-
-impl Shl<usize> for Word {
-    type Output = Word;
-
-    fn shl(self, rhs: usize) -> Self::Output {
-        Word(
-            #[rustfmt::ignore]
-            ((self.0 << (2 * rhs)) & WORD_BIT_MASK)
-                | (Word::ZERO.0 >> (WORD_BIT_LEN - (2 * rhs))),
-        )
-    }
-}
-
-impl Shr<usize> for Word {
-    type Output = Word;
-
-    fn shr(self, rhs: usize) -> Self::Output {
-        Word(
-            ((self.num() >> (2 * rhs)) & WORD_BIT_MASK)
-                | (Word::ZERO.0 << (WORD_BIT_LEN - (2 * rhs))),
-        )
-    }
-}
-
-impl Mul<Trit> for Word {
-    type Output = Word;
-
-    fn mul(self, rhs: Trit) -> Self::Output {
-        let arr: [Trit; 27] = self.into();
-        arr.map(|trit| trit * rhs).into()
-    }
-}
-
-impl Mul for Word {
-    type Output = Word;
-
-    fn mul(self, rhs: Self) -> Self::Output {
-        rhs.into_iter()
-            .enumerate()
-            .map(|(i, trit)| (self * trit) << i)
-            .fold(Word::ZERO, |acc, right| acc + right)
-    }
-}
-
-impl Div for Word {
-    type Output = Option<Word>;
-
-    fn div(self, rhs: Self) -> Self::Output {
-        if rhs == Word::ZERO {
-            None
-        } else {
-            let lhs: isize = self.into();
-            let rhs: isize = rhs.into();
-            Some(lhs.div_euclid(rhs).into())
-        }
-    }
-}
-
-impl Rem for Word {
-    type Output = Option<Word>;
-
-    fn rem(self, rhs: Self) -> Self::Output {
-        if rhs == Word::ZERO {
-            None
-        } else {
-            let lhs: isize = self.into();
-            let rhs: isize = rhs.into();
-            Some(lhs.rem_euclid(rhs).into())
-        }
-    }
-}
-
-impl BitAnd for Word {
-    type Output = Word;
-
-    fn bitand(self, rhs: Self) -> Self::Output {
-        let mut ret: [Trit; 27] = Word::ZERO.into();
-        let arr0: [Trit; 27] = self.into();
-        let arr1: [Trit; 27] = rhs.into();
-        for i in 0..27 {
-            ret[i] = arr0[i] & arr1[i];
-        }
-        ret.into()
-    }
-}
-
-impl BitOr for Word {
-    type Output = Word;
+    all
+};
 
-    fn bitor(self, rhs: Self) -> Self::Output {
-        let mut ret: [Trit; 27] = Word::ZERO.into();
-        let arr0: [Trit; 27] = self.into();
-        let arr1: [Trit; 27] = rhs.into();
-        for i in 0..27 {
-            ret[i] = arr0[i] | arr1[i];
-        }
-        ret.into()
+impl From<Word> for [Tribble; 9] {
+    fn from(value: Word) -> Self {
+        std::array::from_fn(|i| TRIBBLES[(value.0 >> (6 * i)) as usize & 0b11_1111])
     }
 }
 
-impl PartialEq for Word {
-    fn eq(&self, other: &Self) -> bool {
-        self.num() == other.num()
+impl From<Tryte> for Word {
+    fn from(value: Tryte) -> Self {
+        value.resize()
     }
 }
 
-impl Eq for Word {}
-
-impl PartialOrd for Word {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl From<Word> for DoubleWord {
+    fn from(value: Word) -> Self {
+        value.resize()
     }
 }
 
-impl Ord for Word {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        let lhs: isize = (*self).into();
-        let rhs: isize = (*other).into();
-        lhs.cmp(&rhs)
+/// Keeps the low word
+impl From<DoubleWord> for Word {
+    fn from(value: DoubleWord) -> Self {
+        value.resize()
     }
 }
 
 impl Word {
-    pub const WORD_BIT_MASK: u64 = 0b111111111111111111111111111111111111111111111111111111;
-    pub const PONE: Word = Word(0b101010101010101010101010101010101010101010101010101011);
-    pub const ZERO: Word = Word(0b101010101010101010101010101010101010101010101010101010);
-    pub const NONE: Word = Word(0b101010101010101010101010101010101010101010101010101001);
-    pub const TWO: Word = Word(0b101010101010101010101010101010101010101010101010101101);
-    pub const MIN: Word = Word(0b010101010101010101010101010101010101010101010101010101);
-    pub const MAX: Word = Word(0b111111111111111111111111111111111111111111111111111111);
+    pub const WORD_BIT_MASK: u64 = <Width<WORD_LEN>>::MASK;
     pub const WORD_SIZE: usize = 3usize.pow(27);
 
     pub const unsafe fn from_u64(num: u64) -> Word {
-        Word(num)
+        Ternary(num)
     }
 
-    pub fn rot(&self, val: isize) -> Word {
-        let mut word: [Trit; 27] = self.into();
-        let func = match val.signum() {
-            -1 => <[Trit]>::rotate_right,
-            1 => <[Trit]>::rotate_left,
-            _ => unsafe { unreachable_unchecked() }
-        };
-        func(&mut word, val.abs() as usize);
-        word.into()
+    /// Full 54 trit product
+    pub fn wide_mul(self, rhs: Word) -> DoubleWord {
+        // The product always fits, in 54 trits and in an i128
+        Ternary(swar::from_i128(i128::from(self) * i128::from(rhs), 2 * WORD_LEN))
     }
 
-    pub const fn get(&self, idx: usize) -> Option<Trit> {
-        if idx < 27 {
-            unsafe { std::mem::transmute((self.0 >> (2 * idx)) as u8 & TRIT_BIT_MASK) }
-        } else {
-            None
-        }
-    }
-
-    pub fn pow_isize(lhs: Word, rhs: isize) -> Word {
-        if rhs < 0 {
-            [Trit::Zero; 27].into()
-        } else if rhs == 1 || lhs == Self::PONE {
-            lhs
-        } else {
-            let mut ret = Self::PONE;
-            let mut count = rhs;
-            while count > 0 {
-                ret = ret * lhs;
-                count -= 1;
-            }
-            ret
-        }
-    }
-
-    pub fn get_carry(&self) -> Trit {
-        unsafe { std::mem::transmute((self.0 >> WORD_BIT_LEN) as u8 & TRIT_BIT_MASK) }
-    }
-
-    pub fn get_sign(&self) -> Trit {
-        let nums: [Trit; 27] = self.into();
-        for &trit in nums.iter().rev() {
-            if trit != Trit::Zero {
-                return trit;
-            }
-        }
-        return Trit::Zero;
+    /// Full 54 trit product, as `(low, high)`
+    pub fn widening_mul(self, rhs: Word) -> (Word, Word) {
+        self.wide_mul(rhs).halves()
     }
+}
 
-    pub fn get_parity(&self) -> Trit {
-        unsafe {
-            std::mem::transmute(self.num() as u8 & Trit::TRIT_BIT_MASK)
-        }
+impl DoubleWord {
+    /// Puts `high` above `low`
+    pub fn from_halves(low: Word, high: Word) -> DoubleWord {
+        Ternary(low.num() as u128 | (high.num() as u128) << (2 * WORD_LEN))
     }
 
-    pub const fn num(&self) -> u64 {
-        (self.0) & WORD_BIT_MASK
+    /// The low and high words
+    pub fn halves(self) -> (Word, Word) {
+        let num = self.num();
+        (Ternary(num as u64 & WORD_BIT_MASK), Ternary((num >> (2 * WORD_LEN)) as u64))
     }
 }
 
@@ -574,6 +189,27 @@ pub mod test {
         assert_eq!(carry, Trit::Zero);
     }
 
+    #[test]
+    fn overflowing() {
+        let two: Word = 2.into();
+        assert_eq!(Word::MAX.overflowing_add(Word::PONE), (Word::MIN, Trit::POne));
+        assert_eq!(Word::MIN.overflowing_add(Word::NONE), (Word::MAX, Trit::NOne));
+        assert_eq!(Word::MAX.overflowing_add(Word::NONE), (Word::MAX + Word::NONE, Trit::Zero));
+        assert_eq!(Word::MAX.carrying_add(Word::MAX, Trit::POne), (Word::ZERO, Trit::POne));
+        assert_eq!(Word::MIN.overflowing_sub(Word::PONE), (Word::MAX, Trit::NOne));
+        assert_eq!(Word::MIN.borrowing_sub(Word::MAX, Trit::NOne), (Word::ZERO, Trit::NOne));
+        assert_eq!(two.overflowing_sub(Word::MIN), (Word::MIN + Word::PONE, Trit::POne));
+
+        let big: Word = 3isize.pow(20).into();
+        assert_eq!(two.overflowing_mul(two), (4.into(), Trit::Zero));
+        assert_eq!(big.overflowing_mul(big), (Word::ZERO, Trit::POne));
+        assert_eq!(big.overflowing_mul(-big), (Word::ZERO, Trit::NOne));
+        assert_eq!(big.widening_mul(big), (Word::ZERO, 3isize.pow(13).into()));
+        let max: isize = Word::MAX.into();
+        assert_eq!(Word::MAX.widening_mul(Word::MAX), (((max + 1) / 2).into(), ((max - 1) / 2).into()));
+        assert_eq!(Word::MAX.widening_mul(Word::MIN), ((-(max + 1) / 2).into(), (-(max - 1) / 2).into()));
+    }
+
     #[test]
     fn test_ord() {
         let min: Tryte = [NONE; 9].into();
@@ -726,6 +362,33 @@ pub mod test {
         assert_eq!(nine_arr, three * three);
     }
 
+    #[test]
+    fn div_rem() {
+        use crate::{errors::DivByZeroError, rounding::Rounding};
+
+        assert_eq!(Word::PONE / Word::ZERO, Err(DivByZeroError));
+        assert_eq!(Word::PONE % Word::ZERO, Err(DivByZeroError));
+
+        let max: isize = Word::MAX.into();
+        let values = [0, 1, -1, 2, -2, 3, 7, -13, 100, -729, 123_456_789, -987_654_321, max, -max, max - 1, 1 - max];
+        for a in values {
+            for b in values.into_iter().filter(|&b| b != 0) {
+                let (x, y): (Word, Word) = (a.into(), b.into());
+                let check = |rounding, quot: isize, rem: isize| {
+                    let (q, r) = x.div_rem(y, rounding).unwrap();
+                    assert_eq!((q.into(), r.into()), (quot, rem), "{a} / {b} {rounding:?}");
+                };
+                check(Rounding::Euclidean, a.div_euclid(b), a.rem_euclid(b));
+                check(Rounding::Truncate, a / b, a % b);
+
+                let (q, r) = x.div_rem(y, Rounding::Nearest).unwrap();
+                let (q, r): (isize, isize) = (q.into(), r.into());
+                assert_eq!(q * b + r, a, "{a} / {b} nearest");
+                assert!(2 * r.abs() <= b.abs(), "{a} / {b} nearest");
+            }
+        }
+    }
+
     #[test]
     fn test_convertion() {
         for i in -9841..=-9839 {
@@ -746,8 +409,8 @@ pub mod test {
         let i_word: Word = (-38127987).into();
         let r_word: Word = 18.into();
         b.iter(|| {
-            assert_eq!(div, (i_word / r_word).unwrap().into());
-            assert_eq!(rem, (i_word % r_word).unwrap().into());
+            assert_eq!(div, isize::from((i_word / r_word).unwrap()));
+            assert_eq!(rem, isize::from((i_word % r_word).unwrap()));
         });
     }
 }
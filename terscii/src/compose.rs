@@ -0,0 +1,313 @@
+//! Accented Latin letters as a letter and the accents on it, the way the
+//! `ACCENTS_*` codes write them. Only the letters whose Unicode decomposition
+//! is an ASCII letter and accents TERSCII has are here.
+
+use crate::Accent::{self, *};
+
+/// Each letter with the letter it is made from and the last accent put on it,
+/// sorted by letter. A letter with two accents is made from the letter with
+/// the first.
+const COMPOSED: [(char, char, Accent); 264] = [
+    ('À', 'A', Grave),
+    ('Á', 'A', Acute),
+    ('Â', 'A', Circumflex),
+    ('Ã', 'A', Tilde),
+    ('Ä', 'A', Diaeresis),
+    ('Å', 'A', Ring),
+    ('Ç', 'C', Cedilla),
+    ('È', 'E', Grave),
+    ('É', 'E', Acute),
+    ('Ê', 'E', Circumflex),
+    ('Ë', 'E', Diaeresis),
+    ('Ì', 'I', Grave),
+    ('Í', 'I', Acute),
+    ('Î', 'I', Circumflex),
+    ('Ï', 'I', Diaeresis),
+    ('Ñ', 'N', Tilde),
+    ('Ò', 'O', Grave),
+    ('Ó', 'O', Acute),
+    ('Ô', 'O', Circumflex),
+    ('Õ', 'O', Tilde),
+    ('Ö', 'O', Diaeresis),
+    ('Ù', 'U', Grave),
+    ('Ú', 'U', Acute),
+    ('Û', 'U', Circumflex),
+    ('Ü', 'U', Diaeresis),
+    ('Ý', 'Y', Acute),
+    ('à', 'a', Grave),
+    ('á', 'a', Acute),
+    ('â', 'a', Circumflex),
+    ('ã', 'a', Tilde),
+    ('ä', 'a', Diaeresis),
+    ('å', 'a', Ring),
+    ('ç', 'c', Cedilla),
+    ('è', 'e', Grave),
+    ('é', 'e', Acute),
+    ('ê', 'e', Circumflex),
+    ('ë', 'e', Diaeresis),
+    ('ì', 'i', Grave),
+    ('í', 'i', Acute),
+    ('î', 'i', Circumflex),
+    ('ï', 'i', Diaeresis),
+    ('ñ', 'n', Tilde),
+    ('ò', 'o', Grave),
+    ('ó', 'o', Acute),
+    ('ô', 'o', Circumflex),
+    ('õ', 'o', Tilde),
+    ('ö', 'o', Diaeresis),
+    ('ù', 'u', Grave),
+    ('ú', 'u', Acute),
+    ('û', 'u', Circumflex),
+    ('ü', 'u', Diaeresis),
+    ('ý', 'y', Acute),
+    ('ÿ', 'y', Diaeresis),
+    ('Ā', 'A', Macron),
+    ('ā', 'a', Macron),
+    ('Ă', 'A', Breve),
+    ('ă', 'a', Breve),
+    ('Ć', 'C', Acute),
+    ('ć', 'c', Acute),
+    ('Ĉ', 'C', Circumflex),
+    ('ĉ', 'c', Circumflex),
+    ('Č', 'C', Caron),
+    ('č', 'c', Caron),
+    ('Ď', 'D', Caron),
+    ('ď', 'd', Caron),
+    ('Ē', 'E', Macron),
+    ('ē', 'e', Macron),
+    ('Ĕ', 'E', Breve),
+    ('ĕ', 'e', Breve),
+    ('Ě', 'E', Caron),
+    ('ě', 'e', Caron),
+    ('Ĝ', 'G', Circumflex),
+    ('ĝ', 'g', Circumflex),
+    ('Ğ', 'G', Breve),
+    ('ğ', 'g', Breve),
+    ('Ģ', 'G', Cedilla),
+    ('ģ', 'g', Cedilla),
+    ('Ĥ', 'H', Circumflex),
+    ('ĥ', 'h', Circumflex),
+    ('Ĩ', 'I', Tilde),
+    ('ĩ', 'i', Tilde),
+    ('Ī', 'I', Macron),
+    ('ī', 'i', Macron),
+    ('Ĭ', 'I', Breve),
+    ('ĭ', 'i', Breve),
+    ('Ĵ', 'J', Circumflex),
+    ('ĵ', 'j', Circumflex),
+    ('Ķ', 'K', Cedilla),
+    ('ķ', 'k', Cedilla),
+    ('Ĺ', 'L', Acute),
+    ('ĺ', 'l', Acute),
+    ('Ļ', 'L', Cedilla),
+    ('ļ', 'l', Cedilla),
+    ('Ľ', 'L', Caron),
+    ('ľ', 'l', Caron),
+    ('Ń', 'N', Acute),
+    ('ń', 'n', Acute),
+    ('Ņ', 'N', Cedilla),
+    ('ņ', 'n', Cedilla),
+    ('Ň', 'N', Caron),
+    ('ň', 'n', Caron),
+    ('Ō', 'O', Macron),
+    ('ō', 'o', Macron),
+    ('Ŏ', 'O', Breve),
+    ('ŏ', 'o', Breve),
+    ('Ő', 'O', DoubleAcute),
+    ('ő', 'o', DoubleAcute),
+    ('Ŕ', 'R', Acute),
+    ('ŕ', 'r', Acute),
+    ('Ŗ', 'R', Cedilla),
+    ('ŗ', 'r', Cedilla),
+    ('Ř', 'R', Caron),
+    ('ř', 'r', Caron),
+    ('Ś', 'S', Acute),
+    ('ś', 's', Acute),
+    ('Ŝ', 'S', Circumflex),
+    ('ŝ', 's', Circumflex),
+    ('Ş', 'S', Cedilla),
+    ('ş', 's', Cedilla),
+    ('Š', 'S', Caron),
+    ('š', 's', Caron),
+    ('Ţ', 'T', Cedilla),
+    ('ţ', 't', Cedilla),
+    ('Ť', 'T', Caron),
+    ('ť', 't', Caron),
+    ('Ũ', 'U', Tilde),
+    ('ũ', 'u', Tilde),
+    ('Ū', 'U', Macron),
+    ('ū', 'u', Macron),
+    ('Ŭ', 'U', Breve),
+    ('ŭ', 'u', Breve),
+    ('Ů', 'U', Ring),
+    ('ů', 'u', Ring),
+    ('Ű', 'U', DoubleAcute),
+    ('ű', 'u', DoubleAcute),
+    ('Ŵ', 'W', Circumflex),
+    ('ŵ', 'w', Circumflex),
+    ('Ŷ', 'Y', Circumflex),
+    ('ŷ', 'y', Circumflex),
+    ('Ÿ', 'Y', Diaeresis),
+    ('Ź', 'Z', Acute),
+    ('ź', 'z', Acute),
+    ('Ž', 'Z', Caron),
+    ('ž', 'z', Caron),
+    ('Ǎ', 'A', Caron),
+    ('ǎ', 'a', Caron),
+    ('Ǐ', 'I', Caron),
+    ('ǐ', 'i', Caron),
+    ('Ǒ', 'O', Caron),
+    ('ǒ', 'o', Caron),
+    ('Ǔ', 'U', Caron),
+    ('ǔ', 'u', Caron),
+    ('Ǖ', 'Ü', Macron),
+    ('ǖ', 'ü', Macron),
+    ('Ǘ', 'Ü', Acute),
+    ('ǘ', 'ü', Acute),
+    ('Ǚ', 'Ü', Caron),
+    ('ǚ', 'ü', Caron),
+    ('Ǜ', 'Ü', Grave),
+    ('ǜ', 'ü', Grave),
+    ('Ǟ', 'Ä', Macron),
+    ('ǟ', 'ä', Macron),
+    ('Ǧ', 'G', Caron),
+    ('ǧ', 'g', Caron),
+    ('Ǩ', 'K', Caron),
+    ('ǩ', 'k', Caron),
+    ('ǰ', 'j', Caron),
+    ('Ǵ', 'G', Acute),
+    ('ǵ', 'g', Acute),
+    ('Ǹ', 'N', Grave),
+    ('ǹ', 'n', Grave),
+    ('Ǻ', 'Å', Acute),
+    ('ǻ', 'å', Acute),
+    ('Ȟ', 'H', Caron),
+    ('ȟ', 'h', Caron),
+    ('Ȩ', 'E', Cedilla),
+    ('ȩ', 'e', Cedilla),
+    ('Ȫ', 'Ö', Macron),
+    ('ȫ', 'ö', Macron),
+    ('Ȭ', 'Õ', Macron),
+    ('ȭ', 'õ', Macron),
+    ('Ȳ', 'Y', Macron),
+    ('ȳ', 'y', Macron),
+    ('Ḉ', 'Ç', Acute),
+    ('ḉ', 'ç', Acute),
+    ('Ḑ', 'D', Cedilla),
+    ('ḑ', 'd', Cedilla),
+    ('Ḕ', 'Ē', Grave),
+    ('ḕ', 'ē', Grave),
+    ('Ḗ', 'Ē', Acute),
+    ('ḗ', 'ē', Acute),
+    ('Ḝ', 'Ȩ', Breve),
+    ('ḝ', 'ȩ', Breve),
+    ('Ḡ', 'G', Macron),
+    ('ḡ', 'g', Macron),
+    ('Ḧ', 'H', Diaeresis),
+    ('ḧ', 'h', Diaeresis),
+    ('Ḩ', 'H', Cedilla),
+    ('ḩ', 'h', Cedilla),
+    ('Ḯ', 'Ï', Acute),
+    ('ḯ', 'ï', Acute),
+    ('Ḱ', 'K', Acute),
+    ('ḱ', 'k', Acute),
+    ('Ḿ', 'M', Acute),
+    ('ḿ', 'm', Acute),
+    ('Ṍ', 'Õ', Acute),
+    ('ṍ', 'õ', Acute),
+    ('Ṏ', 'Õ', Diaeresis),
+    ('ṏ', 'õ', Diaeresis),
+    ('Ṑ', 'Ō', Grave),
+    ('ṑ', 'ō', Grave),
+    ('Ṓ', 'Ō', Acute),
+    ('ṓ', 'ō', Acute),
+    ('Ṕ', 'P', Acute),
+    ('ṕ', 'p', Acute),
+    ('Ṹ', 'Ũ', Acute),
+    ('ṹ', 'ũ', Acute),
+    ('Ṻ', 'Ū', Diaeresis),
+    ('ṻ', 'ū', Diaeresis),
+    ('Ṽ', 'V', Tilde),
+    ('ṽ', 'v', Tilde),
+    ('Ẁ', 'W', Grave),
+    ('ẁ', 'w', Grave),
+    ('Ẃ', 'W', Acute),
+    ('ẃ', 'w', Acute),
+    ('Ẅ', 'W', Diaeresis),
+    ('ẅ', 'w', Diaeresis),
+    ('Ẍ', 'X', Diaeresis),
+    ('ẍ', 'x', Diaeresis),
+    ('Ẑ', 'Z', Circumflex),
+    ('ẑ', 'z', Circumflex),
+    ('ẗ', 't', Diaeresis),
+    ('ẘ', 'w', Ring),
+    ('ẙ', 'y', Ring),
+    ('Ấ', 'Â', Acute),
+    ('ấ', 'â', Acute),
+    ('Ầ', 'Â', Grave),
+    ('ầ', 'â', Grave),
+    ('Ẫ', 'Â', Tilde),
+    ('ẫ', 'â', Tilde),
+    ('Ắ', 'Ă', Acute),
+    ('ắ', 'ă', Acute),
+    ('Ằ', 'Ă', Grave),
+    ('ằ', 'ă', Grave),
+    ('Ẵ', 'Ă', Tilde),
+    ('ẵ', 'ă', Tilde),
+    ('Ẽ', 'E', Tilde),
+    ('ẽ', 'e', Tilde),
+    ('Ế', 'Ê', Acute),
+    ('ế', 'ê', Acute),
+    ('Ề', 'Ê', Grave),
+    ('ề', 'ê', Grave),
+    ('Ễ', 'Ê', Tilde),
+    ('ễ', 'ê', Tilde),
+    ('Ố', 'Ô', Acute),
+    ('ố', 'ô', Acute),
+    ('Ồ', 'Ô', Grave),
+    ('ồ', 'ô', Grave),
+    ('Ỗ', 'Ô', Tilde),
+    ('ỗ', 'ô', Tilde),
+    ('Ỳ', 'Y', Grave),
+    ('ỳ', 'y', Grave),
+    ('Ỹ', 'Y', Tilde),
+    ('ỹ', 'y', Tilde),
+];
+
+/// The letter and accent `letter` is made from
+pub(crate) fn decompose(letter: char) -> Option<(char, Accent)> {
+    COMPOSED
+        .binary_search_by_key(&letter, |&(composed, ..)| composed)
+        .ok()
+        .map(|i| (COMPOSED[i].1, COMPOSED[i].2))
+}
+
+/// `letter` with `accent` put on it
+pub(crate) fn compose(letter: char, accent: Accent) -> Option<char> {
+    COMPOSED
+        .iter()
+        .find(|&&(_, base, a)| base == letter && a == accent)
+        .map(|&(composed, ..)| composed)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        compose::{compose, decompose, COMPOSED},
+        Accent::*,
+    };
+
+    #[test]
+    fn table() {
+        assert!(COMPOSED.is_sorted_by_key(|&(composed, ..)| composed));
+        for (composed, base, accent) in COMPOSED {
+            assert_eq!(decompose(composed), Some((base, accent)));
+            assert_eq!(compose(base, accent), Some(composed));
+        }
+        assert_eq!(decompose('é'), Some(('e', Acute)));
+        assert_eq!(decompose('ǖ'), Some(('ü', Macron)));
+        assert_eq!(decompose('e'), None);
+        assert_eq!(decompose('ø'), None);
+        assert_eq!(compose('x', Acute), None);
+    }
+}
@@ -1,14 +1,19 @@
 #![allow(dead_code)]
 
 use septivigntimal::*;
-use ternary::{trits::Trit, tryte::Tryte, word::Word};
+use ternary::{tryte::Tryte, word::Word};
 
-const fn isize(val: [[Trit; 3]; 3]) -> isize {
-    Tryte::from_arr_2d(val).isize()
+mod compose;
+pub mod string;
+
+pub use string::{decode, encode, TersciiStr, TersciiString};
+
+const fn isize(val: [Tribble; 3]) -> isize {
+    Tryte::from_tribbles(val).isize()
 }
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 #[repr(isize)]
 pub enum TERSCII {
@@ -293,6 +298,132 @@ impl From<TERSCII> for Tryte {
     }
 }
 
+/// A value that isn't one of the codes [`TERSCII`] defines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndefinedCodeError(pub isize);
+
+impl std::fmt::Display for UndefinedCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a TERSCII code", self.0)
+    }
+}
+
+impl std::error::Error for UndefinedCodeError {}
+
+impl TryFrom<Tryte> for TERSCII {
+    type Error = UndefinedCodeError;
+
+    fn try_from(value: Tryte) -> Result<Self, Self::Error> {
+        value.isize().try_into()
+    }
+}
+
+impl TERSCII {
+    /// Codes that don't stand for a character of their own: the control
+    /// codes, the shifts and the accents
+    pub fn is_control(&self) -> bool {
+        use TERSCII::*;
+
+        matches!(
+            self,
+            NULL | LE | ESC | LR | OP | SU | SD | ACK | NACK | RT | TAB | ADD_27_PREV | SUB_27_PREV
+                | ACCENTS_00 | ACCENTS_01 | ACCENTS_02 | ACCENTS_03 | ACCENTS_04 | ACCENTS_05
+                | ACCENTS_06 | ACCENTS_07 | ACCENTS_08 | ACCENTS_09 | ACCENTS_10
+        )
+    }
+
+    /// The character to show for this code, or `None` for a [control](TERSCII::is_control) code
+    pub fn glyph(self) -> Option<char> {
+        if self.is_control() { None } else { Some(self.into()) }
+    }
+
+    /// The accent an `ACCENTS_*` code puts on the character before it. They
+    /// are the first 11 codes of the `ZW` row.
+    pub fn accent(self) -> Option<Accent> {
+        let [_, row, column]: [Tribble; 3] = Tryte::from(self).into();
+        Accent::ALL.get(column.index()).copied().filter(|_| row == W)
+    }
+
+    pub fn from_accent(accent: Accent) -> TERSCII {
+        let index = Accent::ALL.iter().position(|&a| a == accent).unwrap();
+        TERSCII::try_from(Tryte::from([Z, W, Tribble::ALL[index]])).unwrap()
+    }
+
+    /// How far [`TERSCII::ADD_27_PREV`] and [`TERSCII::SUB_27_PREV`] move the
+    /// code before them. A row of the table is 27 codes, so they turn capitals
+    /// into lower case and back, and digits into the capitals `N` to `W`.
+    pub fn shift(self) -> Option<isize> {
+        match self {
+            TERSCII::ADD_27_PREV => Some(27),
+            TERSCII::SUB_27_PREV => Some(-27),
+            _ => None,
+        }
+    }
+
+    /// The code `by` above this one, if there is one
+    pub fn shifted(self, by: isize) -> Option<TERSCII> {
+        let value = Tryte::from(self).isize() + by;
+        if (Tryte::MIN.isize()..=Tryte::MAX.isize()).contains(&value) {
+            TERSCII::try_from(Tryte::from(value)).ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// What an `ACCENTS_*` code puts on the character before it, in code order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Accent {
+    Grave,
+    Acute,
+    Circumflex,
+    Tilde,
+    Diaeresis,
+    Ring,
+    Cedilla,
+    Caron,
+    Macron,
+    Breve,
+    DoubleAcute,
+}
+
+impl Accent {
+    pub const ALL: [Accent; 11] = [
+        Accent::Grave,
+        Accent::Acute,
+        Accent::Circumflex,
+        Accent::Tilde,
+        Accent::Diaeresis,
+        Accent::Ring,
+        Accent::Cedilla,
+        Accent::Caron,
+        Accent::Macron,
+        Accent::Breve,
+        Accent::DoubleAcute,
+    ];
+
+    /// The Unicode combining mark for the accent
+    pub const fn combining(self) -> char {
+        match self {
+            Accent::Grave => '\u{300}',
+            Accent::Acute => '\u{301}',
+            Accent::Circumflex => '\u{302}',
+            Accent::Tilde => '\u{303}',
+            Accent::Diaeresis => '\u{308}',
+            Accent::Ring => '\u{30a}',
+            Accent::Cedilla => '\u{327}',
+            Accent::Caron => '\u{30c}',
+            Accent::Macron => '\u{304}',
+            Accent::Breve => '\u{306}',
+            Accent::DoubleAcute => '\u{30b}',
+        }
+    }
+
+    pub fn from_combining(mark: char) -> Option<Accent> {
+        Accent::ALL.into_iter().find(|accent| accent.combining() == mark)
+    }
+}
+
 impl From<TERSCII> for Word {
     fn from(value: TERSCII) -> Self {
         let tryte: Tryte = value.into();
@@ -300,9 +431,13 @@ impl From<TERSCII> for Word {
     }
 }
 
-impl From<isize> for TERSCII {
-    fn from(value: isize) -> Self {
-        match value {
+/// The codes run from `ZZZ` to `ZVM`, so they all start with `Z` and have a
+/// second tribble between `Z` and `V`
+impl TryFrom<isize> for TERSCII {
+    type Error = UndefinedCodeError;
+
+    fn try_from(value: isize) -> Result<Self, Self::Error> {
+        Ok(match value {
             _ if value == const { isize([Z, Z, Z]) } => TERSCII::NULL,
             _ if value == const { isize([Z, Z, Y]) } => TERSCII::LE,
             _ if value == const { isize([Z, Z, X]) } => TERSCII::ESC,
@@ -440,26 +575,30 @@ impl From<isize> for TERSCII {
             _ if value == const { isize([Z, V, K]) } => TERSCII::ROUNDED_SE,
             _ if value == const { isize([Z, V, L]) } => TERSCII::ROUNDED_SW,
             _ if value == const { isize([Z, V, M]) } => TERSCII::NULLSET,
-            _ => todo!(),
-        }
+            _ => return Err(UndefinedCodeError(value)),
+        })
     }
 }
 
+/// Codes with no character of their own take an ASCII control character:
+/// `LR` is a carriage return, `OP` a backspace, `SU` and `SD` are device
+/// controls 1 and 2, `RT` is the record separator and the shifts are shift out
+/// for [`TERSCII::ADD_27_PREV`] and shift in for [`TERSCII::SUB_27_PREV`]. The
+/// accents are their Unicode combining marks.
 impl From<TERSCII> for char {
     fn from(value: TERSCII) -> Self {
         match value {
             TERSCII::NULL => '\0',
             TERSCII::LE => '\n',
             TERSCII::ESC => '\x1b',
-            TERSCII::LR => todo!(),
-            // NOTE: FIXME TEMP
+            TERSCII::LR => '\r',
             TERSCII::OP => '\x08',
-            TERSCII::SU => todo!(),
-            TERSCII::SD => todo!(),
+            TERSCII::SU => '\x11',
+            TERSCII::SD => '\x12',
             TERSCII::SP => ' ',
             TERSCII::ACK => '\x06',
-            TERSCII::NACK => todo!(),
-            TERSCII::RT => todo!(),
+            TERSCII::NACK => '\x15',
+            TERSCII::RT => '\x1e',
             TERSCII::TAB => '\t',
             TERSCII::Period => '.',
             TERSCII::Zero => '0',
@@ -502,7 +641,7 @@ impl From<TERSCII> for char {
             TERSCII::X => 'X',
             TERSCII::Y => 'Y',
             TERSCII::Z => 'Z',
-            TERSCII::ADD_27_PREV => todo!(),
+            TERSCII::ADD_27_PREV => '\x0e',
             TERSCII::a => 'a',
             TERSCII::b => 'b',
             TERSCII::c => 'c',
@@ -529,19 +668,19 @@ impl From<TERSCII> for char {
             TERSCII::x => 'x',
             TERSCII::y => 'y',
             TERSCII::z => 'z',
-            TERSCII::SUB_27_PREV => todo!(),
-
-            TERSCII::ACCENTS_00 => todo!(),
-            TERSCII::ACCENTS_01 => todo!(),
-            TERSCII::ACCENTS_02 => todo!(),
-            TERSCII::ACCENTS_03 => todo!(),
-            TERSCII::ACCENTS_04 => todo!(),
-            TERSCII::ACCENTS_05 => todo!(),
-            TERSCII::ACCENTS_06 => todo!(),
-            TERSCII::ACCENTS_07 => todo!(),
-            TERSCII::ACCENTS_08 => todo!(),
-            TERSCII::ACCENTS_09 => todo!(),
-            TERSCII::ACCENTS_10 => todo!(),
+            TERSCII::SUB_27_PREV => '\x0f',
+
+            TERSCII::ACCENTS_00 => Accent::ALL[0].combining(),
+            TERSCII::ACCENTS_01 => Accent::ALL[1].combining(),
+            TERSCII::ACCENTS_02 => Accent::ALL[2].combining(),
+            TERSCII::ACCENTS_03 => Accent::ALL[3].combining(),
+            TERSCII::ACCENTS_04 => Accent::ALL[4].combining(),
+            TERSCII::ACCENTS_05 => Accent::ALL[5].combining(),
+            TERSCII::ACCENTS_06 => Accent::ALL[6].combining(),
+            TERSCII::ACCENTS_07 => Accent::ALL[7].combining(),
+            TERSCII::ACCENTS_08 => Accent::ALL[8].combining(),
+            TERSCII::ACCENTS_09 => Accent::ALL[9].combining(),
+            TERSCII::ACCENTS_10 => Accent::ALL[10].combining(),
             TERSCII::LPAREN => '(',
             TERSCII::RPAREN => ')',
             TERSCII::LBRACE => '{',
@@ -586,26 +725,39 @@ impl From<TERSCII> for char {
             TERSCII::ROUNDED_SE => '╯',
             TERSCII::ROUNDED_SW => '╰',
             TERSCII::NULLSET => '∅',
-            #[allow(unreachable_patterns)]
-            _ => todo!(),
         }
     }
 }
 
-impl From<char> for TERSCII {
-    fn from(value: char) -> Self {
-        match value {
+/// A character with no TERSCII code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnmappedCharError(pub char);
+
+impl std::fmt::Display for UnmappedCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} has no TERSCII code", self.0)
+    }
+}
+
+impl std::error::Error for UnmappedCharError {}
+
+/// The reverse of `From<TERSCII> for char`, see there for the control codes
+impl TryFrom<char> for TERSCII {
+    type Error = UnmappedCharError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
             '\0' => TERSCII::NULL,
             '\n' => TERSCII::LE,
             '\x1b' => TERSCII::ESC,
-            // todo!() => TERSCII::LR,
+            '\r' => TERSCII::LR,
             '\x08' => TERSCII::OP,
-            // todo!() => TERSCII::SU,
-            // todo!() => TERSCII::SD,
+            '\x11' => TERSCII::SU,
+            '\x12' => TERSCII::SD,
             ' ' => TERSCII::SP,
             '\x06' => TERSCII::ACK,
-            // todo!() => TERSCII::NACK,
-            // todo!() => TERSCII::RT,
+            '\x15' => TERSCII::NACK,
+            '\x1e' => TERSCII::RT,
             '\t' => TERSCII::TAB,
             '.' => TERSCII::Period,
             '0' => TERSCII::Zero,
@@ -648,7 +800,7 @@ impl From<char> for TERSCII {
             'X' => TERSCII::X,
             'Y' => TERSCII::Y,
             'Z' => TERSCII::Z,
-            // todo!() => TERSCII::ADD_27_PREV,
+            '\x0e' => TERSCII::ADD_27_PREV,
             'a' => TERSCII::a,
             'b' => TERSCII::b,
             'c' => TERSCII::c,
@@ -675,18 +827,7 @@ impl From<char> for TERSCII {
             'x' => TERSCII::x,
             'y' => TERSCII::y,
             'z' => TERSCII::z,
-            // todo!() => TERSCII::SUB_27_PREV,
-            // todo!() => TERSCII::ACCENTS_00,
-            // todo!() => TERSCII::ACCENTS_01,
-            // todo!() => TERSCII::ACCENTS_02,
-            // todo!() => TERSCII::ACCENTS_03,
-            // todo!() => TERSCII::ACCENTS_04,
-            // todo!() => TERSCII::ACCENTS_05,
-            // todo!() => TERSCII::ACCENTS_06,
-            // todo!() => TERSCII::ACCENTS_07,
-            // todo!() => TERSCII::ACCENTS_08,
-            // todo!() => TERSCII::ACCENTS_09,
-            // todo!() => TERSCII::ACCENTS_10,
+            '\x0f' => TERSCII::SUB_27_PREV,
             '(' => TERSCII::LPAREN,
             ')' => TERSCII::RPAREN,
             '{' => TERSCII::LBRACE,
@@ -731,9 +872,11 @@ impl From<char> for TERSCII {
             '╯' => TERSCII::ROUNDED_SE,
             '╰' => TERSCII::ROUNDED_SW,
             '∅' => TERSCII::NULLSET,
-            #[allow(unreachable_patterns)]
-            _ => todo!(),
-        }
+            _ => match Accent::from_combining(value) {
+                Some(accent) => TERSCII::from_accent(accent),
+                None => return Err(UnmappedCharError(value)),
+            },
+        })
     }
 }
 
@@ -746,4 +889,91 @@ mod tests {
         let null = TERSCII::NULL;
         println!("{null:?}");
     }
+    #[test]
+    fn try_from_tryte() {
+        assert_eq!(TERSCII::try_from(Tryte::from(TERSCII::A)), Ok(TERSCII::A));
+        assert_eq!(TERSCII::try_from(Tryte::from(TERSCII::NULLSET)), Ok(TERSCII::NULLSET));
+        assert_eq!(TERSCII::try_from(Tryte::MIN), Ok(TERSCII::NULL));
+        for tryte in [Tryte::ZERO, Tryte::MAX, [Z, U, Z].into(), [Y, Z, Z].into()] {
+            assert_eq!(TERSCII::try_from(tryte), Err(UndefinedCodeError(tryte.isize())));
+        }
+        // Every defined code converts, and nothing else does
+        let defined = (-9841..=9841)
+            .filter_map(|value| TERSCII::try_from(Tryte::from(value)).ok())
+            // None of them panic for want of a character
+            .map(TERSCII::glyph)
+            .count();
+        assert_eq!(defined, 5 * 27);
+    }
+    #[test]
+    fn try_from_isize() {
+        assert_eq!(TERSCII::try_from(Tryte::from(TERSCII::A).isize()), Ok(TERSCII::A));
+        for value in [0, 100_000, isize::MIN, isize::MAX] {
+            assert_eq!(TERSCII::try_from(value), Err(UndefinedCodeError(value)));
+        }
+    }
+
+    #[test]
+    fn glyph() {
+        assert_eq!(TERSCII::a.glyph(), Some('a'));
+        assert_eq!(TERSCII::SP.glyph(), Some(' '));
+        assert_eq!(TERSCII::VERT_LINE.glyph(), Some('│'));
+        assert_eq!(TERSCII::LE.glyph(), None);
+        assert_eq!(TERSCII::ACCENTS_04.glyph(), None);
+        assert_eq!(TERSCII::SUB_27_PREV.glyph(), None);
+    }
+
+    fn codes() -> impl Iterator<Item = TERSCII> {
+        (-9841..=9841).filter_map(|value| TERSCII::try_from(Tryte::from(value)).ok())
+    }
+
+    #[test]
+    fn chars() {
+        // Every code has its own character
+        for code in codes() {
+            assert_eq!(TERSCII::try_from(char::from(code)), Ok(code), "{code:?}");
+        }
+        assert_eq!(TERSCII::try_from('\r'), Ok(TERSCII::LR));
+        assert_eq!(TERSCII::try_from('\x0e'), Ok(TERSCII::ADD_27_PREV));
+        assert_eq!(char::from(TERSCII::ACCENTS_01), '\u{301}');
+        for c in ['é', '\x7f', '€', '\u{ffff}'] {
+            assert_eq!(TERSCII::try_from(c), Err(UnmappedCharError(c)));
+        }
+    }
+
+    #[test]
+    fn accents() {
+        let accents: Vec<_> = codes().filter_map(TERSCII::accent).collect();
+        assert_eq!(accents, Accent::ALL);
+        for accent in Accent::ALL {
+            let code = TERSCII::from_accent(accent);
+            assert_eq!(code.accent(), Some(accent));
+            assert!(code.is_control());
+            assert_eq!(Accent::from_combining(accent.combining()), Some(accent));
+        }
+        assert_eq!(TERSCII::from_accent(Accent::Grave), TERSCII::ACCENTS_00);
+        assert_eq!(TERSCII::from_accent(Accent::DoubleAcute), TERSCII::ACCENTS_10);
+        assert_eq!(TERSCII::LPAREN.accent(), None);
+        assert_eq!(TERSCII::Zero.accent(), None);
+    }
+
+    #[test]
+    fn shifts() {
+        let up = TERSCII::ADD_27_PREV.shift().unwrap();
+        let down = TERSCII::SUB_27_PREV.shift().unwrap();
+        assert_eq!(TERSCII::A.shifted(up), Some(TERSCII::a));
+        assert_eq!(TERSCII::Z.shifted(up), Some(TERSCII::z));
+        assert_eq!(TERSCII::q.shifted(down), Some(TERSCII::Q));
+        assert_eq!(TERSCII::Zero.shifted(up), Some(TERSCII::N));
+        assert_eq!(TERSCII::Plus.shifted(up), Some(TERSCII::ADD_27_PREV));
+        // Off either end of the table
+        assert_eq!(TERSCII::NULL.shifted(down), None);
+        assert_eq!(TERSCII::NULLSET.shifted(up), None);
+        assert_eq!(TERSCII::A.shift(), None);
+        for code in codes() {
+            if let Some(shifted) = code.shifted(up) {
+                assert_eq!(shifted.shifted(down), Some(code));
+            }
+        }
+    }
 }
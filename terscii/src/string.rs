@@ -0,0 +1,448 @@
+//! TERSCII text, and moving it to and from UTF-8.
+//!
+//! [`encode`] writes an accented letter as the letter followed by its
+//! accents, `é` is `e` then the acute `ACCENTS_*` code, and [`decode`] puts
+//! them back together. Decoding also applies [`TERSCII::ADD_27_PREV`] and
+//! [`TERSCII::SUB_27_PREV`] to the code before them, so `A` followed by
+//! `ADD_27_PREV` is `a`. Encoding never writes a shift.
+//!
+//! TERSCII has no way to tell `é` from `e` and a combining acute, so the
+//! exchange is lossless up to composition, see [`encode`]. Words from
+//! [`pack`] are padded with a tryte that isn't a code, so any text, `NULL`s
+//! and all, comes back from [`TersciiString::from_words`] as it went in.
+//!
+//! ```
+//! use terscii::string::{decode, encode};
+//!
+//! let text = encode("Ça coûte 5€").unwrap_err();
+//! assert_eq!(text.char, '€');
+//! let text = encode("Ça coûte 5$").unwrap();
+//! assert_eq!(text.len(), 13);
+//! assert_eq!(decode(text.as_trytes()).unwrap(), "Ça coûte 5$");
+//! ```
+
+use std::{borrow::Borrow, fmt, ops::Deref};
+
+use ternary::{tryte::Tryte, word::Word};
+
+use crate::{compose, Accent, UndefinedCodeError, TERSCII};
+
+/// A character [`encode`] can't write, and its byte offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeError {
+    pub char: char,
+    pub index: usize,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} at byte {} has no TERSCII encoding",
+            self.char, self.index
+        )
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Why [`decode`] couldn't read a tryte, and which one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// Not a TERSCII code
+    Undefined { index: usize },
+    /// A shift with nothing before it, or that goes off the table
+    Shift { index: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Undefined { index } => write!(f, "tryte {index} isn't a TERSCII code"),
+            DecodeError::Shift { index } => {
+                write!(f, "the shift at tryte {index} has nothing to shift")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Borrowed TERSCII text, trytes that are all TERSCII codes
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct TersciiStr([Tryte]);
+
+/// Owned TERSCII text
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct TersciiString(Vec<Tryte>);
+
+impl TersciiStr {
+    pub fn from_trytes(trytes: &[Tryte]) -> Result<&TersciiStr, UndefinedCodeError> {
+        for &tryte in trytes {
+            TERSCII::try_from(tryte)?;
+        }
+        // SAFETY: TersciiStr is a transparent wrapper around [Tryte]
+        Ok(unsafe { &*(trytes as *const [Tryte] as *const TersciiStr) })
+    }
+
+    pub fn as_trytes(&self) -> &[Tryte] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn codes(&self) -> impl DoubleEndedIterator<Item = TERSCII> + '_ {
+        self.0
+            .iter()
+            .map(|&tryte| TERSCII::try_from(tryte).unwrap())
+    }
+
+    /// See [`decode`]
+    pub fn decode(&self) -> Result<String, DecodeError> {
+        decode(&self.0)
+    }
+
+    /// See [`pack`]
+    pub fn to_words(&self) -> Vec<Word> {
+        pack(&self.0)
+    }
+}
+
+impl TersciiString {
+    pub fn new() -> TersciiString {
+        TersciiString(Vec::new())
+    }
+
+    pub fn from_trytes(trytes: Vec<Tryte>) -> Result<TersciiString, UndefinedCodeError> {
+        TersciiStr::from_trytes(&trytes)?;
+        Ok(TersciiString(trytes))
+    }
+
+    /// Reads text that [`TersciiStr::to_words`] wrote, dropping the
+    /// [`PADDING`] at the end of the last word
+    pub fn from_words(words: &[Word]) -> Result<TersciiString, UndefinedCodeError> {
+        let mut trytes = unpack(words);
+        let text = trytes.len().saturating_sub(2);
+        let len = trytes[text..]
+            .iter()
+            .position(|&tryte| tryte == PADDING)
+            .map_or(trytes.len(), |i| text + i);
+        trytes.truncate(len);
+        TersciiString::from_trytes(trytes)
+    }
+
+    pub fn push(&mut self, code: TERSCII) {
+        self.0.push(code.into());
+    }
+
+    pub fn into_trytes(self) -> Vec<Tryte> {
+        self.0
+    }
+}
+
+impl Deref for TersciiString {
+    type Target = TersciiStr;
+
+    fn deref(&self) -> &TersciiStr {
+        // SAFETY: every tryte was checked or pushed as a code
+        unsafe { &*(self.0.as_slice() as *const [Tryte] as *const TersciiStr) }
+    }
+}
+
+impl Borrow<TersciiStr> for TersciiString {
+    fn borrow(&self) -> &TersciiStr {
+        self
+    }
+}
+
+impl AsRef<TersciiStr> for TersciiString {
+    fn as_ref(&self) -> &TersciiStr {
+        self
+    }
+}
+
+impl ToOwned for TersciiStr {
+    type Owned = TersciiString;
+
+    fn to_owned(&self) -> TersciiString {
+        TersciiString(self.0.to_vec())
+    }
+}
+
+impl FromIterator<TERSCII> for TersciiString {
+    fn from_iter<T: IntoIterator<Item = TERSCII>>(iter: T) -> Self {
+        TersciiString(iter.into_iter().map(Tryte::from).collect())
+    }
+}
+
+/// Writes `text` as TERSCII, see the [module docs](self). Only fails for a
+/// character with no code, or for shift out and shift in, which would shift
+/// the character before them instead of standing for themselves.
+///
+/// A precomposed letter and the same letter followed by combining accents
+/// encode the same, so [`decode`] gives back the composed form: `e\u{301}`
+/// comes back as `é`. Text that is already composed, or that has accents
+/// with no composed form, round-trips exactly.
+pub fn encode(text: &str) -> Result<TersciiString, EncodeError> {
+    let mut string = TersciiString::new();
+    for (index, char) in text.char_indices() {
+        let error = EncodeError { char, index };
+        // Accents are pulled off the end, so they come out backwards
+        let mut accents = Vec::new();
+        let mut letter = char;
+        while let Some((base, accent)) = compose::decompose(letter) {
+            accents.push(accent);
+            letter = base;
+        }
+
+        let code = TERSCII::try_from(letter).map_err(|_| error)?;
+        if code.shift().is_some() {
+            return Err(error);
+        }
+        string.push(code);
+        string.0.extend(
+            accents
+                .into_iter()
+                .rev()
+                .map(|accent| Tryte::from(TERSCII::from_accent(accent))),
+        );
+    }
+    Ok(string)
+}
+
+/// Reads TERSCII text, see the [module docs](self). Accents are put on the
+/// letter before them for as long as there is a composed letter for it, and
+/// the rest are written as combining marks after it, so the text is composed
+/// whichever way it was written.
+pub fn decode(trytes: &[Tryte]) -> Result<String, DecodeError> {
+    // Each character with the accents on it. A shift changes the code, and
+    // keeps the accents.
+    let mut chars: Vec<(Option<TERSCII>, Vec<Accent>)> = Vec::new();
+    for (index, &tryte) in trytes.iter().enumerate() {
+        let code = TERSCII::try_from(tryte).map_err(|_| DecodeError::Undefined { index })?;
+        match (code.shift(), code.accent(), chars.last_mut()) {
+            (Some(by), _, last) => {
+                let code = last
+                    .and_then(|(code, _)| code.as_mut())
+                    .ok_or(DecodeError::Shift { index })?;
+                *code = code.shifted(by).ok_or(DecodeError::Shift { index })?;
+            }
+            (_, Some(accent), Some((_, accents))) => accents.push(accent),
+            (_, Some(accent), None) => chars.push((None, vec![accent])),
+            _ => chars.push((Some(code), Vec::new())),
+        }
+    }
+
+    let mut text = String::new();
+    for (code, accents) in chars {
+        let mut letter = code.map(char::from);
+        // Once an accent doesn't compose, it and the rest are combining marks
+        let mut marks = String::new();
+        for accent in accents {
+            match letter
+                .filter(|_| marks.is_empty())
+                .and_then(|l| compose::compose(l, accent))
+            {
+                Some(composed) => letter = Some(composed),
+                None => marks.push(accent.combining()),
+            }
+        }
+        text.extend(letter);
+        text.push_str(&marks);
+    }
+    Ok(text)
+}
+
+/// What [`pack`] fills the last word out with. It isn't a TERSCII code, so it
+/// can't be mistaken for text.
+pub const PADDING: Tryte = Tryte::ZERO;
+
+/// Three trytes to a word, the first in the low tryte. The last word is
+/// padded with [`PADDING`].
+pub fn pack(trytes: &[Tryte]) -> Vec<Word> {
+    trytes
+        .chunks(3)
+        .map(|chunk| {
+            let mut word = [PADDING; 3];
+            word[..chunk.len()].copy_from_slice(chunk);
+            Word::from(word)
+        })
+        .collect()
+}
+
+/// Every tryte of `words`, padding and all
+pub fn unpack(words: &[Word]) -> Vec<Tryte> {
+    words
+        .iter()
+        .flat_map(|&word| <[Tryte; 3]>::from(word))
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ternary::{tryte::Tryte, word::Word};
+
+    use crate::{
+        string::{
+            decode, encode, pack, unpack, DecodeError, EncodeError, TersciiStr, TersciiString,
+            PADDING,
+        },
+        Accent, UndefinedCodeError, TERSCII,
+    };
+
+    fn trytes(codes: &[TERSCII]) -> Vec<Tryte> {
+        codes.iter().map(|&code| code.into()).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        for text in [
+            "",
+            "Hello, world!\n",
+            "Ça coûte très cher à Zürich",
+            "Ångström, Dvořák?",
+            "ǖ ḉ ő Ş ĵ",
+            "x\u{301} \u{308}e é\u{301}",
+            "┌─┐\n│∅│\n└─┘",
+        ] {
+            assert_eq!(
+                encode(text).unwrap().decode(),
+                Ok(text.to_string()),
+                "{text:?}"
+            );
+        }
+        // Letters that aren't an ASCII letter with accents
+        assert_eq!(
+            encode("Łódź"),
+            Err(EncodeError {
+                char: 'Ł',
+                index: 0
+            })
+        );
+        assert_eq!(encode("æ").unwrap_err().char, 'æ');
+    }
+
+    #[test]
+    fn accents() {
+        use TERSCII::*;
+
+        assert_eq!(encode("é").unwrap().into_trytes(), trytes(&[e, ACCENTS_01]));
+        assert_eq!(
+            encode("ḉ").unwrap().into_trytes(),
+            trytes(&[c, ACCENTS_06, ACCENTS_01])
+        );
+        // Decomposed text comes back composed
+        assert_eq!(encode("e\u{301}"), encode("é"));
+        assert_eq!(encode("e\u{301}").unwrap().decode(), Ok("é".to_string()));
+        assert_eq!(
+            encode("ç\u{301}").unwrap().decode(),
+            Ok("ḉ".to_string())
+        );
+        assert_eq!(
+            decode(&trytes(&[ACCENTS_04, A])),
+            Ok("\u{308}A".to_string())
+        );
+        assert_eq!(
+            decode(&trytes(&[TERSCII::from_accent(Accent::Ring), SP])),
+            Ok("\u{30a} ".to_string())
+        );
+    }
+
+    #[test]
+    fn shifts() {
+        use TERSCII::*;
+
+        assert_eq!(decode(&trytes(&[A, ADD_27_PREV, b])), Ok("ab".to_string()));
+        assert_eq!(
+            decode(&trytes(&[q, SUB_27_PREV, SUB_27_PREV])),
+            Ok("3".to_string())
+        );
+        // The accents stay on the shifted letter
+        assert_eq!(
+            decode(&trytes(&[E, ACCENTS_00, ADD_27_PREV])),
+            Ok("è".to_string())
+        );
+        assert_eq!(
+            decode(&trytes(&[ADD_27_PREV])),
+            Err(DecodeError::Shift { index: 0 })
+        );
+        assert_eq!(
+            decode(&trytes(&[ACCENTS_02, SUB_27_PREV])),
+            Err(DecodeError::Shift { index: 1 })
+        );
+        assert_eq!(
+            decode(&trytes(&[A, NULL, SUB_27_PREV])),
+            Err(DecodeError::Shift { index: 2 })
+        );
+        // Shift out and in can't be written as themselves
+        assert_eq!(
+            encode("a\x0e"),
+            Err(EncodeError {
+                char: '\x0e',
+                index: 1
+            })
+        );
+        assert_eq!(
+            encode("é€"),
+            Err(EncodeError {
+                char: '€',
+                index: 2
+            })
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let string: TersciiString = [TERSCII::H, TERSCII::i].into_iter().collect();
+        assert_eq!(string.len(), 2);
+        assert_eq!(string.codes().collect::<Vec<_>>(), [TERSCII::H, TERSCII::i]);
+        let borrowed: &TersciiStr = &string;
+        assert_eq!(borrowed.to_owned(), string);
+        assert_eq!(TersciiStr::from_trytes(string.as_trytes()), Ok(borrowed));
+        assert_eq!(
+            TersciiStr::from_trytes(&[Tryte::ZERO]),
+            Err(UndefinedCodeError(0))
+        );
+        assert_eq!(
+            decode(&[TERSCII::A.into(), Tryte::MAX]),
+            Err(DecodeError::Undefined { index: 1 })
+        );
+    }
+
+    #[test]
+    fn words() {
+        let string = encode("Zürich").unwrap();
+        let words = string.to_words();
+        assert_eq!(words.len(), 3);
+        assert_eq!(
+            words[0],
+            Word::from([TERSCII::Z, TERSCII::u, TERSCII::ACCENTS_04].map(Tryte::from))
+        );
+        assert_eq!(unpack(&words)[7..], [PADDING; 2]);
+        assert_eq!(TersciiString::from_words(&words), Ok(string));
+        assert_eq!(pack(&[]), []);
+        assert_eq!(TersciiString::from_words(&[]), Ok(TersciiString::new()));
+        // NULLs are text, wherever they are
+        for text in ["\0", "a\0", "ab\0", "\0\0\0", "\0a\0\0"] {
+            let string = encode(text).unwrap();
+            let words = string.to_words();
+            assert_eq!(TersciiString::from_words(&words), Ok(string), "{text:?}");
+        }
+        // Padding is only at the end
+        assert_eq!(
+            TersciiString::from_words(&[Word::ZERO, Word::from(TERSCII::A as isize)]),
+            Err(UndefinedCodeError(0))
+        );
+        assert_eq!(
+            TersciiString::from_words(&[Word::ZERO]),
+            Err(UndefinedCodeError(0))
+        );
+    }
+}
@@ -1,8 +1,22 @@
 //! a.out inspired ternary object file
+//!
+//! On disk a tobj is its header, one word per field holding the field as an
+//! integer, followed by the sections back to back in the order of
+//! [`Section`]. Sizes are in words, and `bss` takes no space.
+//!
+//! The `sym` section is a run of [`Symbol`]s, each its address, the number of
+//! characters in its name, then the name in TERSCII, three characters to a
+//! word.
+
+use std::fmt;
 
 use ternary::word::Word;
+use terscii::string::{self, EncodeError};
 type Addr = isize;
 
+/// a.out's `OMAGIC`
+pub const MAGIC: u64 = 0o407;
+
 #[repr(C)]
 pub struct TObj<'data> {
     header: TObjHeader,
@@ -20,3 +34,217 @@ pub struct TObjHeader {
     textr:  u64,
     datar:  u64,
 }
+
+/// The parts of a tobj with contents, in the order they're stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Text,
+    Data,
+    TextReloc,
+    DataReloc,
+    Sym,
+}
+
+impl Section {
+    pub const ALL: [Section; 5] =
+        [Section::Text, Section::Data, Section::TextReloc, Section::DataReloc, Section::Sym];
+
+    /// Name as the command line tools take it
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Text => "text",
+            Section::Data => "data",
+            Section::TextReloc => "textr",
+            Section::DataReloc => "datar",
+            Section::Sym => "sym",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Section> {
+        Section::ALL.into_iter().find(|section| section.name() == name)
+    }
+}
+
+/// Why a run of words isn't a tobj
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TObjError {
+    /// Fewer words than the header takes
+    Truncated,
+    BadMagic,
+    /// A header field that is negative
+    BadField,
+    /// The sections need more words than there are
+    TooShort { needed: u64, found: u64 },
+    /// The symbol starting at this word of the `sym` section runs past its
+    /// end, or its name isn't TERSCII
+    BadSymbol(usize),
+}
+
+impl fmt::Display for TObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TObjError::Truncated => f.write_str("too short for a tobj header"),
+            TObjError::BadMagic => f.write_str("not a tobj, the magic number is wrong"),
+            TObjError::BadField => f.write_str("negative size in the tobj header"),
+            TObjError::TooShort { needed, found } => {
+                write!(f, "tobj sections need {needed} words but there are {found}")
+            }
+            TObjError::BadSymbol(index) => write!(f, "bad symbol at word {index} of the sym section"),
+        }
+    }
+}
+
+impl std::error::Error for TObjError {}
+
+/// A named address from the `sym` section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub addr: Addr,
+    pub name: String,
+}
+
+impl Symbol {
+    pub fn new(addr: Addr, name: impl Into<String>) -> Symbol {
+        Symbol { addr, name: name.into() }
+    }
+
+    /// The symbol as it's stored in the `sym` section
+    pub fn to_words(&self) -> Result<Vec<Word>, EncodeError> {
+        let name = string::encode(&self.name)?;
+        let mut words = vec![Word::from(self.addr), Word::from(name.len() as isize)];
+        words.extend(name.to_words());
+        Ok(words)
+    }
+}
+
+impl TObjHeader {
+    const LEN: usize = 8;
+
+    /// Words in a section
+    fn size(&self, section: Section) -> u64 {
+        match section {
+            Section::Text => self.text,
+            Section::Data => self.data,
+            Section::TextReloc => self.textr,
+            Section::DataReloc => self.datar,
+            Section::Sym => self.sym,
+        }
+    }
+}
+
+impl<'data> TObj<'data> {
+    /// Reads the header, and checks the sections fit in what's left
+    pub fn parse(words: &'data [Word]) -> Result<TObj<'data>, TObjError> {
+        if words.len() < TObjHeader::LEN {
+            return Err(TObjError::Truncated);
+        }
+        let (fields, data) = words.split_at(TObjHeader::LEN);
+        let fields = fields
+            .iter()
+            .map(|&word| u64::try_from(isize::from(word)).map_err(|_| TObjError::BadField))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [magic, text, data_size, bss, sym, entry, textr, datar] = fields[..] else {
+            unreachable!()
+        };
+        if magic != MAGIC {
+            return Err(TObjError::BadMagic);
+        }
+        let header = TObjHeader { magic, text, data: data_size, bss, sym, entry, textr, datar };
+
+        let needed = Section::ALL.into_iter().map(|section| header.size(section)).sum();
+        let found = data.len() as u64;
+        if needed > found {
+            return Err(TObjError::TooShort { needed, found });
+        }
+        Ok(TObj { header, data })
+    }
+
+    /// The words of a section
+    pub fn section(&self, section: Section) -> &'data [Word] {
+        let start: u64 = Section::ALL
+            .into_iter()
+            .take_while(|&s| s != section)
+            .map(|s| self.header.size(s))
+            .sum();
+        let end = start + self.header.size(section);
+        &self.data[start as usize..end as usize]
+    }
+
+    /// Where execution starts
+    pub fn entry(&self) -> Addr {
+        self.header.entry as Addr
+    }
+
+    /// Reads the `sym` section
+    pub fn symbols(&self) -> Result<Vec<Symbol>, TObjError> {
+        let words = self.section(Section::Sym);
+        let mut symbols = Vec::new();
+        let mut index = 0;
+        while index < words.len() {
+            let bad = TObjError::BadSymbol(index);
+            let [addr, len] = words.get(index..index + 2).ok_or(bad)? else {
+                unreachable!()
+            };
+            let len = usize::try_from(isize::from(*len)).map_err(|_| bad)?;
+            let start = index + 2;
+            let name = words.get(start..start + len.div_ceil(3)).ok_or(bad)?;
+            let name = string::decode(&string::unpack(name)[..len]).map_err(|_| bad)?;
+            symbols.push(Symbol { addr: isize::from(*addr), name });
+            index = start + len.div_ceil(3);
+        }
+        Ok(symbols)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use ternary::word::Word;
+
+    use crate::{Section, Symbol, TObj, TObjError, MAGIC};
+
+    fn header(sizes: [isize; 7]) -> Vec<Word> {
+        [MAGIC as isize].into_iter().chain(sizes).map(Word::from).collect()
+    }
+
+    #[test]
+    fn sections() {
+        // 2 text, 1 data, 4 bss, 1 sym, entry 3, no relocations
+        let mut words = header([2, 1, 4, 1, 3, 0, 0]);
+        words.extend([10, 11, 20, 30].map(Word::from));
+        let tobj = TObj::parse(&words).unwrap();
+
+        assert_eq!(tobj.section(Section::Text), &[10, 11].map(Word::from));
+        assert_eq!(tobj.section(Section::Data), &[Word::from(20)]);
+        assert_eq!(tobj.section(Section::TextReloc), &[]);
+        assert_eq!(tobj.section(Section::Sym), &[Word::from(30)]);
+        assert_eq!(tobj.entry(), 3);
+        assert_eq!(Section::from_name("textr"), Some(Section::TextReloc));
+        assert_eq!(Section::from_name("bss"), None);
+    }
+
+    #[test]
+    fn symbols() {
+        let symbols = [Symbol::new(0, "main"), Symbol::new(15, "fact"), Symbol::new(-3, "")];
+        let sym: Vec<Word> = symbols.iter().flat_map(|symbol| symbol.to_words().unwrap()).collect();
+        assert_eq!(sym.len(), 4 + 4 + 2);
+        let mut words = header([0, 0, 0, sym.len() as isize, 0, 0, 0]);
+        words.extend(&sym);
+        assert_eq!(TObj::parse(&words).unwrap().symbols(), Ok(symbols.to_vec()));
+
+        // The second name is 7 characters, but no words follow
+        let mut words = header([0, 0, 0, 4, 0, 0, 0]);
+        words.extend([0, 0, 5, 7].map(Word::from));
+        assert_eq!(TObj::parse(&words).unwrap().symbols(), Err(TObjError::BadSymbol(2)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(TObj::parse(&[Word::ZERO; 7]).err(), Some(TObjError::Truncated));
+        assert_eq!(TObj::parse(&[Word::ZERO; 8]).err(), Some(TObjError::BadMagic));
+        assert_eq!(TObj::parse(&header([-1, 0, 0, 0, 0, 0, 0])).err(), Some(TObjError::BadField));
+        assert_eq!(
+            TObj::parse(&header([2, 1, 0, 0, 0, 0, 0])).err(),
+            Some(TObjError::TooShort { needed: 3, found: 0 })
+        );
+    }
+}